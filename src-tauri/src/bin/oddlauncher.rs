@@ -0,0 +1,5 @@
+// Headless command-line interface to OddLauncher (no webview)
+
+fn main() {
+  std::process::exit(app_lib::cli::run());
+}
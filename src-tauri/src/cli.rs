@@ -0,0 +1,1172 @@
+use crate::commands::browser::{open_url_in_browser, wait_for_port_ready};
+use crate::commands::config::{
+    ensure_config_dir_exists, export_config, find_app, get_config_dir, read_global_config,
+    store_global_config, write_global_config, ExportOptions, CONFIG_DIR_FLAG, PORTABLE_FLAG,
+};
+use crate::commands::dev_env::wrap_in_dev_environment;
+use crate::commands::environment::{resolve_launch_fields, LaunchFields};
+use crate::commands::hooks::{hook_timeout, run_hooks, HookEnvironment, HookOutcome, HookReporter, HookStage};
+use crate::commands::instance::{has_instance_actions, ControlClient};
+use crate::commands::process::{parse_step_marker, platform_utils, prepare_launch, remove_launch_script, terminate_process_tree};
+use crate::commands::secrets::{known_secret_values, redact, resolve_app_secrets, SecretStore};
+use crate::commands::shell_env::ShellEnvironment;
+use crate::commands::templates::create_app_from_template;
+use crate::commands::terminal::WslTarget;
+use crate::commands::validation::ensure_valid_app;
+use crate::models::app::{AppConfig, AppError, AppResult, AppType, CustomShell, GlobalConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+// Headless command-line interface: manages apps without the webview.
+// Processes started here run detached; their state is tracked in files under the config directory.
+// While the launcher itself is running, starts and stops go through its control API instead.
+
+const USAGE: &str = "\
+Usage: oddlauncher [--config-dir <path> | --portable] <command> [options]
+
+Commands:
+  list [--json]                     List configured apps
+  start <app> [--profile <name>] [--open]
+                                    Start an app in the background (or in the running launcher)
+  stop <app>                        Stop an app started with `start` or in the running launcher
+  status [<app>] [--json]           Show which apps are running
+  logs <app> [-f] [-n <lines>]      Print an app's output (-f follows new output)
+  add --name <name> [--command <cmd>]... [--dir <path>] [--url <url>] [--port <port>] [--tag <tag>]...
+  add --template <id> [--param <key=value>]...
+                                    Add an app from options or a template
+  export [--app <app>]... [--include-settings] [--strip-secrets] [--strip-paths] [-o <file>]
+                                    Export apps as JSON
+  --start <app> | --stop <app> [--profile <name>]
+                                    Ask the running launcher to start or stop apps, as a second launch would
+  help                              Show this help
+
+Apps can be referred to by ID or by name (case-insensitive).";
+
+/// How long `start` watches a new process for an immediate failure
+const STARTUP_CHECK_DELAY: Duration = Duration::from_millis(500);
+
+/// Interval between reads when following a log file
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/**
+ * Run state of an app started from the command line (~/.oddlauncher/run/<app id>.json)
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RunState {
+    app_id: String,
+    pid: u32,
+    /// Process group the app runs in (Unix only)
+    pgid: Option<i32>,
+    started_at: String,
+    profile: Option<String>,
+    log_file: String,
+    /// Launch script the app runs from, in script mode
+    #[serde(default)]
+    script_file: Option<String>,
+    /// When the process started, to recognize the PID being reused by an unrelated process
+    #[serde(default)]
+    process_start: Option<String>,
+}
+
+/// Entry point for the `oddlauncher` binary; returns the process exit code
+pub fn run() -> i32 {
+    let args = strip_global_flags(std::env::args().skip(1).collect());
+
+    match dispatch(&args) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("error: {}", e.message);
+            1
+        }
+    }
+}
+
+/// Remove the config location flags, which are read by the config module itself
+fn strip_global_flags(args: Vec<String>) -> Vec<String> {
+    let mut remaining = Vec::new();
+    let mut iter = args.into_iter();
+
+    while let Some(arg) = iter.next() {
+        if arg == CONFIG_DIR_FLAG {
+            iter.next();
+        } else if arg != PORTABLE_FLAG && !arg.starts_with(&format!("{}=", CONFIG_DIR_FLAG)) {
+            remaining.push(arg);
+        }
+    }
+
+    remaining
+}
+
+fn dispatch(args: &[String]) -> AppResult<()> {
+    let Some((command, rest)) = args.split_first() else {
+        println!("{}", USAGE);
+        return Ok(());
+    };
+    // The launcher's own --start/--stop go to the running launcher, as they would from a second launch
+    if command.starts_with("--") && has_instance_actions(args) {
+        return forward_instance_args(args);
+    }
+    let mut options = Options::parse(rest)?;
+
+    match command.as_str() {
+        "list" => list_apps(&options),
+        "start" => start_app(&mut options),
+        "stop" => stop_app(&mut options),
+        "status" => show_status(&mut options),
+        "logs" => show_logs(&mut options),
+        "add" => add_app(&options),
+        "export" => export_apps(&options),
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        other => Err(usage_error(&format!("Unknown command '{}'", other))),
+    }
+}
+
+/// Hand launcher arguments to the running launcher and report what it did with each
+fn forward_instance_args(args: &[String]) -> AppResult<()> {
+    let Some(mut client) = ControlClient::connect()? else {
+        return Err(AppError::new(
+            "LAUNCHER_NOT_RUNNING",
+            "OddLauncher isn't running; use `oddlauncher start <app>` to start an app without it",
+        ));
+    };
+
+    let results = client.call("handle_instance_args", serde_json::json!({ "args": args }))?;
+    let mut failures = 0;
+    for result in results.as_array().into_iter().flatten() {
+        let message = result["message"].as_str().or(result["error"].as_str()).unwrap_or_default();
+        if result["success"].as_bool().unwrap_or(false) {
+            if !message.is_empty() {
+                println!("{}", message);
+            }
+        } else {
+            failures += 1;
+            eprintln!("error: {}", message);
+        }
+    }
+
+    if failures > 0 {
+        return Err(AppError::new(
+            "INSTANCE_ACTION_ERROR",
+            &format!("{} of the requested actions failed", failures),
+        ));
+    }
+    Ok(())
+}
+
+fn usage_error(message: &str) -> AppError {
+    AppError::new("CLI_USAGE_ERROR", &format!("{}\n\n{}", message, USAGE))
+}
+
+/**
+ * Parsed command options: positional arguments, boolean flags and (repeatable) valued options
+ */
+struct Options {
+    positional: Vec<String>,
+    flags: Vec<String>,
+    values: HashMap<String, Vec<String>>,
+}
+
+/// Options that take a value; everything else starting with `-` is a flag
+const VALUE_OPTIONS: [&str; 13] = [
+    "--profile", "--name", "--command", "--dir", "--url", "--port", "--tag", "--template", "--param",
+    "--app", "-o", "--output", "-n",
+];
+
+impl Options {
+    fn parse(args: &[String]) -> AppResult<Options> {
+        let mut options = Options {
+            positional: Vec::new(),
+            flags: Vec::new(),
+            values: HashMap::new(),
+        };
+        let mut iter = args.iter();
+
+        while let Some(arg) = iter.next() {
+            if let Some((name, value)) = arg.split_once('=').filter(|(name, _)| VALUE_OPTIONS.contains(name)) {
+                options.values.entry(name.to_string()).or_default().push(value.to_string());
+            } else if VALUE_OPTIONS.contains(&arg.as_str()) {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| usage_error(&format!("Missing value for {}", arg)))?;
+                options.values.entry(arg.clone()).or_default().push(value.clone());
+            } else if arg.starts_with('-') && arg.len() > 1 {
+                options.flags.push(arg.clone());
+            } else {
+                options.positional.push(arg.clone());
+            }
+        }
+
+        Ok(options)
+    }
+
+    fn flag(&self, names: &[&str]) -> bool {
+        self.flags.iter().any(|f| names.contains(&f.as_str()))
+    }
+
+    fn value(&self, names: &[&str]) -> Option<String> {
+        names
+            .iter()
+            .filter_map(|name| self.values.get(*name))
+            .flatten()
+            .last()
+            .cloned()
+    }
+
+    fn all(&self, name: &str) -> Vec<String> {
+        self.values.get(name).cloned().unwrap_or_default()
+    }
+
+    fn take_app(&mut self) -> AppResult<String> {
+        if self.positional.is_empty() {
+            return Err(usage_error("Missing app ID or name"));
+        }
+        Ok(self.positional.remove(0))
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) -> AppResult<()> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| {
+        AppError::new("JSON_SERIALIZE_ERROR", &format!("Failed to serialize output: {}", e))
+    })?;
+    println!("{}", json);
+    Ok(())
+}
+
+fn list_apps(options: &Options) -> AppResult<()> {
+    let config = read_global_config()?;
+
+    if options.flag(&["--json"]) {
+        return print_json(&config.apps);
+    }
+
+    if config.apps.is_empty() {
+        println!("No apps configured");
+        return Ok(());
+    }
+
+    for app in &config.apps {
+        let kind = match app.get_app_type() {
+            AppType::Process => "process",
+            AppType::Bookmark => "bookmark",
+            AppType::Both => "process+url",
+        };
+        let state = match read_run_state(&app.id)? {
+            Some(state) if is_running(&state) => format!("running (pid {})", state.pid),
+            _ => String::new(),
+        };
+        println!("{:<36}  {:<30}  {:<12}  {}", app.id, app.name, kind, state);
+    }
+
+    Ok(())
+}
+
+fn start_app(options: &mut Options) -> AppResult<()> {
+    let reference = options.take_app()?;
+    let config = read_global_config()?;
+    let app = find_app(&config, &reference)?.clone();
+
+    if let Some(state) = read_run_state(&app.id)? {
+        if is_running(&state) {
+            return Err(AppError::new(
+                "PROCESS_RUNNING",
+                &format!("'{}' is already running (pid {})", app.name, state.pid),
+            ));
+        }
+        remove_run_state(&app.id)?;
+    }
+
+    let requested_profile = options.value(&["--profile"]);
+    let resolved = resolved_launch_fields(&app, requested_profile.as_deref(), &config)?;
+
+    let launch_commands = resolved.launch_commands.clone().unwrap_or_default();
+    if let Some(mut client) = ControlClient::connect()? {
+        // The running launcher tracks the processes it starts, so it starts the app itself
+        let result = client.call(
+            "start_app_process",
+            serde_json::json!({ "appId": app.id, "profile": requested_profile }),
+        )?;
+        if !result["success"].as_bool().unwrap_or(false) {
+            return Err(AppError::new(
+                "LAUNCH_ERROR",
+                &format!(
+                    "Failed to start '{}': {}",
+                    app.name,
+                    result["message"].as_str().unwrap_or("Unknown error")
+                ),
+            ));
+        }
+        println!("Started '{}' in the running OddLauncher", app.name);
+
+        if options.flag(&["--open"]) {
+            if let Some(url) = resolved.url.clone() {
+                open_when_ready(&app, url, resolved.port_to_check)?;
+            }
+        }
+    } else if launch_commands.trim().is_empty() {
+        // Bookmark app: opening the URL is all there is to do
+        let url = resolved
+            .url
+            .clone()
+            .ok_or_else(|| AppError::new("LAUNCH_ERROR", "Bookmark apps require a URL"))?;
+        tauri::async_runtime::block_on(open_url_in_browser(url.clone()))
+            .map_err(|e| AppError::new("BROWSER_LAUNCH_ERROR", &e))?;
+        println!("Opened {}", url);
+    } else {
+        let store = unlock_secret_store(&app)?;
+        // Captured once for both the hooks and the app, since it means running the login shell
+        let login_environment = login_environment(&app);
+        let pre_launch = HookStage::PreLaunch.commands(&app);
+        if !pre_launch.is_empty() {
+            let hooks = hook_environment(&app, &resolved, &config.settings.custom_shells, &store, &login_environment)?;
+            let result = tauri::async_runtime::block_on(run_hooks(
+                &hooks,
+                HookStage::PreLaunch,
+                &pre_launch,
+                app.abort_on_pre_launch_failure.unwrap_or(true),
+                &mut TerminalHookReporter,
+            ));
+            if let Err(failure) = result {
+                if app.abort_on_pre_launch_failure.unwrap_or(true) {
+                    return Err(AppError::new("LAUNCH_ERROR", &format!("Not starting '{}': {}", app.name, failure)));
+                }
+                eprintln!("Starting '{}' anyway: {}", app.name, failure);
+            }
+        }
+
+        let state = spawn_detached(
+            &app,
+            &launch_commands,
+            &resolved,
+            &config.settings.custom_shells,
+            &store,
+            &login_environment,
+        )?;
+        println!(
+            "Started '{}' (pid {}), output in {}",
+            app.name, state.pid, state.log_file
+        );
+
+        if options.flag(&["--open"]) {
+            if let Some(url) = resolved.url.clone() {
+                open_when_ready(&app, url, resolved.port_to_check)?;
+            }
+        }
+    }
+
+    // Re-read, since hooks can run for minutes and the launcher may have saved changes meanwhile;
+    // only usage statistics change, so this is stored without an undo entry
+    let mut config = read_global_config()?;
+    if let Some(saved) = config.apps.iter_mut().find(|a| a.id == app.id) {
+        saved.last_used_at = Some(chrono::Utc::now().to_rfc3339());
+        saved.use_count = Some(saved.use_count.unwrap_or(0) + 1);
+    }
+    store_global_config(config)
+}
+
+/// Apply an app's profile, env files and variable references to its launch settings
+fn resolved_launch_fields(app: &AppConfig, requested_profile: Option<&str>, config: &GlobalConfig) -> AppResult<LaunchFields> {
+    let profile = app.resolve_profile(requested_profile)?.cloned();
+    Ok(resolve_launch_fields(
+        &app.id,
+        &LaunchFields {
+            launch_commands: app.launch_commands.clone(),
+            working_directory: app.working_directory.clone(),
+            url: app.url.clone(),
+            environment_variables: app.environment_variables.clone(),
+            env_files: app.env_files.clone().unwrap_or_default(),
+            port_to_check: app.port_to_check,
+            profile,
+        },
+        &config.settings.variables,
+    )?
+    .fields)
+}
+
+/// Open the secret store, asking for its passphrase if the app uses secrets and no keyfile unlocks it
+fn unlock_secret_store(app: &AppConfig) -> AppResult<SecretStore> {
+    let store = SecretStore::default();
+    let uses_secrets = app.secret_variables.as_ref().is_some_and(|vars| !vars.is_empty());
+    if uses_secrets && store.needs_passphrase()? {
+        let passphrase = prompt_passphrase("Secret store passphrase: ")?;
+        store.unlock_with_passphrase(&passphrase)?;
+    }
+    Ok(store)
+}
+
+/// Read a passphrase from the terminal without echoing it
+fn prompt_passphrase(prompt: &str) -> AppResult<String> {
+    let read_error = |e: std::io::Error| AppError::new("CLI_INPUT_ERROR", &format!("Failed to read passphrase: {}", e));
+
+    eprint!("{}", prompt);
+    std::io::stderr().flush().map_err(read_error)?;
+
+    #[cfg(unix)]
+    let saved = unsafe {
+        let mut termios = std::mem::zeroed::<libc::termios>();
+        if libc::tcgetattr(libc::STDIN_FILENO, &mut termios) == 0 {
+            let saved = termios;
+            termios.c_lflag &= !libc::ECHO;
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &termios);
+            Some(saved)
+        } else {
+            // Not a terminal (piped input)
+            None
+        }
+    };
+
+    let mut line = String::new();
+    let result = std::io::stdin().lock().read_line(&mut line);
+
+    #[cfg(unix)]
+    if let Some(saved) = saved {
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &saved);
+        }
+        eprintln!();
+    }
+
+    result.map_err(read_error)?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// The login shell environment an app runs with, or nothing if the app opts out of it
+fn login_environment(app: &AppConfig) -> HashMap<String, String> {
+    if app.use_login_shell_env.unwrap_or(true) {
+        tauri::async_runtime::block_on(ShellEnvironment::default().variables())
+    } else {
+        HashMap::new()
+    }
+}
+
+/// Set up hooks to run with the directory, shell and environment `spawn_detached` gives the app
+fn hook_environment(
+    app: &AppConfig,
+    fields: &LaunchFields,
+    custom_shells: &[CustomShell],
+    store: &SecretStore,
+    login_environment: &HashMap<String, String>,
+) -> AppResult<HookEnvironment> {
+    let working_dir = match fields.working_directory.as_deref() {
+        Some(dir) => Some(
+            platform_utils::validate_directory(dir).map_err(|e| AppError::new("LAUNCH_ERROR", &e))?,
+        ),
+        None => None,
+    };
+    let mut variables = login_environment.clone();
+    if let Some(ref env_vars) = fields.environment_variables {
+        variables.extend(env_vars.clone());
+    }
+    let secret_variables = app.secret_variables.clone().unwrap_or_default();
+    let secrets = resolve_app_secrets(store, &secret_variables)?;
+    let secret_values = secrets.iter().map(|(_, value)| value.clone()).collect();
+    variables.extend(secrets);
+
+    Ok(HookEnvironment {
+        app_id: app.id.clone(),
+        working_dir,
+        terminal_type: app.terminal_type.clone(),
+        custom_shells: custom_shells.to_vec(),
+        wsl_target: WslTarget::for_app(app.wsl_distro.as_deref(), app.wsl_user.as_deref(), fields.working_directory.as_deref()),
+        dev_environment: app.dev_environment,
+        variables,
+        secret_values,
+        timeout: hook_timeout(app.hook_timeout),
+        cancel: None,
+    })
+}
+
+/**
+ * Prints hook progress and output to the terminal the command runs in
+ */
+struct TerminalHookReporter;
+
+impl HookReporter for TerminalHookReporter {
+    fn started(&mut self, stage: HookStage, index: usize, total: usize, command: &str) {
+        println!("[{}] ({}/{}) {}", stage.label(), index + 1, total, command);
+    }
+
+    fn output(&mut self, stage: HookStage, stream: &str, line: &str) {
+        if stream == "stderr" {
+            eprintln!("[{}] {}", stage.label(), line);
+        } else {
+            println!("[{}] {}", stage.label(), line);
+        }
+    }
+
+    fn finished(
+        &mut self,
+        stage: HookStage,
+        _index: usize,
+        _total: usize,
+        command: &str,
+        outcome: &HookOutcome,
+        duration: Duration,
+    ) {
+        let result = match outcome {
+            HookOutcome::Succeeded => return,
+            HookOutcome::Failed(Some(code)) => format!("failed with exit code {}", code),
+            HookOutcome::Failed(None) => "failed".to_string(),
+            HookOutcome::TimedOut => "timed out".to_string(),
+            HookOutcome::Cancelled => "was cancelled".to_string(),
+            HookOutcome::Error(e) => format!("could not be started ({})", e),
+        };
+        eprintln!("[{}] '{}' {} after {:.1}s", stage.label(), command, result, duration.as_secs_f64());
+    }
+}
+
+/// Spawn an app's launch commands in the background with output going to its log file
+fn spawn_detached(
+    app: &AppConfig,
+    launch_commands: &str,
+    fields: &LaunchFields,
+    custom_shells: &[CustomShell],
+    store: &SecretStore,
+    login_environment: &HashMap<String, String>,
+) -> AppResult<RunState> {
+    let launch_error = |message: String| AppError::new("LAUNCH_ERROR", &message);
+
+    let working_dir = match fields.working_directory.as_deref() {
+        Some(dir) => Some(platform_utils::validate_directory(dir).map_err(launch_error)?),
+        None => None,
+    };
+    let (program, args, launch_script) = prepare_launch(
+        &app.id,
+        launch_commands,
+        working_dir.as_deref(),
+        app.terminal_type.as_deref(),
+        app.launch_mode.unwrap_or_default(),
+        custom_shells,
+        &WslTarget::for_app(app.wsl_distro.as_deref(), app.wsl_user.as_deref(), fields.working_directory.as_deref()),
+    )
+    .and_then(|(program, args, script)| match app.dev_environment {
+        Some(kind) => tauri::async_runtime::block_on(wrap_in_dev_environment(
+            kind,
+            program,
+            args,
+            working_dir.as_deref(),
+            login_environment.get("PATH").map(String::as_str),
+        ))
+        .map(|(program, args)| (program, args, script)),
+        None => Ok((program, args, script)),
+    })
+    .map_err(|e| launch_error(format!("Failed to prepare launch commands: {}", e)))?;
+
+    let log_path = get_log_file_path(&app.id)?;
+    let log_file = open_log_file(&log_path)?;
+    let stderr_file = log_file
+        .try_clone()
+        .map_err(|e| AppError::new("FILE_WRITE_ERROR", &format!("Failed to open log file: {}", e)))?;
+
+    let mut cmd = Command::new(&program);
+    // cmd.exe doesn't follow the usual argument quoting rules, and its script is already escaped for it
+    #[cfg(windows)]
+    {
+        if program == "cmd.exe" {
+            for arg in &args {
+                cmd.raw_arg(arg);
+            }
+        } else {
+            cmd.args(&args);
+        }
+    }
+    #[cfg(not(windows))]
+    cmd.args(&args);
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::from(log_file))
+        .stderr(Stdio::from(stderr_file));
+
+    if let Some(ref dir) = working_dir {
+        if !(cfg!(target_os = "windows") && program == "wsl.exe") {
+            cmd.current_dir(dir);
+        }
+    }
+
+    cmd.envs(login_environment);
+    if let Some(ref env_vars) = fields.environment_variables {
+        cmd.envs(env_vars);
+    }
+
+    let secret_variables = app.secret_variables.clone().unwrap_or_default();
+    for (key, value) in resolve_app_secrets(store, &secret_variables)? {
+        cmd.env(key, value);
+    }
+
+    // Run in a new process group so the app outlives this command and can be stopped as a whole
+    #[cfg(unix)]
+    unsafe {
+        cmd.pre_exec(|| {
+            libc::setpgid(0, 0);
+            Ok(())
+        });
+    }
+    #[cfg(windows)]
+    {
+        const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NEW_PROCESS_GROUP | CREATE_NO_WINDOW);
+    }
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| launch_error(format!("Failed to start '{}': {}", program, e)))?;
+    let process_start = platform_utils::process_start_time(child.id());
+
+    // Catch commands that fail straight away (typos, missing executables)
+    std::thread::sleep(STARTUP_CHECK_DELAY);
+    if let Ok(Some(status)) = child.try_wait() {
+        if !status.success() {
+            return Err(launch_error(format!(
+                "'{}' exited immediately with {}; see {}",
+                app.name,
+                status,
+                log_path.display()
+            )));
+        }
+    }
+
+    let pid = child.id();
+    let state = RunState {
+        app_id: app.id.clone(),
+        pid,
+        pgid: if cfg!(unix) { Some(pid as i32) } else { None },
+        started_at: chrono::Utc::now().to_rfc3339(),
+        profile: fields.profile.as_ref().map(|p| p.name.clone()),
+        log_file: log_path.to_string_lossy().to_string(),
+        // The app outlives this command, so its script is removed when it's found stopped
+        script_file: launch_script.map(|script| script.keep().to_string_lossy().to_string()),
+        process_start,
+    };
+    write_run_state(&state)?;
+
+    Ok(state)
+}
+
+/// Wait for the app's port (if any) and then open its URL
+fn open_when_ready(app: &AppConfig, url: String, port_to_check: Option<u16>) -> AppResult<()> {
+    if let Some(port) = port_to_check {
+        let check_url = if url.contains("://") {
+            url.clone()
+        } else {
+            format!("http://localhost:{}", port)
+        };
+        let timeout = app.port_check_timeout.unwrap_or(30) as u64;
+
+        println!("Waiting for {} ...", check_url);
+        let ready = tauri::async_runtime::block_on(wait_for_port_ready(check_url, timeout))
+            .map_err(|e| AppError::new("PORT_CHECK_ERROR", &e))?;
+        if !ready {
+            return Err(AppError::new(
+                "PORT_CHECK_ERROR",
+                &format!("Port {} was not ready within {}s, not opening the browser", port, timeout),
+            ));
+        }
+    }
+
+    tauri::async_runtime::block_on(open_url_in_browser(url.clone()))
+        .map_err(|e| AppError::new("BROWSER_LAUNCH_ERROR", &e))?;
+    println!("Opened {}", url);
+    Ok(())
+}
+
+fn stop_app(options: &mut Options) -> AppResult<()> {
+    let reference = options.take_app()?;
+    let config = read_global_config()?;
+    let app = find_app(&config, &reference)?;
+
+    // Apps started in the running launcher are stopped (and run their post-stop hooks) there
+    if let Some(mut client) = ControlClient::connect()? {
+        let result = client.call("stop_app_process", serde_json::json!({ "appId": app.id }))?;
+        if result["success"].as_bool().unwrap_or(false) {
+            println!("Stopped '{}' in the running OddLauncher", app.name);
+            return Ok(());
+        }
+    }
+
+    let state = match read_run_state(&app.id)? {
+        Some(state) if is_running(&state) => state,
+        Some(_) => {
+            remove_run_state(&app.id)?;
+            println!("'{}' is not running", app.name);
+            return Ok(());
+        }
+        None => {
+            println!("'{}' is not running", app.name);
+            return Ok(());
+        }
+    };
+
+    tauri::async_runtime::block_on(terminate_process_tree(state.pid, state.pgid)).map_err(|e| {
+        AppError::new(
+            "PROCESS_STOP_ERROR",
+            &format!("Failed to stop '{}' (pid {}): {}", app.name, state.pid, e),
+        )
+    })?;
+
+    remove_run_state(&app.id)?;
+    println!("Stopped '{}'", app.name);
+
+    // Post-stop hooks run with the profile the app was started with. Nothing watches a detached app,
+    // so they only run here: an app started from the command line that exits on its own skips them.
+    let post_stop = HookStage::PostStop.commands(app);
+    if !post_stop.is_empty() {
+        let resolved = resolved_launch_fields(app, state.profile.as_deref(), &config)?;
+        let store = unlock_secret_store(app)?;
+        let hooks = hook_environment(app, &resolved, &config.settings.custom_shells, &store, &login_environment(app))?;
+        let result = tauri::async_runtime::block_on(run_hooks(
+            &hooks,
+            HookStage::PostStop,
+            &post_stop,
+            false,
+            &mut TerminalHookReporter,
+        ));
+        if let Err(failure) = result {
+            return Err(AppError::new("HOOK_ERROR", &failure));
+        }
+    }
+    Ok(())
+}
+
+/// Whether the process recorded in a run state is still the app's
+///
+/// A PID alone isn't enough: after a reboot or once the app has exited it may belong to an
+/// unrelated process, which must never be signalled. States without a start time are stale.
+fn is_running(state: &RunState) -> bool {
+    state.process_start.is_some()
+        && process_alive(state.pid)
+        && platform_utils::process_start_time(state.pid) == state.process_start
+}
+
+/// PID of an app started from the command line, if it's still running
+pub(crate) fn running_pid(app_id: &str) -> Option<u32> {
+    read_run_state(app_id)
+        .ok()
+        .flatten()
+        .filter(is_running)
+        .map(|state| state.pid)
+}
+
+/// Whether a process with this PID still exists
+fn process_alive(pid: u32) -> bool {
+    #[cfg(unix)]
+    {
+        // Reap the process if it's our own exited child, so it doesn't linger as a zombie
+        unsafe {
+            libc::waitpid(pid as i32, std::ptr::null_mut(), libc::WNOHANG);
+        }
+        unsafe { libc::kill(pid as i32, 0) == 0 }
+    }
+
+    #[cfg(windows)]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        Command::new("tasklist")
+            .arg("/FI")
+            .arg(format!("PID eq {}", pid))
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+            .map(|out| String::from_utf8_lossy(&out.stdout).contains(&pid.to_string()))
+            .unwrap_or(false)
+    }
+}
+
+fn show_status(options: &mut Options) -> AppResult<()> {
+    let config = read_global_config()?;
+    let apps: Vec<&AppConfig> = if options.positional.is_empty() {
+        config.apps.iter().collect()
+    } else {
+        let reference = options.take_app()?;
+        vec![find_app(&config, &reference)?]
+    };
+
+    // Apps started through the running launcher are tracked there rather than in run files
+    let launcher_processes = match ControlClient::connect()? {
+        Some(mut client) => client.call("get_all_process_status", serde_json::json!({}))?,
+        None => serde_json::Value::Null,
+    };
+
+    let mut statuses = Vec::new();
+    for app in apps {
+        let state = read_run_state(&app.id)?;
+        let running = state.as_ref().is_some_and(is_running);
+        if state.is_some() && !running {
+            remove_run_state(&app.id)?;
+        }
+
+        let status = match (state.filter(|_| running), launcher_processes.get(&app.id)) {
+            (Some(state), _) => serde_json::json!({
+                "appId": app.id,
+                "name": app.name,
+                "status": "running",
+                "pid": state.pid,
+                "startedAt": state.started_at,
+                "profile": state.profile,
+                "launcher": false,
+            }),
+            (None, Some(process)) => serde_json::json!({
+                "appId": app.id,
+                "name": app.name,
+                "status": "running",
+                "pid": process["pid"],
+                "startedAt": process["startedAt"],
+                "profile": process["activeProfile"],
+                "launcher": true,
+            }),
+            (None, None) => serde_json::json!({
+                "appId": app.id,
+                "name": app.name,
+                "status": "stopped",
+                "pid": null,
+                "startedAt": null,
+                "profile": null,
+                "launcher": false,
+            }),
+        };
+        statuses.push(status);
+    }
+
+    if options.flag(&["--json"]) {
+        return print_json(&statuses);
+    }
+
+    for status in &statuses {
+        let mut pid = status["pid"].as_u64().map(|pid| format!("pid {}", pid)).unwrap_or_default();
+        if status["launcher"].as_bool() == Some(true) {
+            pid.push_str(" (in OddLauncher)");
+        }
+        println!(
+            "{:<30}  {:<8}  {}",
+            status["name"].as_str().unwrap_or_default(),
+            status["status"].as_str().unwrap_or_default(),
+            pid
+        );
+    }
+
+    Ok(())
+}
+
+fn show_logs(options: &mut Options) -> AppResult<()> {
+    let reference = options.take_app()?;
+    let config = read_global_config()?;
+    let app = find_app(&config, &reference)?;
+    let follow = options.flag(&["-f", "--follow"]);
+    let tail = match options.value(&["-n"]) {
+        Some(n) => Some(n.parse::<usize>().map_err(|_| usage_error("-n expects a number of lines"))?),
+        None => None,
+    };
+
+    // Output of apps the running launcher started goes to its window, not to a log file
+    let started_here = read_run_state(&app.id)?.as_ref().is_some_and(is_running);
+    if !started_here {
+        if let Some(mut client) = ControlClient::connect()? {
+            let process = client.call("get_process_status", serde_json::json!({ "appId": app.id }))?;
+            if !process.is_null() {
+                println!("'{}' was started by the running OddLauncher; its output is shown there", app.name);
+                return Ok(());
+            }
+        }
+    }
+
+    let log_path = get_log_file_path(&app.id)?;
+    if !log_path.exists() && !follow {
+        println!("No output recorded for '{}'", app.name);
+        return Ok(());
+    }
+
+    // Output files hold whatever the app printed, so secrets are redacted on the way out
+    let secret_values = known_secret_values(&SecretStore::default());
+
+    let content = fs::read(&log_path).unwrap_or_default();
+    let mut position = content.len() as u64;
+    let text = String::from_utf8_lossy(&content);
+    // Step markers are for the launcher's own step tracking
+    let lines: Vec<&str> = text.lines().filter(|line| parse_step_marker(line).is_none()).collect();
+    let skip = tail.map_or(0, |n| lines.len().saturating_sub(n));
+    for line in &lines[skip..] {
+        println!("{}", redact(line, &secret_values));
+    }
+
+    if !follow {
+        return Ok(());
+    }
+
+    let mut pending = String::new();
+    loop {
+        std::thread::sleep(FOLLOW_POLL_INTERVAL);
+
+        let Ok(mut file) = File::open(&log_path) else {
+            continue;
+        };
+        let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+        if len < position {
+            // The log was truncated by a restart
+            position = 0;
+            pending.clear();
+        }
+        if len == position {
+            continue;
+        }
+
+        let mut chunk = Vec::new();
+        if file.seek(SeekFrom::Start(position)).is_ok() && file.read_to_end(&mut chunk).is_ok() {
+            position += chunk.len() as u64;
+            pending.push_str(&String::from_utf8_lossy(&chunk));
+
+            while let Some(newline) = pending.find('\n') {
+                let line: String = pending.drain(..=newline).collect();
+                if parse_step_marker(&line).is_none() {
+                    println!("{}", redact(line.trim_end(), &secret_values));
+                }
+            }
+            let _ = std::io::stdout().flush();
+        }
+    }
+}
+
+fn add_app(options: &Options) -> AppResult<()> {
+    let mut config = read_global_config()?;
+
+    let app = match options.value(&["--template"]) {
+        Some(template_id) => {
+            let mut params = HashMap::new();
+            for param in options.all("--param") {
+                let (key, value) = param
+                    .split_once('=')
+                    .ok_or_else(|| usage_error(&format!("Invalid --param '{}', expected key=value", param)))?;
+                params.insert(key.to_string(), value.to_string());
+            }
+            if let Some(name) = options.value(&["--name"]) {
+                params.insert("name".to_string(), name);
+            }
+            tauri::async_runtime::block_on(create_app_from_template(template_id, Some(params)))?
+        }
+        None => app_from_options(options)?,
+    };
+
+    ensure_valid_app(&app, &config.apps, &config.settings.custom_shells)?;
+    config.apps.push(app.clone());
+    write_global_config(config)?;
+
+    println!("Added '{}' ({})", app.name, app.id);
+    Ok(())
+}
+
+/// Build an app from `add` options; several `--command` options become separate lines
+fn app_from_options(options: &Options) -> AppResult<AppConfig> {
+    let name = options
+        .value(&["--name"])
+        .ok_or_else(|| usage_error("add needs --name (or --template)"))?;
+    let commands = options.all("--command");
+    let port_to_check = match options.value(&["--port"]) {
+        Some(port) => Some(port.parse::<u16>().map_err(|_| usage_error("--port expects a port number"))?),
+        None => None,
+    };
+    let tags = options.all("--tag");
+    let now = chrono::Utc::now().to_rfc3339();
+
+    Ok(AppConfig {
+        id: uuid::Uuid::new_v4().to_string(),
+        name,
+        launch_commands: if commands.is_empty() { None } else { Some(commands.join("\n")) },
+        working_directory: options.value(&["--dir"]),
+        url: options.value(&["--url"]),
+        environment_variables: None,
+        env_files: None,
+        secret_variables: None,
+        auto_launch_browser: None,
+        browser_delay: None,
+        port_to_check,
+        port_check_timeout: None,
+        tags: if tags.is_empty() { None } else { Some(tags) },
+        terminal_type: None,
+        wsl_distro: None,
+        wsl_user: None,
+        launch_mode: None,
+        use_login_shell_env: None,
+        dev_environment: None,
+        pre_launch: None,
+        post_stop: None,
+        hook_timeout: None,
+        abort_on_pre_launch_failure: None,
+        profiles: None,
+        default_profile: None,
+        app_type: None,
+        last_used_at: None,
+        use_count: None,
+        created_at: now.clone(),
+        updated_at: now,
+    })
+}
+
+fn export_apps(options: &Options) -> AppResult<()> {
+    let config = read_global_config()?;
+
+    let references = options.all("--app");
+    let app_ids = if references.is_empty() {
+        None
+    } else {
+        Some(
+            references
+                .iter()
+                .map(|r| find_app(&config, r).map(|a| a.id.clone()))
+                .collect::<AppResult<Vec<_>>>()?,
+        )
+    };
+
+    let export = export_config(
+        &config,
+        &ExportOptions {
+            app_ids,
+            include_settings: options.flag(&["--include-settings"]),
+            strip_secrets: options.flag(&["--strip-secrets"]),
+            strip_absolute_paths: options.flag(&["--strip-paths"]),
+        },
+    )?;
+
+    match options.value(&["-o", "--output"]) {
+        Some(path) => {
+            fs::write(&path, export).map_err(|e| {
+                AppError::new("FILE_WRITE_ERROR", &format!("Failed to write {}: {}", path, e))
+            })?;
+            eprintln!("Exported to {}", path);
+        }
+        None => println!("{}", export),
+    }
+
+    Ok(())
+}
+
+/// Get the output file for an app started from the command line (~/.oddlauncher/logs/<app id>.log)
+fn get_log_file_path(app_id: &str) -> AppResult<PathBuf> {
+    Ok(get_config_dir()?.join("logs").join(format!("{}.log", app_id)))
+}
+
+/// Get the run state file for an app (~/.oddlauncher/run/<app id>.json)
+fn get_run_state_path(app_id: &str) -> AppResult<PathBuf> {
+    Ok(get_config_dir()?.join("run").join(format!("{}.json", app_id)))
+}
+
+/// Open an app's log file for a new run, replacing the previous run's output
+fn open_log_file(path: &Path) -> AppResult<File> {
+    ensure_parent_dir(path)?;
+
+    let mut open = OpenOptions::new();
+    open.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        // App output may contain secrets
+        open.mode(0o600);
+    }
+
+    open.open(path)
+        .map_err(|e| AppError::new("FILE_WRITE_ERROR", &format!("Failed to open log file: {}", e)))
+}
+
+fn ensure_parent_dir(path: &Path) -> AppResult<()> {
+    ensure_config_dir_exists()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| {
+            AppError::new("DIR_CREATE_ERROR", &format!("Failed to create {}: {}", parent.display(), e))
+        })?;
+    }
+    Ok(())
+}
+
+fn read_run_state(app_id: &str) -> AppResult<Option<RunState>> {
+    let path = get_run_state_path(app_id)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&path).map_err(|e| {
+        AppError::new("FILE_READ_ERROR", &format!("Failed to read run state: {}", e))
+    })?;
+
+    // An unreadable state file is treated as "not running"
+    Ok(serde_json::from_str(&content).ok())
+}
+
+fn write_run_state(state: &RunState) -> AppResult<()> {
+    let path = get_run_state_path(&state.app_id)?;
+    ensure_parent_dir(&path)?;
+
+    let content = serde_json::to_string_pretty(state).map_err(|e| {
+        AppError::new("JSON_SERIALIZE_ERROR", &format!("Failed to serialize run state: {}", e))
+    })?;
+    fs::write(&path, content).map_err(|e| {
+        AppError::new("FILE_WRITE_ERROR", &format!("Failed to write run state: {}", e))
+    })
+}
+
+fn remove_run_state(app_id: &str) -> AppResult<()> {
+    if let Some(script_file) = read_run_state(app_id)?.and_then(|state| state.script_file) {
+        remove_launch_script(Path::new(&script_file));
+    }
+
+    let path = get_run_state_path(app_id)?;
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(AppError::new(
+            "FILE_WRITE_ERROR",
+            &format!("Failed to remove run state: {}", e),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(pid: u32, process_start: Option<String>) -> RunState {
+        RunState {
+            app_id: "app".to_string(),
+            pid,
+            pgid: None,
+            started_at: String::new(),
+            profile: None,
+            log_file: String::new(),
+            script_file: None,
+            process_start,
+        }
+    }
+
+    #[test]
+    fn running_state_matches_start_time() {
+        let pid = std::process::id();
+        let started = platform_utils::process_start_time(pid);
+        assert!(started.is_some());
+        assert!(is_running(&state(pid, started)));
+    }
+
+    #[test]
+    fn reused_pid_is_not_running() {
+        let pid = std::process::id();
+        assert!(!is_running(&state(pid, Some("0:0".to_string()))));
+        // States written before start times were recorded can't be verified
+        assert!(!is_running(&state(pid, None)));
+    }
+
+    #[test]
+    fn old_run_state_still_parses() {
+        let parsed: RunState = serde_json::from_str(
+            r#"{"appId":"app","pid":1,"pgid":1,"startedAt":"","profile":null,"logFile":""}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.process_start, None);
+    }
+}
@@ -0,0 +1,586 @@
+use crate::commands::config::{
+    get_config_dir, get_config_file_path, read_global_config, write_global_config,
+};
+use crate::commands::config_format::ConfigFormat;
+use crate::models::app::{AppConfig, AppError, AppResult, BackupRetention, GlobalConfig};
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+/// File name prefix for backups created on request
+const MANUAL_BACKUP_PREFIX: &str = "apps_backup_";
+
+/// File name prefix for snapshots taken automatically before destructive saves
+const AUTO_BACKUP_PREFIX: &str = "apps_autobackup_";
+
+/// App fields that change on every launch or save, which alone don't warrant a snapshot
+const USAGE_FIELDS: [&str; 3] = ["lastUsedAt", "useCount", "updatedAt"];
+
+/**
+ * How a backup was created
+ */
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupKind {
+    Manual,
+    Automatic,
+}
+
+/**
+ * Metadata about a configuration backup
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupInfo {
+    /// Full path to the backup file
+    pub path: String,
+    pub file_name: String,
+    pub kind: BackupKind,
+    /// When the backup was taken (RFC 3339)
+    pub timestamp: String,
+    pub size_bytes: u64,
+    /// Number of apps in the backup, or None if the file can't be parsed
+    pub app_count: Option<usize>,
+}
+
+/**
+ * Summary of one app in a backup diff
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppDiffEntry {
+    pub id: String,
+    pub name: String,
+    /// Top-level fields that differ (only for changed apps)
+    pub changed_fields: Vec<String>,
+}
+
+/**
+ * Changes that restoring a backup would make to the current configuration
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupDiff {
+    pub backup: BackupInfo,
+    /// Apps in the backup that don't exist in the current configuration
+    pub apps_added: Vec<AppDiffEntry>,
+    /// Apps in the current configuration that aren't in the backup
+    pub apps_removed: Vec<AppDiffEntry>,
+    /// Apps present in both with different settings
+    pub apps_changed: Vec<AppDiffEntry>,
+    pub apps_unchanged: usize,
+    /// Global settings fields that differ
+    pub settings_changed: Vec<String>,
+}
+
+/// Backup the current configuration
+#[tauri::command]
+pub async fn backup_config(_app: AppHandle) -> AppResult<String> {
+    log::info!("Creating configuration backup");
+
+    let config_file = get_config_file_path()?;
+
+    if !config_file.exists() {
+        return Err(AppError::new(
+            "NO_CONFIG_ERROR",
+            "No configuration file exists to backup",
+        ));
+    }
+
+    let backup_file = copy_to_backup(&config_file, BackupKind::Manual)?;
+    let retention = read_global_config()
+        .map(|c| c.settings.backup_retention)
+        .unwrap_or_default();
+    prune_backups(&retention)?;
+
+    let backup_path = backup_file.to_string_lossy().to_string();
+    log::info!("Successfully created backup: {}", backup_path);
+    Ok(backup_path)
+}
+
+/// List all backups in the configuration directory, newest first
+#[tauri::command]
+pub async fn list_backups(_app: AppHandle) -> AppResult<Vec<BackupInfo>> {
+    let backups = collect_backups()?;
+    log::info!("Found {} configuration backups", backups.len());
+    Ok(backups)
+}
+
+/// Compare a backup with the current configuration
+#[tauri::command]
+pub async fn diff_backup(_app: AppHandle, backup_path: String) -> AppResult<BackupDiff> {
+    let backup_file = resolve_backup_path(&backup_path)?;
+    let backup = read_backup(&backup_file)?;
+    let current = read_global_config()?;
+
+    Ok(diff_configs(backup_info(&backup_file)?, &backup, &current))
+}
+
+/// Restore configuration from backup
+#[tauri::command]
+pub async fn restore_config(_app: AppHandle, backup_path: String) -> AppResult<GlobalConfig> {
+    log::info!("Restoring configuration from backup: {}", backup_path);
+
+    let backup_file = resolve_backup_path(&backup_path)?;
+    let config = read_backup(&backup_file)?;
+
+    // Keep the configuration being replaced so the restore itself can be undone
+    let retention = read_global_config()
+        .map(|c| c.settings.backup_retention)
+        .unwrap_or_default();
+    create_snapshot(&retention)?;
+
+    write_global_config(config.clone())?;
+
+    log::info!("Successfully restored configuration from backup");
+    Ok(config)
+}
+
+/// Take an automatic snapshot if the new configuration drops or overwrites any currently saved app
+pub(crate) fn snapshot_before_destructive_save(new_config: &GlobalConfig) -> AppResult<()> {
+    let current = match read_global_config() {
+        Ok(current) => current,
+        Err(e) => {
+            log::warn!("Skipping pre-save snapshot, current config unreadable: {}", e.message);
+            return Ok(());
+        }
+    };
+
+    let mut removed = 0;
+    let mut overwritten = 0;
+    for app in &current.apps {
+        match new_config.apps.iter().find(|a| a.id == app.id) {
+            None => removed += 1,
+            Some(updated) if settings_changed(app, updated) => overwritten += 1,
+            Some(_) => {}
+        }
+    }
+
+    if removed + overwritten > 0 {
+        log::info!(
+            "Save removes {} and overwrites {} apps, taking automatic snapshot",
+            removed,
+            overwritten
+        );
+        create_snapshot(&new_config.settings.backup_retention)?;
+    }
+
+    Ok(())
+}
+
+/// Whether an app's settings changed, ignoring usage statistics and its update time
+fn settings_changed(previous: &AppConfig, updated: &AppConfig) -> bool {
+    changed_fields(previous, updated)
+        .iter()
+        .any(|field| !USAGE_FIELDS.contains(&field.as_str()))
+}
+
+/// Snapshot the current configuration file and apply the retention policy
+///
+/// Does nothing when automatic backups are disabled, there is no configuration file
+/// yet, or the newest backup already has identical contents.
+pub(crate) fn create_snapshot(retention: &BackupRetention) -> AppResult<Option<PathBuf>> {
+    if !retention.auto_backup {
+        return Ok(None);
+    }
+
+    let config_file = get_config_file_path()?;
+    if !config_file.exists() {
+        return Ok(None);
+    }
+
+    if let Some(latest) = collect_backups()?.first() {
+        let unchanged = match (fs::read(&config_file), fs::read(&latest.path)) {
+            (Ok(current), Ok(previous)) => current == previous,
+            _ => false,
+        };
+        if unchanged {
+            log::debug!("Configuration matches latest backup, skipping snapshot");
+            return Ok(None);
+        }
+    }
+
+    let snapshot = copy_to_backup(&config_file, BackupKind::Automatic)?;
+    log::info!("Created automatic snapshot: {:?}", snapshot);
+    prune_backups(retention)?;
+
+    Ok(Some(snapshot))
+}
+
+/// Copy the configuration file to a new timestamped backup file
+fn copy_to_backup(config_file: &Path, kind: BackupKind) -> AppResult<PathBuf> {
+    let prefix = match kind {
+        BackupKind::Manual => MANUAL_BACKUP_PREFIX,
+        BackupKind::Automatic => AUTO_BACKUP_PREFIX,
+    };
+
+    // Keep the config file's format so backups can be restored as they are
+    let extension = ConfigFormat::from_path(config_file)
+        .unwrap_or(ConfigFormat::Json)
+        .extension();
+
+    // Millisecond precision so snapshots taken in quick succession don't collide
+    let timestamp = Utc::now().format("%Y%m%d_%H%M%S_%3f");
+    let backup_file = get_config_dir()?.join(format!("{}{}.{}", prefix, timestamp, extension));
+
+    fs::copy(config_file, &backup_file).map_err(|e| {
+        AppError::new(
+            "BACKUP_ERROR",
+            &format!("Failed to create backup: {}", e),
+        )
+    })?;
+
+    Ok(backup_file)
+}
+
+/// Delete automatic snapshots that fall outside the retention policy
+///
+/// Backups created on request are never deleted automatically.
+fn prune_backups(retention: &BackupRetention) -> AppResult<()> {
+    let backups = collect_backups()?;
+
+    for backup in backups_to_prune(&backups, retention, Utc::now()) {
+        match fs::remove_file(&backup.path) {
+            Ok(()) => log::info!("Pruned old backup: {}", backup.file_name),
+            Err(e) => log::warn!("Failed to prune backup {}: {}", backup.file_name, e),
+        }
+    }
+
+    Ok(())
+}
+
+/// Pick the automatic backups the retention policy no longer keeps, given backups newest first
+fn backups_to_prune<'a>(
+    backups: &'a [BackupInfo],
+    retention: &BackupRetention,
+    now: DateTime<Utc>,
+) -> Vec<&'a BackupInfo> {
+    let automatic: Vec<&BackupInfo> = backups
+        .iter()
+        .filter(|b| b.kind == BackupKind::Automatic)
+        .collect();
+
+    let mut keep: HashSet<&str> = automatic
+        .iter()
+        .take((retention.keep_last as usize).max(1))
+        .map(|b| b.path.as_str())
+        .collect();
+
+    // Backups are sorted newest first, so the first one seen for each day is that day's newest
+    let mut days_seen = HashSet::new();
+    for backup in &automatic {
+        let Ok(taken) = DateTime::parse_from_rfc3339(&backup.timestamp) else {
+            continue;
+        };
+        let age_days = (now - taken.with_timezone(&Utc)).num_days();
+        if age_days < retention.keep_daily_days as i64 && days_seen.insert(taken.date_naive()) {
+            keep.insert(backup.path.as_str());
+        }
+    }
+
+    automatic
+        .into_iter()
+        .filter(|b| !keep.contains(b.path.as_str()))
+        .collect()
+}
+
+/// Find all backup files in the configuration directory, newest first
+fn collect_backups() -> AppResult<Vec<BackupInfo>> {
+    let config_dir = get_config_dir()?;
+    if !config_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries = fs::read_dir(&config_dir).map_err(|e| {
+        AppError::new(
+            "DIR_READ_ERROR",
+            &format!("Failed to read config directory: {}", e),
+        )
+    })?;
+
+    let mut backups: Vec<BackupInfo> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| backup_kind(path).is_some())
+        .filter_map(|path| backup_info(&path).ok())
+        .collect();
+
+    backups.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    Ok(backups)
+}
+
+/// Determine the backup kind from a file name, or None if it isn't a backup
+fn backup_kind(path: &Path) -> Option<BackupKind> {
+    // Only files in a supported config format count as backups
+    ConfigFormat::from_path(path)?;
+
+    let name = path.file_name()?.to_str()?;
+    if name.starts_with(AUTO_BACKUP_PREFIX) {
+        Some(BackupKind::Automatic)
+    } else if name.starts_with(MANUAL_BACKUP_PREFIX) {
+        Some(BackupKind::Manual)
+    } else {
+        None
+    }
+}
+
+/// Build the metadata for a single backup file
+fn backup_info(path: &Path) -> AppResult<BackupInfo> {
+    let metadata = fs::metadata(path).map_err(|e| {
+        AppError::new(
+            "BACKUP_READ_ERROR",
+            &format!("Failed to read backup file: {}", e),
+        )
+    })?;
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let kind = backup_kind(path).unwrap_or(BackupKind::Manual);
+
+    // Prefer the timestamp in the file name, falling back to the modification time
+    let timestamp = parse_backup_timestamp(&file_name)
+        .or_else(|| metadata.modified().ok().map(DateTime::<Utc>::from))
+        .map(|t| t.to_rfc3339())
+        .unwrap_or_default();
+
+    let app_count = read_backup(path).ok().map(|c| c.apps.len());
+
+    Ok(BackupInfo {
+        path: path.to_string_lossy().to_string(),
+        file_name,
+        kind,
+        timestamp,
+        size_bytes: metadata.len(),
+        app_count,
+    })
+}
+
+/// Parse `<prefix>YYYYmmdd_HHMMSS[_mmm].<ext>` into a UTC timestamp
+fn parse_backup_timestamp(file_name: &str) -> Option<DateTime<Utc>> {
+    let stem = Path::new(file_name).file_stem()?.to_str()?;
+    let raw = stem
+        .strip_prefix(AUTO_BACKUP_PREFIX)
+        .or_else(|| stem.strip_prefix(MANUAL_BACKUP_PREFIX))?;
+
+    NaiveDateTime::parse_from_str(raw, "%Y%m%d_%H%M%S_%3f")
+        .or_else(|_| NaiveDateTime::parse_from_str(raw, "%Y%m%d_%H%M%S"))
+        .ok()
+        .map(|t| Utc.from_utc_datetime(&t))
+}
+
+/// Accept either a full path or a backup file name in the configuration directory
+fn resolve_backup_path(backup_path: &str) -> AppResult<PathBuf> {
+    let path = PathBuf::from(backup_path);
+    let backup_file = if path.is_absolute() {
+        path
+    } else {
+        get_config_dir()?.join(path)
+    };
+
+    if !backup_file.exists() {
+        return Err(AppError::new(
+            "BACKUP_NOT_FOUND_ERROR",
+            "Backup file not found",
+        ));
+    }
+
+    Ok(backup_file)
+}
+
+/// Read and parse a backup file
+fn read_backup(path: &Path) -> AppResult<GlobalConfig> {
+    let backup_content = fs::read_to_string(path).map_err(|e| {
+        AppError::new(
+            "BACKUP_READ_ERROR",
+            &format!("Failed to read backup file: {}", e),
+        )
+    })?;
+
+    let format = ConfigFormat::from_path(path).unwrap_or(ConfigFormat::Json);
+    format.parse(&backup_content).map_err(|e| {
+        AppError::new(
+            "BACKUP_PARSE_ERROR",
+            &format!("Failed to parse backup file: {}", e),
+        )
+    })
+}
+
+/// Work out what restoring `backup` over `current` would change
+fn diff_configs(info: BackupInfo, backup: &GlobalConfig, current: &GlobalConfig) -> BackupDiff {
+    let entry = |app: &AppConfig, changed_fields: Vec<String>| AppDiffEntry {
+        id: app.id.clone(),
+        name: app.name.clone(),
+        changed_fields,
+    };
+
+    let mut diff = BackupDiff {
+        backup: info,
+        apps_added: Vec::new(),
+        apps_removed: Vec::new(),
+        apps_changed: Vec::new(),
+        apps_unchanged: 0,
+        settings_changed: changed_fields(&backup.settings, &current.settings),
+    };
+
+    for app in &backup.apps {
+        match current.apps.iter().find(|a| a.id == app.id) {
+            None => diff.apps_added.push(entry(app, Vec::new())),
+            Some(existing) => {
+                let fields = changed_fields(app, existing);
+                if fields.is_empty() {
+                    diff.apps_unchanged += 1;
+                } else {
+                    diff.apps_changed.push(entry(app, fields));
+                }
+            }
+        }
+    }
+
+    for app in &current.apps {
+        if !backup.apps.iter().any(|a| a.id == app.id) {
+            diff.apps_removed.push(entry(app, Vec::new()));
+        }
+    }
+
+    diff
+}
+
+/// Names of the top-level serialized fields that differ between two values
+fn changed_fields<T: Serialize>(a: &T, b: &T) -> Vec<String> {
+    let (Ok(serde_json::Value::Object(a)), Ok(serde_json::Value::Object(b))) =
+        (serde_json::to_value(a), serde_json::to_value(b))
+    else {
+        return Vec::new();
+    };
+
+    let mut fields: Vec<String> = a
+        .keys()
+        .chain(b.keys())
+        .filter(|key| a.get(*key) != b.get(*key))
+        .cloned()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    fields.sort();
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 6, 15, 12, 0, 0).unwrap()
+    }
+
+    fn backup(kind: BackupKind, taken: DateTime<Utc>) -> BackupInfo {
+        let prefix = match kind {
+            BackupKind::Manual => MANUAL_BACKUP_PREFIX,
+            BackupKind::Automatic => AUTO_BACKUP_PREFIX,
+        };
+        let file_name = format!("{}{}.json", prefix, taken.format("%Y%m%d_%H%M%S"));
+        BackupInfo {
+            path: format!("/config/{}", file_name),
+            file_name,
+            kind,
+            timestamp: taken.to_rfc3339(),
+            size_bytes: 0,
+            app_count: Some(0),
+        }
+    }
+
+    fn retention(keep_last: u32, keep_daily_days: u32) -> BackupRetention {
+        BackupRetention { auto_backup: true, keep_last, keep_daily_days }
+    }
+
+    fn pruned(backups: &[BackupInfo], retention: &BackupRetention) -> Vec<String> {
+        backups_to_prune(backups, retention, now()).into_iter().map(|b| b.file_name.clone()).collect()
+    }
+
+    #[test]
+    fn keep_last() {
+        let backups: Vec<BackupInfo> =
+            (0..4).map(|i| backup(BackupKind::Automatic, now() - Duration::minutes(i))).collect();
+
+        assert_eq!(pruned(&backups, &retention(2, 0)), [backups[2].file_name.as_str(), backups[3].file_name.as_str()]);
+        assert!(pruned(&backups, &retention(4, 0)).is_empty());
+        // At least the newest snapshot always survives
+        assert_eq!(pruned(&backups, &retention(0, 0)).len(), 3);
+    }
+
+    #[test]
+    fn keep_daily() {
+        let backups = vec![
+            backup(BackupKind::Automatic, now() - Duration::hours(1)),
+            backup(BackupKind::Automatic, now() - Duration::hours(2)),
+            backup(BackupKind::Automatic, now() - Duration::hours(25)),
+            backup(BackupKind::Automatic, now() - Duration::hours(26)),
+            backup(BackupKind::Automatic, now() - Duration::days(5)),
+        ];
+
+        assert_eq!(
+            pruned(&backups, &retention(1, 3)),
+            [backups[1].file_name.as_str(), backups[3].file_name.as_str(), backups[4].file_name.as_str()]
+        );
+        assert_eq!(pruned(&backups, &retention(1, 7)).len(), 2);
+    }
+
+    #[test]
+    fn manual_backups_are_kept() {
+        let backups = vec![
+            backup(BackupKind::Automatic, now() - Duration::hours(1)),
+            backup(BackupKind::Manual, now() - Duration::days(30)),
+            backup(BackupKind::Automatic, now() - Duration::days(40)),
+            backup(BackupKind::Manual, now() - Duration::days(400)),
+        ];
+
+        assert_eq!(pruned(&backups, &retention(1, 0)), [backups[2].file_name.as_str()]);
+    }
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(
+            parse_backup_timestamp("apps_autobackup_20240615_120000_250.json"),
+            Some(now() + Duration::milliseconds(250))
+        );
+        assert_eq!(parse_backup_timestamp("apps_backup_20240615_120000.yaml"), Some(now()));
+        assert_eq!(parse_backup_timestamp("apps_backup_latest.json"), None);
+        assert_eq!(parse_backup_timestamp("apps_20240615_120000.json"), None);
+    }
+
+    #[test]
+    fn diff() {
+        let kept = AppConfig::for_test("kept", "Kept", "make");
+        let mut backup_config = GlobalConfig {
+            apps: vec![
+                AppConfig::for_test("api", "API", "npm start"),
+                AppConfig::for_test("old", "Old", "cargo run"),
+                kept.clone(),
+            ],
+            ..GlobalConfig::default()
+        };
+        backup_config.settings.max_terminal_lines = 500;
+        let current = GlobalConfig {
+            apps: vec![
+                AppConfig::for_test("api", "API", "npm run dev"),
+                kept,
+                AppConfig::for_test("new", "New", "go run ."),
+            ],
+            ..GlobalConfig::default()
+        };
+
+        let diff = diff_configs(backup(BackupKind::Manual, now()), &backup_config, &current);
+        let ids = |entries: &[AppDiffEntry]| entries.iter().map(|e| e.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&diff.apps_added), ["old"]);
+        assert_eq!(ids(&diff.apps_removed), ["new"]);
+        assert_eq!(ids(&diff.apps_changed), ["api"]);
+        assert_eq!(diff.apps_changed[0].changed_fields, ["launchCommands"]);
+        assert_eq!(diff.apps_unchanged, 1);
+        assert_eq!(diff.settings_changed, ["maxTerminalLines"]);
+    }
+}
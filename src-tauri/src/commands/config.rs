@@ -1,13 +1,100 @@
+use crate::commands::backup::snapshot_before_destructive_save;
+use crate::commands::config_format::{find_config_file, ConfigFormat};
+use crate::commands::history::record_config_change;
+use crate::commands::validation::{ensure_valid_app, ensure_valid_custom_shells, validate_app};
 use crate::models::app::{GlobalConfig, AppConfig, AppError, AppResult};
+use serde::{Deserialize, Serialize};
 use serde_json;
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use tauri::AppHandle;
 
-/// Get the path to the OddLauncher configuration directory (~/.oddlauncher/)
-fn get_config_dir() -> AppResult<PathBuf> {
+/// Environment variable that overrides the configuration directory
+pub const CONFIG_DIR_ENV: &str = "ODDLAUNCHER_CONFIG_DIR";
+
+/// Command-line flag that overrides the configuration directory (`--config-dir <path>` or `--config-dir=<path>`)
+pub const CONFIG_DIR_FLAG: &str = "--config-dir";
+
+/// Command-line flag that enables portable mode
+pub const PORTABLE_FLAG: &str = "--portable";
+
+/// Marker file next to the executable that enables portable mode
+const PORTABLE_MARKER_FILE: &str = "oddlauncher.portable";
+
+/// Directory next to the executable used in portable mode
+const PORTABLE_DIR_NAME: &str = "oddlauncher-data";
+
+/**
+ * Where the configuration directory location came from, in order of precedence
+ */
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ConfigDirSource {
+    /// `--config-dir` command-line flag
+    CommandLine,
+    /// `ODDLAUNCHER_CONFIG_DIR` environment variable
+    Environment,
+    /// `--portable` flag or marker file next to the executable
+    Portable,
+    /// `~/.oddlauncher`
+    Default,
+}
+
+/// Resolved configuration directory, computed once per process
+static CONFIG_DIR: OnceLock<AppResult<(PathBuf, ConfigDirSource)>> = OnceLock::new();
+
+/// Get the path to the OddLauncher configuration directory (~/.oddlauncher/ unless overridden)
+pub(crate) fn get_config_dir() -> AppResult<PathBuf> {
+    get_config_dir_with_source().map(|(dir, _)| dir)
+}
+
+/// Get the configuration directory along with where its location came from
+pub(crate) fn get_config_dir_with_source() -> AppResult<(PathBuf, ConfigDirSource)> {
+    CONFIG_DIR
+        .get_or_init(|| {
+            let resolved = resolve_config_dir();
+            if let Ok((ref dir, source)) = resolved {
+                log::info!("Using configuration directory {:?} ({:?})", dir, source);
+            }
+            resolved
+        })
+        .clone()
+}
+
+/// Work out the configuration directory: command line, then environment, then portable mode, then home
+fn resolve_config_dir() -> AppResult<(PathBuf, ConfigDirSource)> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let flag_value = args.iter().enumerate().find_map(|(i, arg)| {
+        if arg == CONFIG_DIR_FLAG {
+            args.get(i + 1).cloned()
+        } else {
+            arg.strip_prefix(CONFIG_DIR_FLAG)
+                .and_then(|rest| rest.strip_prefix('='))
+                .map(String::from)
+        }
+    });
+    if let Some(dir) = flag_value.filter(|d| !d.trim().is_empty()) {
+        return Ok((absolute_config_path(&dir)?, ConfigDirSource::CommandLine));
+    }
+
+    if let Some(dir) = std::env::var(CONFIG_DIR_ENV).ok().filter(|d| !d.trim().is_empty()) {
+        return Ok((absolute_config_path(&dir)?, ConfigDirSource::Environment));
+    }
+
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|p| p.to_path_buf()));
+    if let Some(exe_dir) = exe_dir {
+        if args.iter().any(|arg| arg == PORTABLE_FLAG) || exe_dir.join(PORTABLE_MARKER_FILE).exists() {
+            return Ok((exe_dir.join(PORTABLE_DIR_NAME), ConfigDirSource::Portable));
+        }
+    }
+
     match dirs::home_dir() {
-        Some(home) => Ok(home.join(".oddlauncher")),
+        Some(home) => Ok((home.join(".oddlauncher"), ConfigDirSource::Default)),
         None => Err(AppError::new(
             "HOME_DIR_ERROR",
             "Could not determine user home directory",
@@ -15,13 +102,40 @@ fn get_config_dir() -> AppResult<PathBuf> {
     }
 }
 
-/// Get the path to the main configuration file (~/.oddlauncher/apps.json)
-fn get_config_file_path() -> AppResult<PathBuf> {
-    Ok(get_config_dir()?.join("apps.json"))
+/// Expand a leading `~` and make relative override paths absolute against the current directory
+fn absolute_config_path(dir: &str) -> AppResult<PathBuf> {
+    let dir = dir.trim();
+    let path = match dir.strip_prefix("~") {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') || rest.starts_with('\\') => {
+            let home = dirs::home_dir().ok_or_else(|| {
+                AppError::new("HOME_DIR_ERROR", "Could not determine user home directory")
+            })?;
+            home.join(rest.trim_start_matches(['/', '\\']))
+        }
+        _ => PathBuf::from(dir),
+    };
+
+    if path.is_absolute() {
+        return Ok(path);
+    }
+
+    std::env::current_dir()
+        .map(|cwd| cwd.join(path))
+        .map_err(|e| {
+            AppError::new(
+                "DIR_RESOLVE_ERROR",
+                &format!("Failed to resolve config directory: {}", e),
+            )
+        })
+}
+
+/// Get the path to the main configuration file (~/.oddlauncher/apps.json, apps.toml or apps.yaml)
+pub(crate) fn get_config_file_path() -> AppResult<PathBuf> {
+    find_config_file(&get_config_dir()?)
 }
 
 /// Ensure the configuration directory exists
-fn ensure_config_dir_exists() -> AppResult<()> {
+pub(crate) fn ensure_config_dir_exists() -> AppResult<()> {
     let config_dir = get_config_dir()?;
 
     if !config_dir.exists() {
@@ -42,6 +156,14 @@ fn ensure_config_dir_exists() -> AppResult<()> {
 pub async fn load_config(_app: AppHandle) -> AppResult<GlobalConfig> {
     log::info!("Loading configuration from file");
 
+    let config = read_global_config()?;
+
+    log::info!("Successfully loaded configuration with {} apps", config.apps.len());
+    Ok(config)
+}
+
+/// Read the global configuration from disk without going through the Tauri command layer
+pub(crate) fn read_global_config() -> AppResult<GlobalConfig> {
     let config_file = get_config_file_path()?;
 
     // If config file doesn't exist, return default config
@@ -58,48 +180,111 @@ pub async fn load_config(_app: AppHandle) -> AppResult<GlobalConfig> {
         )
     })?;
 
-    let config: GlobalConfig = serde_json::from_str(&config_content).map_err(|e| {
+    let format = ConfigFormat::from_path(&config_file).unwrap_or(ConfigFormat::Json);
+    let config = format.parse(&config_content).map_err(|e| {
         AppError::new(
-            "JSON_PARSE_ERROR",
+            &format!("{}_PARSE_ERROR", format.extension().to_uppercase()),
             &format!("Failed to parse config file: {}", e),
         )
     })?;
 
-    log::info!("Successfully loaded configuration with {} apps", config.apps.len());
     Ok(config)
 }
 
+/// Find a saved app by ID, falling back to a case-insensitive name match
+pub(crate) fn find_app<'a>(config: &'a GlobalConfig, reference: &str) -> AppResult<&'a AppConfig> {
+    config
+        .apps
+        .iter()
+        .find(|a| a.id == reference)
+        .or_else(|| config.apps.iter().find(|a| a.name.eq_ignore_ascii_case(reference.trim())))
+        .ok_or_else(|| {
+            AppError::new(
+                "APP_NOT_FOUND_ERROR",
+                &format!("No app with ID or name '{}'", reference),
+            )
+        })
+}
+
 /// Save the global configuration to file
 #[tauri::command]
 pub async fn save_config(_app: AppHandle, config: GlobalConfig) -> AppResult<()> {
     log::info!("Saving configuration to file");
 
+    // Validate apps that are new or changed; untouched apps are saved as they are
+    let current = read_global_config().unwrap_or_default();
+    if config.settings.custom_shells != current.settings.custom_shells {
+        ensure_valid_custom_shells(&config.settings.custom_shells)?;
+    }
+    for app_config in &config.apps {
+        if !current.apps.iter().any(|a| a == app_config) {
+            ensure_valid_app(app_config, &config.apps, &config.settings.custom_shells)?;
+        }
+    }
+
+    let saved_count = config.apps.len();
+    write_global_config(config)?;
+
+    log::info!("Successfully saved configuration with {} apps", saved_count);
+    Ok(())
+}
+
+/// Write the global configuration to disk without going through the Tauri command layer
+///
+/// The change is recorded in the undo history.
+pub(crate) fn write_global_config(config: GlobalConfig) -> AppResult<()> {
+    let previous = read_global_config().ok();
+
+    store_global_config(config.clone())?;
+
+    if let Some(previous) = previous {
+        if let Err(e) = record_config_change(&previous, &config) {
+            log::warn!("Failed to record configuration history: {}", e.message);
+        }
+    }
+
+    Ok(())
+}
+
+/// Write the global configuration to disk without recording it in the undo history
+pub(crate) fn store_global_config(config: GlobalConfig) -> AppResult<()> {
+    let config_file = get_config_file_path()?;
+    let format = ConfigFormat::from_path(&config_file).unwrap_or(ConfigFormat::Json);
+
+    store_global_config_as(config, &config_file, format)
+}
+
+/// Write the global configuration to a specific file in the given format
+pub(crate) fn store_global_config_as(
+    config: GlobalConfig,
+    config_file: &Path,
+    format: ConfigFormat,
+) -> AppResult<()> {
     // Ensure config directory exists
     ensure_config_dir_exists()?;
 
-    let config_file = get_config_file_path()?;
+    // Snapshot the current file first if this save would drop any apps
+    snapshot_before_destructive_save(&config)?;
 
     // Update the last_modified timestamp
     let mut updated_config = config;
     updated_config.last_modified = chrono::Utc::now().to_rfc3339();
 
-    // Serialize to JSON with pretty formatting
-    let config_json = serde_json::to_string_pretty(&updated_config).map_err(|e| {
+    let config_content = format.serialize(&updated_config).map_err(|e| {
         AppError::new(
-            "JSON_SERIALIZE_ERROR",
+            &format!("{}_SERIALIZE_ERROR", format.extension().to_uppercase()),
             &format!("Failed to serialize config: {}", e),
         )
     })?;
 
     // Write to file
-    fs::write(&config_file, config_json).map_err(|e| {
+    fs::write(config_file, config_content).map_err(|e| {
         AppError::new(
             "FILE_WRITE_ERROR",
             &format!("Failed to write config file: {}", e),
         )
     })?;
 
-    log::info!("Successfully saved configuration with {} apps", updated_config.apps.len());
     Ok(())
 }
 
@@ -118,6 +303,8 @@ pub async fn add_app_config(app: AppHandle, app_config: AppConfig) -> AppResult<
         ));
     }
 
+    ensure_valid_app(&app_config, &config.apps, &config.settings.custom_shells)?;
+
     config.apps.push(app_config.clone());
     save_config(app, config.clone()).await?;
 
@@ -144,6 +331,8 @@ pub async fn update_app_config(app: AppHandle, app_config: AppConfig) -> AppResu
             )
         })?;
 
+    ensure_valid_app(&app_config, &config.apps, &config.settings.custom_shells)?;
+
     config.apps[app_index] = app_config.clone();
     save_config(app, config.clone()).await?;
 
@@ -178,12 +367,14 @@ pub async fn remove_app_config(app: AppHandle, app_id: String) -> AppResult<Glob
 /// Get information about the configuration directory
 #[tauri::command]
 pub async fn get_config_info(_app: AppHandle) -> AppResult<serde_json::Value> {
-    let config_dir = get_config_dir()?;
+    let (config_dir, config_dir_source) = get_config_dir_with_source()?;
     let config_file = get_config_file_path()?;
 
     let info = serde_json::json!({
         "configDir": config_dir.to_string_lossy(),
+        "configDirSource": config_dir_source,
         "configFile": config_file.to_string_lossy(),
+        "configFormat": ConfigFormat::from_path(&config_file).unwrap_or(ConfigFormat::Json),
         "configDirExists": config_dir.exists(),
         "configFileExists": config_file.exists(),
         "lastModified": config_file.metadata()
@@ -196,69 +387,517 @@ pub async fn get_config_info(_app: AppHandle) -> AppResult<serde_json::Value> {
     Ok(info)
 }
 
-/// Backup the current configuration
+/**
+ * How to handle an imported app whose ID or name matches an existing app
+ */
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictStrategy {
+    /// Keep the existing app and ignore the imported one
+    Skip,
+    /// Replace the existing app, keeping its ID
+    Overwrite,
+    /// Import as a new app with a fresh ID and a unique name
+    #[default]
+    Duplicate,
+}
+
+/**
+ * Options for exporting app configurations
+ */
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ExportOptions {
+    /// IDs of the apps to export (all apps when omitted)
+    pub app_ids: Option<Vec<String>>,
+    /// Include global settings, producing a full configuration export
+    pub include_settings: bool,
+    /// Drop secret references and replace sensitive-looking variable values with `${NAME}` placeholders
+    pub strip_secrets: bool,
+    /// Rewrite paths under the home directory to `${HOME}/...` and drop other absolute paths
+    pub strip_absolute_paths: bool,
+}
+
+/**
+ * Options for importing app configurations
+ */
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ImportOptions {
+    /// Conflict handling for apps matching an existing ID or name
+    pub strategy: ConflictStrategy,
+    /// Report what would happen without saving anything
+    pub dry_run: bool,
+}
+
+/**
+ * Outcome for a single imported app
+ */
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportAction {
+    Added,
+    Overwritten,
+    Duplicated,
+    Skipped,
+    Invalid,
+}
+
+/**
+ * Import report entry for a single app
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportItemResult {
+    /// Position of the app in the imported file
+    pub index: usize,
+    /// ID of the app as saved (or as found in the file if not saved)
+    pub id: Option<String>,
+    /// Name of the app as saved (or as found in the file if not saved)
+    pub name: Option<String>,
+    pub action: ImportAction,
+    /// Why the app was skipped, renamed or overwritten
+    pub reason: Option<String>,
+    /// Validation errors for invalid apps
+    pub errors: Vec<String>,
+}
+
+/**
+ * Detailed result of an import
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    pub total: usize,
+    pub added: usize,
+    pub overwritten: usize,
+    pub duplicated: usize,
+    pub skipped: usize,
+    pub invalid: usize,
+    pub dry_run: bool,
+    pub items: Vec<ImportItemResult>,
+}
+
+/// Version of the export file format
+const EXPORT_FORMAT_VERSION: &str = "1.0.0";
+
+/// Environment variable name fragments treated as secrets when stripping exports
+const SENSITIVE_NAME_PATTERNS: [&str; 9] = [
+    "SECRET", "TOKEN", "PASSWORD", "PASSWD", "API_KEY", "APIKEY", "PRIVATE_KEY", "ACCESS_KEY", "CREDENTIAL",
+];
+
+/// Export apps (and optionally settings) as a JSON document
 #[tauri::command]
-pub async fn backup_config(_app: AppHandle) -> AppResult<String> {
-    log::info!("Creating configuration backup");
+pub async fn export_apps(_app: AppHandle, options: Option<ExportOptions>) -> AppResult<String> {
+    let options = options.unwrap_or_default();
+    let config = read_global_config()?;
+    export_config(&config, &options)
+}
 
-    let config_file = get_config_file_path()?;
+/// Build the export document for the given configuration
+pub(crate) fn export_config(config: &GlobalConfig, options: &ExportOptions) -> AppResult<String> {
+    let mut apps: Vec<AppConfig> = match options.app_ids {
+        Some(ref ids) => {
+            let missing: Vec<&String> = ids.iter().filter(|id| !config.apps.iter().any(|a| &a.id == *id)).collect();
+            if !missing.is_empty() {
+                return Err(AppError::new(
+                    "APP_NOT_FOUND_ERROR",
+                    &format!("Apps not found: {}", missing.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", ")),
+                ));
+            }
+            config.apps.iter().filter(|a| ids.contains(&a.id)).cloned().collect()
+        }
+        None => config.apps.clone(),
+    };
+
+    log::info!("Exporting {} apps", apps.len());
+
+    let home = dirs::home_dir();
+    for app in apps.iter_mut() {
+        if options.strip_secrets {
+            strip_app_secrets(app);
+        }
+        if options.strip_absolute_paths {
+            strip_app_paths(app, home.as_deref());
+        }
+    }
 
-    if !config_file.exists() {
-        return Err(AppError::new(
-            "NO_CONFIG_ERROR",
-            "No configuration file exists to backup",
-        ));
+    let (export_type, data) = if options.include_settings {
+        let mut exported = config.clone();
+        exported.apps = apps;
+        ("full-config", serde_json::to_value(exported))
+    } else if apps.len() == 1 && options.app_ids.is_some() {
+        ("single-app", serde_json::to_value(&apps[0]))
+    } else {
+        ("apps", serde_json::to_value(&apps))
+    };
+    let data = data.map_err(|e| {
+        AppError::new("JSON_SERIALIZE_ERROR", &format!("Failed to serialize export: {}", e))
+    })?;
+
+    let export = serde_json::json!({
+        "version": EXPORT_FORMAT_VERSION,
+        "exportDate": chrono::Utc::now().to_rfc3339(),
+        "type": export_type,
+        "data": data,
+    });
+
+    serde_json::to_string_pretty(&export).map_err(|e| {
+        AppError::new("JSON_SERIALIZE_ERROR", &format!("Failed to serialize export: {}", e))
+    })
+}
+
+/// Remove secret references and placeholder sensitive-looking inline values
+fn strip_app_secrets(app: &mut AppConfig) {
+    app.secret_variables = None;
+
+    let strip = |env: &mut HashMap<String, String>| {
+        for (name, value) in env.iter_mut() {
+            let upper = name.to_uppercase();
+            if SENSITIVE_NAME_PATTERNS.iter().any(|p| upper.contains(p)) {
+                *value = format!("${{{}}}", name);
+            }
+        }
+    };
+
+    if let Some(ref mut env) = app.environment_variables {
+        strip(env);
+    }
+    if let Some(ref mut profiles) = app.profiles {
+        for profile in profiles.iter_mut() {
+            if let Some(ref mut env) = profile.environment_variables {
+                strip(env);
+            }
+        }
     }
+}
 
-    // Create backup filename with timestamp
-    let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
-    let backup_file = get_config_dir()?.join(format!("apps_backup_{}.json", timestamp));
+/// Make machine-specific paths portable
+fn strip_app_paths(app: &mut AppConfig, home: Option<&Path>) {
+    app.working_directory = app.working_directory.take().and_then(|dir| {
+        if !is_absolute_path(&dir) {
+            return Some(dir);
+        }
+        home.and_then(|home| home_relative(&dir, home))
+    });
 
-    fs::copy(&config_file, &backup_file).map_err(|e| {
-        AppError::new(
-            "BACKUP_ERROR",
-            &format!("Failed to create backup: {}", e),
-        )
-    })?;
+    if let Some(ref mut env_files) = app.env_files {
+        env_files.retain(|path| !is_absolute_path(path));
+    }
+}
+
+/// Rewrite a path inside the home directory as `${HOME}/...`
+///
+/// Compares whole path components, so `/home/alice2` isn't inside `/home/alice`.
+fn home_relative(dir: &str, home: &Path) -> Option<String> {
+    let home_str = home.to_string_lossy();
+    let rest = dir.strip_prefix(home_str.as_ref())?;
+    Path::new(dir)
+        .starts_with(home)
+        .then(|| format!("${{HOME}}{}", rest))
+}
 
-    let backup_path = backup_file.to_string_lossy().to_string();
-    log::info!("Successfully created backup: {}", backup_path);
-    Ok(backup_path)
+/// Check for absolute Unix, Windows drive or UNC paths regardless of the current platform
+fn is_absolute_path(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    path.starts_with('/')
+        || path.starts_with("\\\\")
+        || (bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':')
 }
 
-/// Restore configuration from backup
+/// Import apps from an export document, apps.json file or bare array of apps
 #[tauri::command]
-pub async fn restore_config(app: AppHandle, backup_path: String) -> AppResult<GlobalConfig> {
-    log::info!("Restoring configuration from backup: {}", backup_path);
+pub async fn import_apps(
+    _app: AppHandle,
+    content: String,
+    options: Option<ImportOptions>,
+) -> AppResult<ImportReport> {
+    import_content(&content, &options.unwrap_or_default())
+}
 
-    let backup_file = PathBuf::from(&backup_path);
+/// Import apps from an export document into the saved configuration
+pub(crate) fn import_content(content: &str, options: &ImportOptions) -> AppResult<ImportReport> {
+    let mut config = read_global_config()?;
 
-    if !backup_file.exists() {
-        return Err(AppError::new(
-            "BACKUP_NOT_FOUND_ERROR",
-            "Backup file not found",
-        ));
+    let report = import_into_config(&mut config, content, options)?;
+
+    if !options.dry_run && report.added + report.overwritten + report.duplicated > 0 {
+        // Overwritten apps are snapshotted by the save
+        write_global_config(config)?;
     }
 
-    // Read backup file
-    let backup_content = fs::read_to_string(&backup_file).map_err(|e| {
-        AppError::new(
-            "BACKUP_READ_ERROR",
-            &format!("Failed to read backup file: {}", e),
-        )
+    log::info!(
+        "Import finished: {} added, {} overwritten, {} duplicated, {} skipped, {} invalid{}",
+        report.added,
+        report.overwritten,
+        report.duplicated,
+        report.skipped,
+        report.invalid,
+        if report.dry_run { " (dry run)" } else { "" }
+    );
+    Ok(report)
+}
+
+/// Merge the apps from an import document into the configuration
+pub(crate) fn import_into_config(
+    config: &mut GlobalConfig,
+    content: &str,
+    options: &ImportOptions,
+) -> AppResult<ImportReport> {
+    let document: serde_json::Value = serde_json::from_str(content).map_err(|e| {
+        AppError::new("IMPORT_PARSE_ERROR", &format!("Invalid JSON: {}", e))
     })?;
+    let entries = extract_import_entries(document)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let mut report = ImportReport {
+        total: entries.len(),
+        added: 0,
+        overwritten: 0,
+        duplicated: 0,
+        skipped: 0,
+        invalid: 0,
+        dry_run: options.dry_run,
+        items: Vec::new(),
+    };
+
+    for (index, entry) in entries.into_iter().enumerate() {
+        let raw_id = entry.get("id").and_then(|v| v.as_str()).map(String::from);
+        let raw_name = entry.get("name").and_then(|v| v.as_str()).map(String::from);
+
+        let mut app: AppConfig = match serde_json::from_value(entry) {
+            Ok(app) => app,
+            Err(e) => {
+                report.invalid += 1;
+                report.items.push(ImportItemResult {
+                    index,
+                    id: raw_id,
+                    name: raw_name,
+                    action: ImportAction::Invalid,
+                    reason: None,
+                    errors: vec![format!("Invalid app configuration: {}", e)],
+                });
+                continue;
+            }
+        };
+
+        // Name clashes are handled by the conflict strategy, so validate in isolation
+        let validation = validate_app(&app, &[], &config.settings.custom_shells);
+        if !validation.valid {
+            let errors = validation
+                .errors
+                .iter()
+                .map(|issue| format!("{}: {}", issue.field, issue.message))
+                .collect();
+            report.invalid += 1;
+            report.items.push(ImportItemResult {
+                index,
+                id: Some(app.id),
+                name: Some(app.name),
+                action: ImportAction::Invalid,
+                reason: None,
+                errors,
+            });
+            continue;
+        }
+
+        let conflict = config
+            .apps
+            .iter()
+            .position(|a| a.id == app.id)
+            .map(|i| (i, format!("ID conflicts with existing app '{}'", config.apps[i].name)))
+            .or_else(|| {
+                config
+                    .apps
+                    .iter()
+                    .position(|a| a.name.eq_ignore_ascii_case(&app.name))
+                    .map(|i| (i, format!("Name conflicts with existing app '{}'", config.apps[i].name)))
+            });
+
+        let (action, reason) = match (conflict, options.strategy) {
+            (None, _) => {
+                app.updated_at = now.clone();
+                config.apps.push(app.clone());
+                report.added += 1;
+                (ImportAction::Added, None)
+            }
+            (Some((_, reason)), ConflictStrategy::Skip) => {
+                report.skipped += 1;
+                (ImportAction::Skipped, Some(reason))
+            }
+            (Some((existing, reason)), ConflictStrategy::Overwrite) => {
+                app.id = config.apps[existing].id.clone();
+                app.created_at = config.apps[existing].created_at.clone();
+                app.updated_at = now.clone();
+                config.apps[existing] = app.clone();
+                report.overwritten += 1;
+                (ImportAction::Overwritten, Some(reason))
+            }
+            (Some((_, reason)), ConflictStrategy::Duplicate) => {
+                app.id = uuid::Uuid::new_v4().to_string();
+                app.name = unique_app_name(&app.name, &config.apps);
+                app.created_at = now.clone();
+                app.updated_at = now.clone();
+                config.apps.push(app.clone());
+                report.duplicated += 1;
+                (ImportAction::Duplicated, Some(reason))
+            }
+        };
+
+        report.items.push(ImportItemResult {
+            index,
+            id: Some(app.id),
+            name: Some(app.name),
+            action,
+            reason,
+            errors: Vec::new(),
+        });
+    }
 
-    // Parse and validate backup
-    let config: GlobalConfig = serde_json::from_str(&backup_content).map_err(|e| {
+    Ok(report)
+}
+
+/// Pull the list of raw app objects out of any supported import document
+fn extract_import_entries(document: serde_json::Value) -> AppResult<Vec<serde_json::Value>> {
+    let unsupported = || {
         AppError::new(
-            "BACKUP_PARSE_ERROR",
-            &format!("Failed to parse backup file: {}", e),
+            "IMPORT_FORMAT_ERROR",
+            "Unsupported import format: expected an OddLauncher export, apps.json or a list of apps",
         )
-    })?;
+    };
+
+    let data = match document.get("type").and_then(|t| t.as_str()) {
+        Some("single-app") => return Ok(vec![document.get("data").cloned().ok_or_else(unsupported)?]),
+        Some("apps") | Some("full-config") => document.get("data").cloned().ok_or_else(unsupported)?,
+        Some(other) => {
+            return Err(AppError::new(
+                "IMPORT_FORMAT_ERROR",
+                &format!("Unsupported export type: {}", other),
+            ))
+        }
+        None => document,
+    };
+
+    match data {
+        serde_json::Value::Array(apps) => Ok(apps),
+        serde_json::Value::Object(ref map) => match map.get("apps") {
+            Some(serde_json::Value::Array(apps)) => Ok(apps.clone()),
+            _ => Err(unsupported()),
+        },
+        _ => Err(unsupported()),
+    }
+}
 
-    // Save restored config
-    save_config(app, config.clone()).await?;
+/// Generate a name that doesn't collide with existing apps ("Name (1)", "Name (2)", ...)
+fn unique_app_name(name: &str, apps: &[AppConfig]) -> String {
+    let taken = |candidate: &str| apps.iter().any(|a| a.name.eq_ignore_ascii_case(candidate));
+    if !taken(name) {
+        return name.to_string();
+    }
+    let mut counter = 1;
+    let mut candidate = format!("{} ({})", name, counter);
+    while taken(&candidate) {
+        counter += 1;
+        candidate = format!("{} ({})", name, counter);
+    }
+    candidate
+}
 
-    log::info!("Successfully restored configuration from backup");
-    Ok(config)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config(apps: Vec<AppConfig>) -> GlobalConfig {
+        GlobalConfig { apps, ..GlobalConfig::default() }
+    }
+
+    fn import(target: &mut GlobalConfig, source: &GlobalConfig, strategy: ConflictStrategy) -> ImportReport {
+        let export = export_config(source, &ExportOptions::default()).unwrap();
+        import_into_config(target, &export, &ImportOptions { strategy, dry_run: false }).unwrap()
+    }
+
+    #[test]
+    fn export_round_trips_into_empty_config() {
+        let mut source = AppConfig::for_test("a", "API", "npm start");
+        source.working_directory = Some("/srv/api".to_string());
+        source.tags = Some(vec!["web".to_string()]);
+        let mut target = config(Vec::new());
+
+        let report = import(&mut target, &config(vec![source.clone()]), ConflictStrategy::Skip);
+        assert_eq!((report.total, report.added), (1, 1));
+        assert_eq!(target.apps.len(), 1);
+        let imported = &target.apps[0];
+        assert_eq!(imported.id, "a");
+        assert_eq!(imported.launch_commands, source.launch_commands);
+        assert_eq!(imported.working_directory, source.working_directory);
+        assert_eq!(imported.tags, source.tags);
+    }
+
+    #[test]
+    fn conflicts_can_be_skipped() {
+        let mut target = config(vec![AppConfig::for_test("a", "API", "npm start")]);
+        let incoming = config(vec![AppConfig::for_test("a", "Renamed", "npm run dev")]);
+        let report = import(&mut target, &incoming, ConflictStrategy::Skip);
+        assert_eq!((report.skipped, report.items[0].action.clone()), (1, ImportAction::Skipped));
+        assert_eq!(target.apps.len(), 1);
+        assert_eq!(target.apps[0].launch_commands.as_deref(), Some("npm start"));
+    }
+
+    #[test]
+    fn conflicts_can_overwrite() {
+        let mut target = config(vec![AppConfig::for_test("a", "API", "npm start")]);
+        // Matched by name, so the existing ID and creation time are kept
+        let incoming = config(vec![AppConfig::for_test("b", "api", "npm run dev")]);
+        let report = import(&mut target, &incoming, ConflictStrategy::Overwrite);
+        assert_eq!(report.overwritten, 1);
+        assert_eq!(target.apps.len(), 1);
+        assert_eq!(target.apps[0].id, "a");
+        assert_eq!(target.apps[0].name, "api");
+        assert_eq!(target.apps[0].launch_commands.as_deref(), Some("npm run dev"));
+    }
+
+    #[test]
+    fn conflicts_can_duplicate() {
+        let mut target = config(vec![AppConfig::for_test("a", "API", "npm start")]);
+        let incoming = config(vec![AppConfig::for_test("a", "API", "npm run dev")]);
+        let report = import(&mut target, &incoming, ConflictStrategy::Duplicate);
+        assert_eq!(report.duplicated, 1);
+        assert_eq!(target.apps.len(), 2);
+        assert_ne!(target.apps[1].id, "a");
+        assert_eq!(target.apps[1].name, "API (1)");
+        assert_eq!(target.apps[1].launch_commands.as_deref(), Some("npm run dev"));
+    }
+
+    #[test]
+    fn invalid_apps_are_reported() {
+        let mut target = config(Vec::new());
+        let content = json!([{ "id": "x", "name": "", "createdAt": "", "updatedAt": "" }, { "name": 1 }]).to_string();
+        let report = import_into_config(&mut target, &content, &ImportOptions::default()).unwrap();
+        assert_eq!(report.invalid, 2);
+        assert!(target.apps.is_empty());
+    }
+
+    #[test]
+    fn paths_are_made_relative_to_home() {
+        let home = Path::new("/home/alice");
+        assert_eq!(home_relative("/home/alice/code/api", home).as_deref(), Some("${HOME}/code/api"));
+        assert_eq!(home_relative("/home/alice", home).as_deref(), Some("${HOME}"));
+        assert_eq!(home_relative("/home/alice2/code", home), None);
+        assert_eq!(home_relative("/srv/api", home), None);
+    }
+
+    #[test]
+    fn stripping_paths_drops_other_absolute_paths() {
+        let mut stripped = AppConfig::for_test("a", "API", "npm start");
+        stripped.working_directory = Some("/home/alice2/api".to_string());
+        stripped.env_files = Some(vec![".env".to_string(), "/etc/api.env".to_string(), "C:\\api.env".to_string()]);
+        strip_app_paths(&mut stripped, Some(Path::new("/home/alice")));
+        assert_eq!(stripped.working_directory, None);
+        assert_eq!(stripped.env_files, Some(vec![".env".to_string()]));
+
+        stripped.working_directory = Some("relative/dir".to_string());
+        strip_app_paths(&mut stripped, Some(Path::new("/home/alice")));
+        assert_eq!(stripped.working_directory.as_deref(), Some("relative/dir"));
+    }
 }
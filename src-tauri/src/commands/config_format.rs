@@ -0,0 +1,230 @@
+use crate::commands::backup::create_snapshot;
+use crate::commands::config::{get_config_dir, get_config_file_path, read_global_config, store_global_config_as};
+use crate::models::app::{AppError, AppResult, GlobalConfig};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+/**
+ * Storage format of the main configuration file
+ */
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+/// Config file names in the order they're checked (`apps.yml` is accepted as YAML)
+const CONFIG_FILE_NAMES: [&str; 4] = ["apps.json", "apps.toml", "apps.yaml", "apps.yml"];
+
+impl ConfigFormat {
+    /// File extension used for this format
+    pub fn extension(self) -> &'static str {
+        match self {
+            ConfigFormat::Json => "json",
+            ConfigFormat::Toml => "toml",
+            ConfigFormat::Yaml => "yaml",
+        }
+    }
+
+    /// Determine the format from a file's extension
+    pub fn from_path(path: &Path) -> Option<ConfigFormat> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "json" => Some(ConfigFormat::Json),
+            "toml" => Some(ConfigFormat::Toml),
+            "yaml" | "yml" => Some(ConfigFormat::Yaml),
+            _ => None,
+        }
+    }
+
+    /// Parse a document in this format: the configuration, a backup of it or a user template
+    pub fn parse<T: DeserializeOwned>(self, content: &str) -> Result<T, String> {
+        match self {
+            ConfigFormat::Json => serde_json::from_str(content).map_err(|e| e.to_string()),
+            ConfigFormat::Toml => toml::from_str(content).map_err(|e| e.to_string()),
+            // serde_yaml is no longer maintained, but 0.9 is stable and its output is plain YAML 1.2;
+            // every YAML file (config, backups, templates) goes through here, so swapping it for a
+            // fork only needs these two calls changed
+            ConfigFormat::Yaml => serde_yaml::from_str(content).map_err(|e| e.to_string()),
+        }
+    }
+
+    /// Serialize a configuration in this format
+    ///
+    /// TOML and YAML write multi-line strings such as launch commands as readable blocks.
+    pub fn serialize(self, config: &GlobalConfig) -> Result<String, String> {
+        match self {
+            ConfigFormat::Json => serde_json::to_string_pretty(config).map_err(|e| e.to_string()),
+            ConfigFormat::Toml => toml::to_string_pretty(config).map_err(|e| e.to_string()),
+            ConfigFormat::Yaml => serde_yaml::to_string(config).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+/// Find the configuration file in `config_dir`, defaulting to `apps.json` when none exists
+///
+/// Having more than one is an error rather than a guess, since saving to one would leave
+/// the others silently out of date.
+pub(crate) fn find_config_file(config_dir: &Path) -> AppResult<PathBuf> {
+    let existing: Vec<PathBuf> = CONFIG_FILE_NAMES
+        .iter()
+        .map(|name| config_dir.join(name))
+        .filter(|path| path.is_file())
+        .collect();
+
+    match existing.as_slice() {
+        [] => Ok(config_dir.join(CONFIG_FILE_NAMES[0])),
+        [path] => Ok(path.clone()),
+        _ => Err(AppError::new(
+            "MULTIPLE_CONFIG_FILES",
+            &format!(
+                "Found several config files in {} ({}); remove or rename all but one",
+                config_dir.display(),
+                existing
+                    .iter()
+                    .map(|p| p.file_name().unwrap_or_default().to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        )),
+    }
+}
+
+/// Convert the configuration file to another storage format
+///
+/// The conversion is verified to round-trip without loss before the new file is written.
+/// The old file is kept as `<name>.bak` so it's no longer picked up.
+#[tauri::command]
+pub async fn convert_config_format(_app: AppHandle, format: ConfigFormat) -> AppResult<String> {
+    let current_file = get_config_file_path()?;
+    let current_format = ConfigFormat::from_path(&current_file).unwrap_or(ConfigFormat::Json);
+
+    if current_format == format && current_file.exists() {
+        log::info!("Configuration is already stored as {}", format.extension());
+        return Ok(current_file.to_string_lossy().to_string());
+    }
+
+    log::info!(
+        "Converting configuration from {} to {}",
+        current_format.extension(),
+        format.extension()
+    );
+
+    let config = read_global_config()?;
+    verify_round_trip(&config, format)?;
+
+    if current_file.exists() {
+        create_snapshot(&config.settings.backup_retention)?;
+    }
+
+    let target_file = get_config_dir()?.join(format!("apps.{}", format.extension()));
+    store_global_config_as(config, &target_file, format)?;
+
+    if current_file.exists() && current_file != target_file {
+        let mut retired = current_file.clone().into_os_string();
+        retired.push(".bak");
+        // Renaming over an existing file fails on Windows
+        let _ = fs::remove_file(&retired);
+        fs::rename(&current_file, &retired).map_err(|e| {
+            AppError::new(
+                "FILE_WRITE_ERROR",
+                &format!("Converted config written, but failed to retire old config file: {}", e),
+            )
+        })?;
+    }
+
+    let target_path = target_file.to_string_lossy().to_string();
+    log::info!("Configuration converted to {}", target_path);
+    Ok(target_path)
+}
+
+/// Make sure a configuration survives serialization in `format` unchanged
+fn verify_round_trip(config: &GlobalConfig, format: ConfigFormat) -> AppResult<()> {
+    let conversion_error = |detail: String| {
+        AppError::new(
+            "CONFIG_CONVERSION_ERROR",
+            &format!("Can't convert configuration to {}: {}", format.extension(), detail),
+        )
+    };
+
+    let serialized = format.serialize(config).map_err(conversion_error)?;
+    let parsed: GlobalConfig = format.parse(&serialized).map_err(conversion_error)?;
+
+    let original = serde_json::to_value(config).map_err(|e| conversion_error(e.to_string()))?;
+    let round_tripped = serde_json::to_value(&parsed).map_err(|e| conversion_error(e.to_string()))?;
+
+    if original != round_tripped {
+        return Err(conversion_error("the converted file would not match the current configuration".to_string()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::app::AppConfig;
+
+    fn config() -> GlobalConfig {
+        let mut app = AppConfig::for_test("api", "API: \"main\"", "npm install\nnpm run dev -- --port 3000\n");
+        app.working_directory = Some("C:\\src\\api".to_string());
+        app.environment_variables = Some(
+            [("NODE_ENV", "development"), ("EMPTY", "")]
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        );
+        app.tags = Some(vec!["web".to_string(), "yes".to_string(), "1.0".to_string()]);
+        app.port_to_check = Some(3000);
+        GlobalConfig { apps: vec![app], ..GlobalConfig::default() }
+    }
+
+    fn round_trip(format: ConfigFormat) {
+        let config = config();
+        verify_round_trip(&config, format).unwrap();
+
+        let parsed: GlobalConfig = format.parse(&format.serialize(&config).unwrap()).unwrap();
+        assert_eq!(parsed.apps[0].launch_commands, config.apps[0].launch_commands);
+        assert_eq!(parsed.apps[0].tags, config.apps[0].tags);
+    }
+
+    #[test]
+    fn toml_round_trips() {
+        round_trip(ConfigFormat::Toml);
+    }
+
+    #[test]
+    fn yaml_round_trips() {
+        round_trip(ConfigFormat::Yaml);
+    }
+
+    #[test]
+    fn formats_from_extension() {
+        assert_eq!(ConfigFormat::from_path(Path::new("apps.YML")), Some(ConfigFormat::Yaml));
+        assert_eq!(ConfigFormat::from_path(Path::new("apps.toml")), Some(ConfigFormat::Toml));
+        assert_eq!(ConfigFormat::from_path(Path::new("apps.json.bak")), None);
+    }
+
+    #[test]
+    fn several_config_files_are_an_error() {
+        let dir = std::env::temp_dir().join(format!("oddlauncher-format-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let default = find_config_file(&dir).unwrap();
+        fs::write(dir.join("apps.yaml"), "").unwrap();
+        fs::write(dir.join("apps.json.bak"), "").unwrap();
+        let single = find_config_file(&dir).unwrap();
+        fs::write(dir.join("apps.toml"), "").unwrap();
+        let several = find_config_file(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(default, dir.join("apps.json"));
+        assert_eq!(single, dir.join("apps.yaml"));
+        let error = several.unwrap_err();
+        assert_eq!(error.code, "MULTIPLE_CONFIG_FILES");
+        assert!(error.message.contains("(apps.toml, apps.yaml)"), "{}", error.message);
+    }
+}
@@ -0,0 +1,666 @@
+use crate::commands::config::{
+    add_app_config, ensure_config_dir_exists, get_config_dir, load_config, remove_app_config,
+    update_app_config,
+};
+use crate::commands::instance::handle_instance_args;
+use crate::commands::process::{
+    get_all_process_status, get_process_status, start_saved_app, stop_app_process, ProcessManager,
+};
+use crate::models::app::{AppConfig, AppError, AppResult};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::OsRng;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Listener, Manager};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::{broadcast, mpsc};
+
+// Local control API: JSON-RPC 2.0 over a Unix domain socket (named pipe on Windows)
+// so editor plugins and scripts can drive a running OddLauncher.
+
+/// Events forwarded to subscribed clients
+const FORWARDED_EVENTS: [&str; 8] = [
+    "process-started",
+    "process-stopped",
+    "process-exit",
+    "process-error",
+    "process-output",
+    "browser-launched",
+    "browser-launch-failed",
+    "config-changed",
+];
+
+/// Events buffered per subscriber before the slowest ones start missing events
+const EVENT_BUFFER_SIZE: usize = 1024;
+
+/// Prefix of the named pipe used on Windows (the socket lives in the config directory elsewhere)
+#[cfg(any(windows, test))]
+const PIPE_PREFIX: &str = r"\\.\pipe\oddlauncher-control-";
+
+// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// The command itself failed; `data` carries the AppError
+const COMMAND_ERROR: i64 = -32000;
+const UNAUTHORIZED: i64 = -32001;
+
+/**
+ * A JSON-RPC request (or notification, when it has no id)
+ */
+#[derive(Debug, Deserialize)]
+struct RpcRequest {
+    jsonrpc: Option<String>,
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/**
+ * A JSON-RPC error object
+ */
+#[derive(Debug, Serialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<Value>,
+}
+
+impl RpcError {
+    fn new(code: i64, message: &str) -> Self {
+        Self {
+            code,
+            message: message.to_string(),
+            data: None,
+        }
+    }
+}
+
+impl From<AppError> for RpcError {
+    fn from(error: AppError) -> Self {
+        Self {
+            code: COMMAND_ERROR,
+            message: error.message.clone(),
+            data: serde_json::to_value(&error).ok(),
+        }
+    }
+}
+
+impl From<String> for RpcError {
+    fn from(message: String) -> Self {
+        Self::new(COMMAND_ERROR, &message)
+    }
+}
+
+/**
+ * An app event as delivered to subscribers
+ */
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ControlEvent {
+    event: String,
+    payload: Value,
+}
+
+/**
+ * Event filter requested by a subscriber
+ */
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct SubscribeParams {
+    /// Event names to receive (all forwarded events when omitted)
+    events: Option<Vec<String>>,
+    /// Only receive events for this app
+    app_id: Option<String>,
+}
+
+/**
+ * Parameters identifying an app
+ */
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AppIdParams {
+    app_id: String,
+}
+
+/**
+ * Parameters for starting a saved app
+ */
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StartParams {
+    app_id: String,
+    profile: Option<String>,
+}
+
+/**
+ * Parameters carrying a full app configuration
+ */
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AppConfigParams {
+    app_config: AppConfig,
+}
+
+/**
+ * Arguments forwarded by a second launch of the app
+ */
+#[derive(Debug, Deserialize)]
+struct InstanceArgsParams {
+    args: Vec<String>,
+}
+
+/// Get the path to the control API token (~/.oddlauncher/control.token)
+pub(crate) fn get_control_token_path() -> AppResult<PathBuf> {
+    Ok(get_config_dir()?.join("control.token"))
+}
+
+/// Get the path to the file naming the control API's pipe on Windows (~/.oddlauncher/control.pipe)
+#[cfg(windows)]
+fn get_control_pipe_path() -> AppResult<PathBuf> {
+    Ok(get_config_dir()?.join("control.pipe"))
+}
+
+/// Get the control API endpoint: a socket in the config directory, or a named pipe on Windows
+pub(crate) fn get_control_endpoint() -> AppResult<String> {
+    #[cfg(windows)]
+    {
+        // The same directory can be reached by different spellings of its path
+        let config_dir = get_config_dir()?;
+        Ok(pipe_name(&config_dir.canonicalize().unwrap_or(config_dir)))
+    }
+    #[cfg(not(windows))]
+    {
+        Ok(get_config_dir()?.join("control.sock").to_string_lossy().to_string())
+    }
+}
+
+/// Named pipe for a config directory
+///
+/// Pipes live in one global namespace, so the name is derived from the config directory
+/// (case-insensitively, as Windows paths are) to give each directory's instance its own.
+#[cfg(any(windows, test))]
+fn pipe_name(config_dir: &std::path::Path) -> String {
+    // FNV-1a: stable across builds, unlike the standard library's hasher
+    let hash = config_dir
+        .to_string_lossy()
+        .to_lowercase()
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+    format!("{}{:016x}", PIPE_PREFIX, hash)
+}
+
+/// Start the control API server in the background
+///
+/// Failures are logged rather than returned; the rest of the app works without it.
+pub fn start_control_server(app: AppHandle) {
+    let token = match write_new_token() {
+        Ok(token) => Arc::new(token),
+        Err(e) => {
+            log::error!("Control API disabled: {}", e.message);
+            return;
+        }
+    };
+
+    // Clients can't look pipes up by config directory, so the name is published next to the token
+    #[cfg(windows)]
+    if let Err(e) = write_pipe_name() {
+        log::warn!("{}", e.message);
+    }
+
+    // Bridge app events into a channel that every subscribed connection can read from
+    let (events, _) = broadcast::channel::<ControlEvent>(EVENT_BUFFER_SIZE);
+    for name in FORWARDED_EVENTS {
+        let sender = events.clone();
+        app.listen_any(name, move |event| {
+            let payload = serde_json::from_str(event.payload()).unwrap_or(Value::Null);
+            let _ = sender.send(ControlEvent {
+                event: name.to_string(),
+                payload,
+            });
+        });
+    }
+
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = serve(app, token, events).await {
+            log::error!("Control API stopped: {}", e);
+        }
+    });
+}
+
+/// Record the pipe name in the config directory for clients
+#[cfg(windows)]
+fn write_pipe_name() -> AppResult<()> {
+    fs::write(get_control_pipe_path()?, get_control_endpoint()?).map_err(|e| {
+        AppError::new(
+            "FILE_WRITE_ERROR",
+            &format!("Failed to write control pipe name: {}", e),
+        )
+    })
+}
+
+/// Generate a fresh token for this run and write it where only the current user can read it
+fn write_new_token() -> AppResult<String> {
+    ensure_config_dir_exists()?;
+
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    let token: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+
+    let write_error = |e: std::io::Error| {
+        AppError::new(
+            "FILE_WRITE_ERROR",
+            &format!("Failed to write control API token: {}", e),
+        )
+    };
+
+    // Replace rather than truncate the previous run's file, so the new one is created private
+    let path = get_control_token_path()?;
+    let _ = fs::remove_file(&path);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&path).map_err(write_error)?;
+    std::io::Write::write_all(&mut file, token.as_bytes()).map_err(write_error)?;
+
+    Ok(token)
+}
+
+#[cfg(unix)]
+async fn serve(
+    app: AppHandle,
+    token: Arc<String>,
+    events: broadcast::Sender<ControlEvent>,
+) -> Result<(), String> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+    use tokio::net::{UnixListener, UnixStream};
+
+    let endpoint = get_control_endpoint().map_err(|e| e.message)?;
+
+    if std::path::Path::new(&endpoint).exists() {
+        if UnixStream::connect(&endpoint).await.is_ok() {
+            return Err(format!("Another instance is already listening on {}", endpoint));
+        }
+        // Left behind by a previous run that didn't shut down cleanly
+        let _ = fs::remove_file(&endpoint);
+    }
+
+    // Bind inside a directory only this user can enter and move the socket into place once its
+    // own permissions are set, so other users never get a window in which they can connect
+    let config_dir = get_config_dir().map_err(|e| e.message)?;
+    let staging_dir = config_dir.join(format!(".control-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&staging_dir);
+    fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&staging_dir)
+        .map_err(|e| format!("Failed to create {}: {}", staging_dir.display(), e))?;
+
+    let staged = staging_dir.join("control.sock");
+    let bound = UnixListener::bind(&staged)
+        .map_err(|e| format!("Failed to bind {}: {}", endpoint, e))
+        .and_then(|listener| {
+            fs::set_permissions(&staged, fs::Permissions::from_mode(0o600))
+                .and_then(|()| fs::rename(&staged, &endpoint))
+                .map_err(|e| format!("Failed to bind {}: {}", endpoint, e))?;
+            Ok(listener)
+        });
+    let _ = fs::remove_dir_all(&staging_dir);
+    let listener = bound?;
+    log::info!("Control API listening on {}", endpoint);
+
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tauri::async_runtime::spawn(handle_connection(
+                    stream,
+                    app.clone(),
+                    Arc::clone(&token),
+                    events.clone(),
+                ));
+            }
+            Err(e) => log::warn!("Control API failed to accept a connection: {}", e),
+        }
+    }
+}
+
+#[cfg(windows)]
+async fn serve(
+    app: AppHandle,
+    token: Arc<String>,
+    events: broadcast::Sender<ControlEvent>,
+) -> Result<(), String> {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    let endpoint = get_control_endpoint().map_err(|e| e.message)?;
+    let mut server = ServerOptions::new()
+        .first_pipe_instance(true)
+        .create(&endpoint)
+        .map_err(|e| format!("Failed to create {}: {}", endpoint, e))?;
+    log::info!("Control API listening on {}", endpoint);
+
+    loop {
+        server
+            .connect()
+            .await
+            .map_err(|e| format!("Failed to accept a connection: {}", e))?;
+
+        // Create the next instance before handing this one off so clients never find the pipe missing
+        let connected = server;
+        server = ServerOptions::new()
+            .create(&endpoint)
+            .map_err(|e| format!("Failed to create {}: {}", endpoint, e))?;
+
+        tauri::async_runtime::spawn(handle_connection(
+            connected,
+            app.clone(),
+            Arc::clone(&token),
+            events.clone(),
+        ));
+    }
+}
+
+/// Serve newline-delimited JSON-RPC requests on one connection until the client disconnects
+async fn handle_connection<S>(
+    stream: S,
+    app: AppHandle,
+    token: Arc<String>,
+    events: broadcast::Sender<ControlEvent>,
+) where
+    S: AsyncRead + AsyncWrite + Send + 'static,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+
+    // Responses and event notifications share the connection, so all writes go through one task
+    let (outgoing, mut outgoing_rx) = mpsc::unbounded_channel::<String>();
+    let writer_task = tauri::async_runtime::spawn(async move {
+        while let Some(mut line) = outgoing_rx.recv().await {
+            line.push('\n');
+            if writer.write_all(line.as_bytes()).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut authenticated = false;
+    let mut subscription: Option<tauri::async_runtime::JoinHandle<()>> = None;
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let request = match serde_json::from_str::<RpcRequest>(&line) {
+            Ok(request) if request.jsonrpc.as_deref() == Some("2.0") => request,
+            Ok(request) => {
+                send_response(&outgoing, request.id, Err(RpcError::new(INVALID_REQUEST, "Expected jsonrpc \"2.0\"")));
+                continue;
+            }
+            Err(e) => {
+                let code = if serde_json::from_str::<Value>(&line).is_ok() { INVALID_REQUEST } else { PARSE_ERROR };
+                send_response(&outgoing, Some(Value::Null), Err(RpcError::new(code, &e.to_string())));
+                continue;
+            }
+        };
+
+        let result = match authorize(&request.method, &request.params, &token, &mut authenticated) {
+            Some(result) => result,
+            None => match request.method.as_str() {
+                "subscribe" => parse_params::<SubscribeParams>(request.params.clone()).map(|filter| {
+                    if let Some(previous) = subscription.take() {
+                        previous.abort();
+                    }
+                    subscription = Some(forward_events(events.subscribe(), filter, outgoing.clone()));
+                    Value::Bool(true)
+                }),
+                "unsubscribe" => {
+                    if let Some(previous) = subscription.take() {
+                        previous.abort();
+                    }
+                    Ok(Value::Bool(true))
+                }
+                method => call_command(&app, method, request.params).await,
+            },
+        };
+
+        // Requests without an id are notifications and get no response
+        if request.id.is_some() {
+            send_response(&outgoing, request.id, result);
+        }
+    }
+
+    if let Some(subscription) = subscription {
+        subscription.abort();
+    }
+    drop(outgoing);
+    let _ = writer_task.await;
+}
+
+/// Answer `authenticate` and reject everything else until it has succeeded on this connection
+///
+/// Returns `None` when the request is allowed through to its method.
+fn authorize(
+    method: &str,
+    params: &Value,
+    token: &str,
+    authenticated: &mut bool,
+) -> Option<Result<Value, RpcError>> {
+    if method == "authenticate" {
+        return Some(authenticate(params, token).map(|()| {
+            *authenticated = true;
+            Value::Bool(true)
+        }));
+    }
+    if !*authenticated {
+        return Some(Err(RpcError::new(UNAUTHORIZED, "Call authenticate with the token first")));
+    }
+    None
+}
+
+fn authenticate(params: &Value, token: &str) -> Result<(), RpcError> {
+    let given = params.get("token").and_then(Value::as_str).unwrap_or_default();
+
+    // Compare without short-circuiting so timing doesn't reveal how much of the token matched
+    let matches = given.len() == token.len()
+        && given.bytes().zip(token.bytes()).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0;
+
+    if matches {
+        Ok(())
+    } else {
+        Err(RpcError::new(UNAUTHORIZED, "Invalid token"))
+    }
+}
+
+/// Dispatch a method to the Tauri command of the same name
+async fn call_command(app: &AppHandle, method: &str, params: Value) -> Result<Value, RpcError> {
+    match method {
+        "load_config" => to_value(load_config(app.clone()).await?),
+        "add_app_config" => {
+            let AppConfigParams { app_config } = parse_params(params)?;
+            let config = add_app_config(app.clone(), app_config).await?;
+            notify_config_changed(app, "control-api");
+            to_value(config)
+        }
+        "update_app_config" => {
+            let AppConfigParams { app_config } = parse_params(params)?;
+            let config = update_app_config(app.clone(), app_config).await?;
+            notify_config_changed(app, "control-api");
+            to_value(config)
+        }
+        "remove_app_config" => {
+            let AppIdParams { app_id } = parse_params(params)?;
+            let config = remove_app_config(app.clone(), app_id).await?;
+            notify_config_changed(app, "control-api");
+            to_value(config)
+        }
+        "start_app_process" => {
+            let StartParams { app_id, profile } = parse_params(params)?;
+            to_value(start_saved_app(app, &app_id, profile).await?)
+        }
+        "stop_app_process" => {
+            let AppIdParams { app_id } = parse_params(params)?;
+            to_value(stop_app_process(app_id, app.clone(), app.state::<ProcessManager>()).await?)
+        }
+        "get_process_status" => {
+            let AppIdParams { app_id } = parse_params(params)?;
+            to_value(get_process_status(app_id, app.state::<ProcessManager>()).await?)
+        }
+        "get_all_process_status" => to_value(get_all_process_status(app.state::<ProcessManager>()).await?),
+        "handle_instance_args" => {
+            let InstanceArgsParams { args } = parse_params(params)?;
+            Ok(handle_instance_args(app, &args).await)
+        }
+        _ => Err(RpcError::new(METHOD_NOT_FOUND, &format!("Unknown method '{}'", method))),
+    }
+}
+
+/// Tell the UI (and subscribers) that the configuration was changed from outside
+pub(crate) fn notify_config_changed(app: &AppHandle, source: &str) {
+    let _ = app.emit("config-changed", serde_json::json!({
+        "source": source,
+        "timestamp": chrono::Utc::now().to_rfc3339()
+    }));
+}
+
+/// Forward matching events to a connection as `event` notifications
+fn forward_events(
+    mut receiver: broadcast::Receiver<ControlEvent>,
+    filter: SubscribeParams,
+    outgoing: mpsc::UnboundedSender<String>,
+) -> tauri::async_runtime::JoinHandle<()> {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let event = match receiver.recv().await {
+                Ok(event) => event,
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    log::warn!("Control API subscriber missed {} events", missed);
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+
+            if let Some(ref names) = filter.events {
+                if !names.contains(&event.event) {
+                    continue;
+                }
+            }
+            if let Some(ref app_id) = filter.app_id {
+                if event.payload.get("appId").and_then(Value::as_str) != Some(app_id.as_str()) {
+                    continue;
+                }
+            }
+
+            let notification = serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "event",
+                "params": event
+            });
+            if outgoing.send(notification.to_string()).is_err() {
+                break;
+            }
+        }
+    })
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    // Parameterless calls may omit params entirely
+    let params = if params.is_null() { Value::Object(Default::default()) } else { params };
+    serde_json::from_value(params).map_err(|e| RpcError::new(INVALID_PARAMS, &e.to_string()))
+}
+
+fn to_value<T: Serialize>(value: T) -> Result<Value, RpcError> {
+    serde_json::to_value(value).map_err(|e| RpcError::new(COMMAND_ERROR, &e.to_string()))
+}
+
+fn send_response(outgoing: &mpsc::UnboundedSender<String>, id: Option<Value>, result: Result<Value, RpcError>) {
+    let response = match result {
+        Ok(result) => serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => serde_json::json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+    };
+    let _ = outgoing.send(response.to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn pipe_name_depends_on_config_dir() {
+        let default = pipe_name(Path::new(r"C:\Users\me\.oddlauncher"));
+        let portable = pipe_name(Path::new(r"D:\Tools\OddLauncher\data"));
+        assert!(default.starts_with(PIPE_PREFIX));
+        assert_eq!(default.len(), PIPE_PREFIX.len() + 16);
+        assert_ne!(default, portable);
+    }
+
+    #[test]
+    fn pipe_name_ignores_case() {
+        assert_eq!(
+            pipe_name(Path::new(r"C:\Users\Me\.oddlauncher")),
+            pipe_name(Path::new(r"c:\users\me\.ODDLAUNCHER"))
+        );
+    }
+
+    #[test]
+    fn pipe_name_is_stable() {
+        // Clients outside this codebase may compute the name, so the hash must never change
+        assert_eq!(pipe_name(Path::new("")), format!("{}cbf29ce484222325", PIPE_PREFIX));
+        assert_eq!(pipe_name(Path::new("a")), format!("{}af63dc4c8601ec8c", PIPE_PREFIX));
+    }
+
+    const TOKEN: &str = "0123456789abcdef0123456789abcdef";
+
+    fn token_params(token: &str) -> Value {
+        serde_json::json!({ "token": token })
+    }
+
+    #[test]
+    fn authenticate_with_token() {
+        assert!(authenticate(&token_params(TOKEN), TOKEN).is_ok());
+    }
+
+    #[test]
+    fn authenticate_rejects_wrong_token() {
+        let wrong = TOKEN.replace('0', "1");
+        assert_eq!(authenticate(&token_params(&wrong), TOKEN).unwrap_err().code, UNAUTHORIZED);
+    }
+
+    #[test]
+    fn authenticate_rejects_wrong_length() {
+        for given in ["", &TOKEN[..8], &format!("{}00", TOKEN)] {
+            assert_eq!(authenticate(&token_params(given), TOKEN).unwrap_err().code, UNAUTHORIZED);
+        }
+        assert_eq!(authenticate(&Value::Null, TOKEN).unwrap_err().code, UNAUTHORIZED);
+    }
+
+    #[test]
+    fn methods_need_authentication() {
+        let mut authenticated = false;
+        for method in ["subscribe", "unsubscribe", "get_all_process_status", "start_app", "handle_instance_args"] {
+            let result = authorize(method, &Value::Null, TOKEN, &mut authenticated);
+            assert_eq!(result.unwrap().unwrap_err().code, UNAUTHORIZED);
+        }
+
+        let result = authorize("authenticate", &token_params("wrong"), TOKEN, &mut authenticated);
+        assert_eq!(result.unwrap().unwrap_err().code, UNAUTHORIZED);
+        assert!(authorize("start_app", &Value::Null, TOKEN, &mut authenticated).is_some());
+
+        let result = authorize("authenticate", &token_params(TOKEN), TOKEN, &mut authenticated);
+        assert_eq!(result.unwrap().unwrap(), Value::Bool(true));
+        assert!(authorize("start_app", &Value::Null, TOKEN, &mut authenticated).is_none());
+    }
+}
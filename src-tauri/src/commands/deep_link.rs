@@ -0,0 +1,422 @@
+use crate::commands::browser::open_url_in_browser;
+use crate::commands::config::{find_app, import_content, read_global_config, ImportOptions, ImportReport};
+use crate::commands::control::notify_config_changed;
+use crate::commands::environment::{resolve_launch_fields, LaunchFields};
+use crate::commands::process::{start_saved_app, stop_app_process, ProcessManager};
+use crate::models::app::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+// oddlauncher:// links: start, stop and open saved apps, or import apps from a URL
+
+/// URL scheme registered for deep links
+pub const DEEP_LINK_SCHEME: &str = "oddlauncher";
+
+/// Largest import document fetched from a link
+const MAX_IMPORT_SIZE: usize = 1024 * 1024;
+
+/// Timeout for fetching an import document
+const IMPORT_FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Most imports held for confirmation at once, so a page can't queue up links endlessly
+const MAX_PENDING_IMPORTS: usize = 10;
+
+/**
+ * Action requested by a deep link
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeepLinkAction {
+    /// `oddlauncher://start/<app>?profile=<name>`
+    Start { app: String, profile: Option<String> },
+    /// `oddlauncher://stop/<app>`
+    Stop { app: String },
+    /// `oddlauncher://open/<app>` opens the app's URL
+    Open { app: String },
+    /// `oddlauncher://import?url=<export file URL>`
+    Import { url: String },
+}
+
+/**
+ * An import from an untrusted link waiting for the user to confirm it
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingImport {
+    pub request_id: String,
+    /// Where the import document was downloaded from
+    pub url: String,
+    /// What importing would do, from a dry run
+    pub preview: ImportReport,
+    #[serde(skip)]
+    content: String,
+}
+
+/**
+ * Imports from untrusted links, held until confirmed or cancelled
+ */
+#[derive(Default)]
+pub struct PendingImports {
+    imports: Mutex<HashMap<String, PendingImport>>,
+}
+
+/// Whether a command-line argument is a deep link
+pub fn is_deep_link(arg: &str) -> bool {
+    let prefix = format!("{}://", DEEP_LINK_SCHEME);
+    arg.get(..prefix.len()).is_some_and(|start| start.eq_ignore_ascii_case(&prefix))
+}
+
+/// Parse an `oddlauncher://` URL into the action it requests
+pub fn parse_deep_link(link: &str) -> AppResult<DeepLinkAction> {
+    let invalid = |message: &str| {
+        AppError::new("DEEP_LINK_ERROR", &format!("Invalid link '{}': {}", link, message))
+    };
+
+    let url = reqwest::Url::parse(link.trim()).map_err(|e| invalid(&e.to_string()))?;
+    if !url.scheme().eq_ignore_ascii_case(DEEP_LINK_SCHEME) {
+        return Err(invalid(&format!("expected an {}:// link", DEEP_LINK_SCHEME)));
+    }
+
+    let query: HashMap<String, String> = url.query_pairs().into_owned().collect();
+    let action = url.host_str().unwrap_or_default().to_ascii_lowercase();
+
+    // Everything after the action is the app ID or name, which may contain encoded spaces or slashes
+    let app = urlencoding::decode(url.path().trim_matches('/'))
+        .map_err(|e| invalid(&e.to_string()))?
+        .trim()
+        .to_string();
+    let require_app = |app: String| {
+        if app.is_empty() {
+            Err(invalid("missing app ID or name"))
+        } else {
+            Ok(app)
+        }
+    };
+
+    match action.as_str() {
+        "start" => Ok(DeepLinkAction::Start {
+            app: require_app(app)?,
+            profile: query.get("profile").filter(|p| !p.is_empty()).cloned(),
+        }),
+        "stop" => Ok(DeepLinkAction::Stop { app: require_app(app)? }),
+        "open" => Ok(DeepLinkAction::Open { app: require_app(app)? }),
+        "import" => {
+            let source = query.get("url").ok_or_else(|| invalid("missing url parameter"))?;
+            let source_url = reqwest::Url::parse(source).map_err(|e| invalid(&e.to_string()))?;
+            if source_url.scheme() != "https" && source_url.scheme() != "http" {
+                return Err(invalid("imports must come from an http(s) URL"));
+            }
+            Ok(DeepLinkAction::Import { url: source_url.to_string() })
+        }
+        other => Err(invalid(&format!("unknown action '{}'", other))),
+    }
+}
+
+/// Carry out a deep link, reporting failures to the UI as a `deep-link-failed` event
+pub(crate) async fn handle_deep_link(app: &AppHandle, link: &str) -> Value {
+    log::info!("Handling deep link: {}", link);
+
+    match run_deep_link(app, link).await {
+        Ok(result) => result,
+        Err(e) => {
+            log::error!("Deep link failed: {}", e.message);
+            let _ = app.emit("deep-link-failed", serde_json::json!({
+                "url": link,
+                "error": e.message,
+                "timestamp": chrono::Utc::now().to_rfc3339()
+            }));
+            serde_json::json!({ "url": link, "success": false, "error": e.message })
+        }
+    }
+}
+
+async fn run_deep_link(app: &AppHandle, link: &str) -> AppResult<Value> {
+    let action = parse_deep_link(link)?;
+    let config = read_global_config()?;
+    let process_error = |e: String| AppError::new("PROCESS_ERROR", &e);
+
+    match action {
+        DeepLinkAction::Start { app: reference, profile } => {
+            let app_id = find_app(&config, &reference)?.id.clone();
+            let result = start_saved_app(app, &app_id, profile).await.map_err(process_error)?;
+            Ok(serde_json::json!({ "url": link, "appId": app_id, "success": result.success, "message": result.message }))
+        }
+        DeepLinkAction::Stop { app: reference } => {
+            let app_id = find_app(&config, &reference)?.id.clone();
+            let result = stop_app_process(app_id.clone(), app.clone(), app.state::<ProcessManager>())
+                .await
+                .map_err(process_error)?;
+            Ok(serde_json::json!({ "url": link, "appId": app_id, "success": result.success, "message": result.message }))
+        }
+        DeepLinkAction::Open { app: reference } => {
+            let stored = find_app(&config, &reference)?;
+            let profile = stored.resolve_profile(None)?.cloned();
+            let resolved = resolve_launch_fields(
+                &stored.id,
+                &LaunchFields {
+                    url: stored.url.clone(),
+                    working_directory: stored.working_directory.clone(),
+                    env_files: stored.env_files.clone().unwrap_or_default(),
+                    environment_variables: stored.environment_variables.clone(),
+                    profile,
+                    ..Default::default()
+                },
+                &config.settings.variables,
+            )?;
+            let url = resolved.fields.url.ok_or_else(|| {
+                AppError::new("DEEP_LINK_ERROR", &format!("App '{}' has no URL to open", stored.name))
+            })?;
+            open_url_in_browser(url.clone())
+                .await
+                .map_err(|e| AppError::new("BROWSER_LAUNCH_ERROR", &e))?;
+            Ok(serde_json::json!({ "url": link, "appId": stored.id, "success": true, "message": format!("Opened {}", url) }))
+        }
+        DeepLinkAction::Import { url } => {
+            let trusted = is_trusted_source(&url, &config.settings.trusted_import_sources);
+            if !trusted {
+                check_pending_capacity(&app.state::<PendingImports>().imports.lock().unwrap())?;
+            }
+            let content = fetch_import_document(&url).await?;
+
+            if trusted {
+                log::info!("Importing from trusted source: {}", url);
+                let report = import_content(&content, &ImportOptions::default())?;
+                notify_config_changed(app, "deep-link");
+                return Ok(serde_json::json!({ "url": link, "success": true, "report": report }));
+            }
+
+            // Untrusted sources need the user's go-ahead; show them what would change first
+            let preview = import_content(
+                &content,
+                &ImportOptions {
+                    dry_run: true,
+                    ..Default::default()
+                },
+            )?;
+            let pending = PendingImport {
+                request_id: uuid::Uuid::new_v4().to_string(),
+                url,
+                preview,
+                content,
+            };
+
+            // Checked again, since other links may have been queued during the download
+            let request_id = pending.request_id.clone();
+            {
+                let pending_imports = app.state::<PendingImports>();
+                let mut imports = pending_imports.imports.lock().unwrap();
+                check_pending_capacity(&imports)?;
+                imports.insert(request_id.clone(), pending.clone());
+            }
+
+            log::info!("Import from {} is waiting for confirmation", pending.url);
+            let _ = app.emit("deep-link-import-request", &pending);
+
+            Ok(serde_json::json!({ "url": link, "success": true, "pendingImport": request_id }))
+        }
+    }
+}
+
+/// Refuse another import while `MAX_PENDING_IMPORTS` are already waiting for confirmation
+fn check_pending_capacity(imports: &HashMap<String, PendingImport>) -> AppResult<()> {
+    if imports.len() >= MAX_PENDING_IMPORTS {
+        return Err(AppError::new(
+            "TOO_MANY_PENDING_IMPORTS",
+            &format!(
+                "{} imports are already waiting for confirmation; confirm or cancel them first",
+                imports.len()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Download an import document, refusing anything unreasonably large
+async fn fetch_import_document(url: &str) -> AppResult<String> {
+    let fetch_error = |message: String| {
+        AppError::new("IMPORT_FETCH_ERROR", &format!("Failed to download {}: {}", url, message))
+    };
+
+    // Trust is decided on the link's URL, so a trusted host must not hand the download elsewhere
+    let client = reqwest::Client::builder()
+        .timeout(IMPORT_FETCH_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .map_err(|e| fetch_error(e.to_string()))?;
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| fetch_error(e.to_string()))?;
+    if response.status().is_redirection() {
+        let status = response.status();
+        return Err(fetch_error(format!("the server redirected ({}), which imports don't follow", status)));
+    }
+
+    if response.content_length().is_some_and(|len| len as usize > MAX_IMPORT_SIZE) {
+        return Err(fetch_error("file is too large".to_string()));
+    }
+    let bytes = response.bytes().await.map_err(|e| fetch_error(e.to_string()))?;
+    if bytes.len() > MAX_IMPORT_SIZE {
+        return Err(fetch_error("file is too large".to_string()));
+    }
+
+    String::from_utf8(bytes.to_vec()).map_err(|_| fetch_error("file is not valid UTF-8".to_string()))
+}
+
+/// Whether a URL falls under one of the trusted source prefixes (same origin and path prefix)
+///
+/// Only https URLs are trusted, since anything on the network path could rewrite a plain http download.
+fn is_trusted_source(url: &str, trusted_sources: &[String]) -> bool {
+    let Ok(url) = reqwest::Url::parse(url) else {
+        return false;
+    };
+    if url.scheme() != "https" {
+        return false;
+    }
+
+    trusted_sources.iter().any(|source| {
+        let Ok(source) = reqwest::Url::parse(source.trim()) else {
+            return false;
+        };
+        let source_path = source.path().trim_end_matches('/');
+
+        source.scheme() == url.scheme()
+            && source.host_str() == url.host_str()
+            && source.port_or_known_default() == url.port_or_known_default()
+            && (url.path() == source_path || url.path().starts_with(&format!("{}/", source_path)))
+    })
+}
+
+/// List imports from untrusted links that are waiting for confirmation
+#[tauri::command]
+pub async fn list_pending_imports(pending: State<'_, PendingImports>) -> AppResult<Vec<PendingImport>> {
+    Ok(pending.imports.lock().unwrap().values().cloned().collect())
+}
+
+/// Import the apps from a pending deep link import
+#[tauri::command]
+pub async fn confirm_deep_link_import(
+    app: AppHandle,
+    pending: State<'_, PendingImports>,
+    request_id: String,
+    options: Option<ImportOptions>,
+) -> AppResult<ImportReport> {
+    let import = pending.imports.lock().unwrap().remove(&request_id).ok_or_else(|| {
+        AppError::new(
+            "IMPORT_REQUEST_NOT_FOUND",
+            &format!("No pending import with ID '{}'", request_id),
+        )
+    })?;
+
+    log::info!("Import from {} confirmed", import.url);
+    let report = import_content(&import.content, &options.unwrap_or_default())?;
+    notify_config_changed(&app, "deep-link");
+
+    Ok(report)
+}
+
+/// Discard a pending deep link import
+#[tauri::command]
+pub async fn cancel_deep_link_import(
+    pending: State<'_, PendingImports>,
+    request_id: String,
+) -> AppResult<()> {
+    if let Some(import) = pending.imports.lock().unwrap().remove(&request_id) {
+        log::info!("Import from {} cancelled", import.url);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(link: &str) -> DeepLinkAction {
+        parse_deep_link(link).unwrap()
+    }
+
+    fn error(link: &str) -> String {
+        parse_deep_link(link).unwrap_err().message
+    }
+
+    #[test]
+    fn app_actions() {
+        assert_eq!(
+            action("oddlauncher://start/api?profile=staging"),
+            DeepLinkAction::Start { app: "api".to_string(), profile: Some("staging".to_string()) }
+        );
+        assert_eq!(
+            action("ODDLAUNCHER://Start/My%20App%2Fv2/?profile="),
+            DeepLinkAction::Start { app: "My App/v2".to_string(), profile: None }
+        );
+        assert_eq!(action("oddlauncher://stop/api"), DeepLinkAction::Stop { app: "api".to_string() });
+        assert_eq!(action(" oddlauncher://open/api "), DeepLinkAction::Open { app: "api".to_string() });
+    }
+
+    #[test]
+    fn import_action() {
+        assert_eq!(
+            action("oddlauncher://import?url=https%3A%2F%2Fexample.com%2Fapps.json"),
+            DeepLinkAction::Import { url: "https://example.com/apps.json".to_string() }
+        );
+        assert!(error("oddlauncher://import?url=file%3A%2F%2F%2Fetc%2Fpasswd").ends_with("imports must come from an http(s) URL"));
+        assert!(error("oddlauncher://import").ends_with("missing url parameter"));
+    }
+
+    #[test]
+    fn invalid_links() {
+        assert!(error("oddlauncher://start").ends_with("missing app ID or name"));
+        assert!(error("oddlauncher://start/%20").ends_with("missing app ID or name"));
+        assert!(error("oddlauncher://delete/api").ends_with("unknown action 'delete'"));
+        assert!(error("https://start/api").ends_with("expected an oddlauncher:// link"));
+        assert!(error("not a link").starts_with("Invalid link 'not a link'"));
+    }
+
+    #[test]
+    fn deep_link_args() {
+        assert!(is_deep_link("OddLauncher://start/api"));
+        assert!(!is_deep_link("oddlauncher:/start"));
+        assert!(!is_deep_link("--minimized"));
+    }
+
+    #[test]
+    fn trusted_sources() {
+        let trusted = ["https://wiki.example.com/oddlauncher/".to_string(), "not a url".to_string()];
+        assert!(is_trusted_source("https://wiki.example.com/oddlauncher/apps.json", &trusted));
+        assert!(is_trusted_source("https://wiki.example.com:443/oddlauncher", &trusted));
+        assert!(!is_trusted_source("https://wiki.example.com/oddlauncher-evil/apps.json", &trusted));
+        assert!(!is_trusted_source("https://wiki.example.com.evil.net/oddlauncher/apps.json", &trusted));
+        assert!(!is_trusted_source("https://wiki.example.com:8443/oddlauncher/apps.json", &trusted));
+        assert!(!is_trusted_source("https://other.example.com/oddlauncher/apps.json", &trusted));
+    }
+
+    #[test]
+    fn trusted_sources_need_https() {
+        let trusted = ["http://intranet/apps/".to_string()];
+        assert!(!is_trusted_source("http://intranet/apps/list.json", &trusted));
+    }
+
+    #[test]
+    fn pending_imports_are_capped() {
+        let mut imports = HashMap::new();
+        for i in 0..MAX_PENDING_IMPORTS {
+            assert!(check_pending_capacity(&imports).is_ok());
+            let pending = PendingImport {
+                request_id: i.to_string(),
+                url: "https://example.com/apps.json".to_string(),
+                preview: serde_json::from_value(serde_json::json!({
+                    "total": 0, "added": 0, "skipped": 0, "overwritten": 0, "duplicated": 0, "invalid": 0, "dryRun": true, "items": []
+                }))
+                .unwrap(),
+                content: String::new(),
+            };
+            imports.insert(i.to_string(), pending);
+        }
+        assert_eq!(check_pending_capacity(&imports).unwrap_err().code, "TOO_MANY_PENDING_IMPORTS");
+    }
+}
@@ -0,0 +1,292 @@
+use crate::commands::shell_env::ShellEnvironment;
+use crate::commands::shell_quote::ShellDialect;
+use crate::models::app::{AppResult, DevEnvironment};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+use tauri::State;
+
+// Project development environments (direnv, Nix, devbox): launch commands are wrapped in the
+// tool so they run with the toolchain the project declares.
+
+/// Files each tool is configured by, in order of preference
+const DEV_ENVIRONMENT_FILES: [(&str, DevEnvironment); 4] = [
+    (".envrc", DevEnvironment::Direnv),
+    ("flake.nix", DevEnvironment::Nix),
+    ("shell.nix", DevEnvironment::Nix),
+    ("devbox.json", DevEnvironment::Devbox),
+];
+
+/**
+ * A development environment tool configured in a working directory
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectedDevEnvironment {
+    pub kind: DevEnvironment,
+    /// File that configures the tool, relative to the working directory
+    pub file: String,
+    /// Whether the tool's program was found on PATH
+    pub installed: bool,
+}
+
+impl DevEnvironment {
+    fn name(self) -> &'static str {
+        match self {
+            DevEnvironment::Direnv => "direnv",
+            DevEnvironment::Nix => "Nix",
+            DevEnvironment::Devbox => "devbox",
+        }
+    }
+
+    /// Files that configure the tool
+    pub(crate) fn files(self) -> Vec<&'static str> {
+        DEV_ENVIRONMENT_FILES
+            .iter()
+            .filter(|(_, kind)| *kind == self)
+            .map(|(file, _)| *file)
+            .collect()
+    }
+
+    /// Program that runs commands in the environment configured by `file`
+    fn program(self, file: &str) -> &'static str {
+        match self {
+            DevEnvironment::Direnv => "direnv",
+            DevEnvironment::Nix if file == "shell.nix" => "nix-shell",
+            DevEnvironment::Nix => "nix",
+            DevEnvironment::Devbox => "devbox",
+        }
+    }
+}
+
+/// Find the development environment tools configured in a directory
+pub fn detect_dev_environments(working_dir: &str, path_var: Option<&str>) -> Vec<DetectedDevEnvironment> {
+    let dir = Path::new(working_dir);
+    let mut detected: Vec<DetectedDevEnvironment> = Vec::new();
+
+    for (file, kind) in DEV_ENVIRONMENT_FILES {
+        // flake.nix takes precedence over shell.nix
+        if !dir.join(file).is_file() || detected.iter().any(|d| d.kind == kind) {
+            continue;
+        }
+        detected.push(DetectedDevEnvironment {
+            kind,
+            file: file.to_string(),
+            installed: find_program(kind.program(file), path_var).is_some(),
+        });
+    }
+    detected
+}
+
+/// Wrap a prepared launch so it runs inside the app's development environment
+///
+/// Fails with a message saying what to fix if the tool, its config file or its approval is missing.
+pub(crate) async fn wrap_in_dev_environment(
+    kind: DevEnvironment,
+    program: String,
+    args: Vec<String>,
+    working_dir: Option<&str>,
+    path_var: Option<&str>,
+) -> Result<(String, Vec<String>), String> {
+    let name = kind.name();
+    let dir = working_dir.ok_or_else(|| format!("{} needs the app to have a working directory", name))?;
+    if program == "wsl.exe" {
+        return Err(format!("{} can't wrap WSL commands; call it from the launch commands instead", name));
+    }
+
+    let detected = detect_dev_environments(dir, path_var)
+        .into_iter()
+        .find(|d| d.kind == kind)
+        .ok_or_else(|| format!("No {} found in {}", kind.files().join(" or "), dir))?;
+
+    let tool_program = kind.program(&detected.file);
+    let tool = find_program(tool_program, path_var)
+        .ok_or_else(|| format!("{} is not installed or not on PATH (needed for {} in {})", tool_program, detected.file, dir))?;
+    let tool = tool.to_string_lossy().to_string();
+
+    log::info!("Wrapping launch in {} using {}", name, tool);
+    let command: Vec<String> = std::iter::once(program).chain(args).collect();
+    let prefixed = |prefix: &[&str]| -> Vec<String> {
+        prefix.iter().map(|arg| arg.to_string()).chain(command.iter().cloned()).collect()
+    };
+    let wrapped_args = match (kind, detected.file.as_str()) {
+        (DevEnvironment::Direnv, _) => {
+            if direnv_allowed(&tool, dir).await == Some(false) {
+                return Err(format!(
+                    "{} is blocked. Review it and run `direnv allow` in {} to approve it",
+                    Path::new(dir).join(".envrc").display(),
+                    dir
+                ));
+            }
+            prefixed(&["exec", dir])
+        }
+        // nix-shell only takes a command as a single string for bash
+        (DevEnvironment::Nix, "shell.nix") => {
+            let shell_nix = Path::new(dir).join("shell.nix").to_string_lossy().to_string();
+            let quoted: Vec<String> = command.iter().map(|arg| ShellDialect::Posix.quote(arg)).collect();
+            vec![shell_nix, "--run".to_string(), quoted.join(" ")]
+        }
+        (DevEnvironment::Nix, _) => prefixed(&[
+            "--extra-experimental-features",
+            "nix-command flakes",
+            "develop",
+            dir,
+            "--command",
+        ]),
+        (DevEnvironment::Devbox, _) => prefixed(&["run", "--config", dir, "--"]),
+    };
+
+    Ok((tool, wrapped_args))
+}
+
+fn find_program(program: &str, path_var: Option<&str>) -> Option<PathBuf> {
+    let path_var = path_var.map(str::to_string).or_else(|| std::env::var("PATH").ok());
+    let cwd = std::env::current_dir().unwrap_or_default();
+    which::which_in(program, path_var, cwd).ok()
+}
+
+/// Whether direnv has approved the directory's .envrc, or None if direnv can't say
+async fn direnv_allowed(direnv: &str, dir: &str) -> Option<bool> {
+    // direnv 2.33 and later: 0 is allowed, 1 not allowed, 2 denied
+    let output = Command::new(direnv).args(["status", "--json"]).current_dir(dir).output().await.ok()?;
+    if output.status.success() {
+        if let Ok(status) = serde_json::from_slice::<serde_json::Value>(&output.stdout) {
+            return status.pointer("/state/foundRC/allowed").and_then(|a| a.as_i64()).map(|a| a == 0);
+        }
+    }
+
+    // Older versions only print text, e.g. "Found RC allowed true"
+    let output = Command::new(direnv).arg("status").current_dir(dir).output().await.ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.strip_prefix("Found RC allowed "))
+        .map(|allowed| matches!(allowed.trim(), "true" | "0"))
+}
+
+/// Detect development environment tools configured in a working directory
+#[tauri::command]
+pub async fn detect_dev_environment(
+    working_directory: String,
+    shell_environment: State<'_, ShellEnvironment>,
+) -> AppResult<Vec<DetectedDevEnvironment>> {
+    let path_var = shell_environment.variables().await.remove("PATH");
+    Ok(detect_dev_environments(&working_directory, path_var.as_deref()))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    /// A project directory holding `files`, with stand-ins for every tool in `bin/`
+    fn project(name: &str, files: &[&str], direnv_status: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("oddlauncher-devenv-{}-{}", name, std::process::id()));
+        let bin = dir.join("bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        for file in files {
+            std::fs::write(dir.join(file), "").unwrap();
+        }
+        for tool in ["direnv", "nix", "nix-shell", "devbox"] {
+            let script = if tool == "direnv" {
+                format!("#!/bin/sh\necho '{}'\n", direnv_status)
+            } else {
+                "#!/bin/sh\n".to_string()
+            };
+            std::fs::write(bin.join(tool), script).unwrap();
+            std::fs::set_permissions(bin.join(tool), std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        dir
+    }
+
+    fn wrap(dir: &Path, kind: DevEnvironment) -> Result<(String, Vec<String>), String> {
+        let path_var = dir.join("bin").to_string_lossy().to_string();
+        let args = vec!["-c".to_string(), "npm start".to_string()];
+        tauri::async_runtime::block_on(wrap_in_dev_environment(
+            kind,
+            "bash".to_string(),
+            args,
+            dir.to_str(),
+            Some(&path_var),
+        ))
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn direnv_exec() {
+        let dir = project("direnv", &[".envrc"], r#"{"state":{"foundRC":{"allowed":0}}}"#);
+        let wrapped = wrap(&dir, DevEnvironment::Direnv);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let (program, args) = wrapped.unwrap();
+        let dir = dir.to_string_lossy().to_string();
+        assert_eq!(program, format!("{}/bin/direnv", dir));
+        assert_eq!(args, strings(&["exec", &dir, "bash", "-c", "npm start"]));
+    }
+
+    #[test]
+    fn blocked_envrc() {
+        let dir = project("blocked", &[".envrc"], "Found RC allowed false");
+        let wrapped = wrap(&dir, DevEnvironment::Direnv);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(wrapped.unwrap_err().contains("direnv allow"));
+    }
+
+    #[test]
+    fn nix_develop() {
+        let dir = project("flake", &["flake.nix", "shell.nix"], "");
+        let wrapped = wrap(&dir, DevEnvironment::Nix);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let (program, args) = wrapped.unwrap();
+        let dir = dir.to_string_lossy().to_string();
+        assert_eq!(program, format!("{}/bin/nix", dir));
+        assert_eq!(
+            args,
+            strings(&[
+                "--extra-experimental-features",
+                "nix-command flakes",
+                "develop",
+                &dir,
+                "--command",
+                "bash",
+                "-c",
+                "npm start"
+            ])
+        );
+    }
+
+    #[test]
+    fn nix_shell_run() {
+        let dir = project("shellnix", &["shell.nix"], "");
+        let wrapped = wrap(&dir, DevEnvironment::Nix);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let (program, args) = wrapped.unwrap();
+        let dir = dir.to_string_lossy().to_string();
+        assert_eq!(program, format!("{}/bin/nix-shell", dir));
+        assert_eq!(args, strings(&[&format!("{}/shell.nix", dir), "--run", "'bash' '-c' 'npm start'"]));
+    }
+
+    #[test]
+    fn devbox_run() {
+        let dir = project("devbox", &["devbox.json"], "");
+        let wrapped = wrap(&dir, DevEnvironment::Devbox);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let (program, args) = wrapped.unwrap();
+        let dir = dir.to_string_lossy().to_string();
+        assert_eq!(program, format!("{}/bin/devbox", dir));
+        assert_eq!(args, strings(&["run", "--config", &dir, "--", "bash", "-c", "npm start"]));
+    }
+
+    #[test]
+    fn missing_config_file() {
+        let dir = project("missing", &[], "");
+        let wrapped = wrap(&dir, DevEnvironment::Devbox);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(wrapped.unwrap_err().starts_with("No devbox.json found in"));
+    }
+}
@@ -0,0 +1,865 @@
+use crate::models::app::{AppConfig, AppError, AppProfile, AppResult};
+use crate::commands::config::read_global_config;
+use crate::commands::secrets::REDACTED;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+
+// Environment resolution for app launches: dotenv files and variable interpolation
+
+/**
+ * App launch fields that support `${VAR}` interpolation
+ */
+#[derive(Debug, Clone, Default)]
+pub struct LaunchFields {
+    pub launch_commands: Option<String>,
+    pub working_directory: Option<String>,
+    pub url: Option<String>,
+    pub environment_variables: Option<HashMap<String, String>>,
+    pub env_files: Vec<String>,
+    pub port_to_check: Option<u16>,
+    /// Profile whose overrides are applied on top of the other fields
+    pub profile: Option<AppProfile>,
+}
+
+/**
+ * Launch fields after env files have been merged and every reference resolved
+ */
+#[derive(Debug, Clone)]
+pub struct ResolvedLaunch {
+    pub fields: LaunchFields,
+    /// Where each variable in `fields.environment_variables` came from
+    pub sources: HashMap<String, String>,
+    /// Load status of each configured env file
+    pub env_files: Vec<EnvFileStatus>,
+}
+
+/**
+ * Load status of a single env file
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvFileStatus {
+    /// Path as configured on the app
+    pub path: String,
+    /// Path after resolving against the working directory
+    pub resolved_path: String,
+    /// Whether the file existed and was loaded
+    pub loaded: bool,
+    /// Number of variables the file defined
+    pub variable_count: usize,
+}
+
+/**
+ * A single variable in the effective environment
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvironmentEntry {
+    pub name: String,
+    pub value: String,
+    /// `inherited`, `inline`, `secret:<name>`, or the env file path the value was read from
+    pub source: String,
+}
+
+/**
+ * Effective environment an app would be launched with
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvironmentPreview {
+    pub variables: Vec<EnvironmentEntry>,
+    pub env_files: Vec<EnvFileStatus>,
+}
+
+/**
+ * Merge env files into the app environment and resolve `${VAR}` references in
+ * every interpolated launch field.
+ *
+ * Env files are read in order, each one overriding the previous; relative paths
+ * are resolved against the working directory, so they are an error for apps
+ * without one. Inline `environment_variables` override every env file. The
+ * active profile's variables take precedence over everything. The profile's URL,
+ * port and launch commands replace the app's when set. Missing env files are
+ * skipped so that optional files like `.env.local` can be listed unconditionally.
+ *
+ * Lookup order for a name is: the app's own environment variables, the global
+ * user-defined variables, the built-ins (`APP_ID`, `APP_DIR`, `HOME`, `PORT`) and
+ * finally the environment OddLauncher itself was started with. `${VAR:-fallback}`
+ * uses the fallback when the variable is unset or empty, and `$${VAR}` produces a
+ * literal `${VAR}` for the shell. Unknown names are an error, except in launch
+ * commands, where they are left for the shell to expand (e.g. a variable the
+ * commands set themselves).
+ */
+pub fn resolve_launch_fields(
+    app_id: &str,
+    fields: &LaunchFields,
+    global_variables: &HashMap<String, String>,
+) -> AppResult<ResolvedLaunch> {
+    let empty_env = HashMap::new();
+    let profile = fields.profile.as_ref();
+    let port_to_check = profile.and_then(|p| p.port_to_check).or(fields.port_to_check);
+    let raw_url = profile.and_then(|p| p.url.as_ref()).or(fields.url.as_ref());
+    let raw_commands = profile
+        .and_then(|p| p.launch_commands.as_ref())
+        .or(fields.launch_commands.as_ref());
+
+    // App-level variable layers in increasing precedence
+    let mut app_layers = vec![(
+        "inline".to_string(),
+        fields.environment_variables.as_ref().unwrap_or(&empty_env),
+    )];
+    if let Some(profile) = profile {
+        app_layers.push((
+            format!("profile:{}", profile.name),
+            profile.environment_variables.as_ref().unwrap_or(&empty_env),
+        ));
+    }
+
+    let mut builtins = HashMap::new();
+    builtins.insert("APP_ID".to_string(), app_id.to_string());
+    if let Some(home) = dirs::home_dir() {
+        builtins.insert("HOME".to_string(), home.to_string_lossy().to_string());
+    }
+    if let Some(port) = port_to_check {
+        builtins.insert("PORT".to_string(), port.to_string());
+    }
+
+    // The working directory is resolved first so that it can locate env files and
+    // back ${APP_DIR} everywhere else
+    let mut inline_layers: HashMap<String, Vec<String>> = HashMap::new();
+    for (_, layer) in &app_layers {
+        for (key, value) in layer.iter() {
+            inline_layers.entry(key.clone()).or_default().push(value.clone());
+        }
+    }
+    let working_directory = {
+        let mut interpolator = Interpolator::new(&inline_layers, global_variables, &builtins);
+        let resolved = fields
+            .working_directory
+            .as_ref()
+            .map(|dir| interpolator.expand(dir));
+        interpolator.finish()?;
+        resolved
+    };
+    if let Some(ref dir) = working_directory {
+        builtins.insert("APP_DIR".to_string(), dir.clone());
+    }
+
+    let mut raw_env: HashMap<String, Vec<String>> = HashMap::new();
+    let mut sources = HashMap::new();
+    let mut env_files = Vec::new();
+
+    for path in &fields.env_files {
+        let resolved_path = match working_directory {
+            Some(ref dir) => Path::new(dir).join(path),
+            None if Path::new(path).is_absolute() => Path::new(path).to_path_buf(),
+            // Otherwise the file would be looked up wherever OddLauncher happened to be started
+            None => {
+                return Err(AppError::new(
+                    "ENV_FILE_RELATIVE_PATH",
+                    &format!("Env file {} is relative, but the app has no working directory to resolve it against", path),
+                ));
+            }
+        };
+
+        if !resolved_path.is_file() {
+            log::info!("Env file not found, skipping: {}", resolved_path.display());
+            env_files.push(EnvFileStatus {
+                path: path.clone(),
+                resolved_path: resolved_path.to_string_lossy().to_string(),
+                loaded: false,
+                variable_count: 0,
+            });
+            continue;
+        }
+
+        let content = fs::read_to_string(&resolved_path).map_err(|e| {
+            AppError::new(
+                "ENV_FILE_READ_ERROR",
+                &format!("Failed to read env file {}: {}", resolved_path.display(), e),
+            )
+        })?;
+        let entries = parse_dotenv(&content).map_err(|e| {
+            AppError::new(
+                "ENV_FILE_PARSE_ERROR",
+                &format!("Failed to parse env file {}: {}", resolved_path.display(), e),
+            )
+        })?;
+
+        env_files.push(EnvFileStatus {
+            path: path.clone(),
+            resolved_path: resolved_path.to_string_lossy().to_string(),
+            loaded: true,
+            variable_count: entries.len(),
+        });
+        for (key, value) in entries {
+            sources.insert(key.clone(), path.clone());
+            raw_env.entry(key).or_default().push(value);
+        }
+    }
+
+    for (source, layer) in &app_layers {
+        for (key, value) in layer.iter() {
+            sources.insert(key.clone(), source.clone());
+            raw_env.entry(key.clone()).or_default().push(value.clone());
+        }
+    }
+
+    let mut interpolator = Interpolator::new(&raw_env, global_variables, &builtins);
+
+    let environment_variables = if raw_env.is_empty() {
+        None
+    } else {
+        // Sorted so that reference cycles are reported the same way on every launch
+        let mut keys: Vec<&String> = raw_env.keys().collect();
+        keys.sort();
+        Some(
+            keys.into_iter()
+                .map(|key| (key.clone(), interpolator.resolve_env(key)))
+                .collect::<HashMap<_, _>>(),
+        )
+    };
+    let launch_commands = raw_commands.map(|commands| interpolator.expand_commands(commands));
+    let url = raw_url.map(|url| interpolator.expand(url));
+
+    interpolator.finish()?;
+
+    Ok(ResolvedLaunch {
+        fields: LaunchFields {
+            launch_commands,
+            working_directory,
+            url,
+            environment_variables,
+            env_files: fields.env_files.clone(),
+            port_to_check,
+            profile: fields.profile.clone(),
+        },
+        sources,
+        env_files,
+    })
+}
+
+/**
+ * Preview the environment an app would be launched with
+ */
+#[tauri::command]
+pub async fn preview_app_environment(
+    app_config: AppConfig,
+    include_inherited: Option<bool>,
+    profile: Option<String>,
+) -> AppResult<EnvironmentPreview> {
+    log::info!("Previewing environment for app: {}", app_config.name);
+
+    let global_variables = read_global_config()?.settings.variables;
+    let profile = app_config.resolve_profile(profile.as_deref())?.cloned();
+    let fields = LaunchFields {
+        launch_commands: app_config.launch_commands.clone(),
+        working_directory: app_config.working_directory.clone(),
+        url: app_config.url.clone(),
+        environment_variables: app_config.environment_variables.clone(),
+        env_files: app_config.env_files.clone().unwrap_or_default(),
+        port_to_check: app_config.port_to_check,
+        profile,
+    };
+    let resolved = resolve_launch_fields(&app_config.id, &fields, &global_variables)?;
+
+    let mut variables: HashMap<String, EnvironmentEntry> = HashMap::new();
+    if include_inherited.unwrap_or(false) {
+        for (name, value) in std::env::vars() {
+            variables.insert(name.clone(), EnvironmentEntry {
+                name,
+                value,
+                source: "inherited".to_string(),
+            });
+        }
+    }
+    for (name, value) in resolved.fields.environment_variables.unwrap_or_default() {
+        let source = resolved
+            .sources
+            .get(&name)
+            .cloned()
+            .unwrap_or_else(|| "inline".to_string());
+        variables.insert(name.clone(), EnvironmentEntry { name, value, source });
+    }
+
+    // Secrets are only ever decrypted at spawn time, so the preview shows them redacted
+    for (name, secret_name) in app_config.secret_variables.clone().unwrap_or_default() {
+        variables.insert(name.clone(), EnvironmentEntry {
+            name,
+            value: REDACTED.to_string(),
+            source: format!("secret:{}", secret_name),
+        });
+    }
+
+    let mut variables: Vec<EnvironmentEntry> = variables.into_values().collect();
+    variables.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(EnvironmentPreview {
+        variables,
+        env_files: resolved.env_files,
+    })
+}
+
+/**
+ * Parse dotenv file contents into key/value pairs in file order.
+ *
+ * Supports `export` prefixes, `#` comments, unquoted values with trailing
+ * comments, single-quoted literal values and double-quoted values with
+ * `\n`, `\r`, `\t`, `\"`, `\\` and `\$` escapes spanning multiple lines.
+ * Values keep their `${VAR}` references for the interpolator, except inside
+ * single quotes where they are escaped so they stay literal.
+ */
+pub fn parse_dotenv(content: &str) -> Result<Vec<(String, String)>, String> {
+    let chars: Vec<char> = content.chars().collect();
+    let mut entries = Vec::new();
+    let mut i = 0;
+    let mut line = 1;
+
+    while i < chars.len() {
+        // Skip leading whitespace and blank lines
+        while i < chars.len() && chars[i].is_whitespace() {
+            if chars[i] == '\n' {
+                line += 1;
+            }
+            i += 1;
+        }
+        if i >= chars.len() {
+            break;
+        }
+
+        // Comment line
+        if chars[i] == '#' {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            continue;
+        }
+
+        let entry_line = line;
+        let mut key = read_while(&chars, &mut i, |c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'));
+
+        if key == "export" && i < chars.len() && (chars[i] == ' ' || chars[i] == '\t') {
+            skip_inline_whitespace(&chars, &mut i);
+            key = read_while(&chars, &mut i, |c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'));
+        }
+
+        if key.is_empty() || key.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(format!("line {}: invalid variable name", entry_line));
+        }
+
+        skip_inline_whitespace(&chars, &mut i);
+        if i >= chars.len() || chars[i] != '=' {
+            return Err(format!("line {}: expected '=' after {}", entry_line, key));
+        }
+        i += 1;
+        skip_inline_whitespace(&chars, &mut i);
+
+        let value = match chars.get(i) {
+            Some('"') => {
+                i += 1;
+                let mut value = String::new();
+                loop {
+                    match chars.get(i) {
+                        None => return Err(format!("line {}: unterminated double-quoted value", entry_line)),
+                        Some('"') => {
+                            i += 1;
+                            break;
+                        }
+                        Some('\\') => {
+                            match chars.get(i + 1) {
+                                Some('n') => value.push('\n'),
+                                Some('r') => value.push('\r'),
+                                Some('t') => value.push('\t'),
+                                Some('"') => value.push('"'),
+                                Some('\\') => value.push('\\'),
+                                // An escaped `$` before `{` must survive interpolation as a literal
+                                Some('$') if chars.get(i + 2) == Some(&'{') => value.push_str("$$"),
+                                Some('$') => value.push('$'),
+                                Some(other) => {
+                                    value.push('\\');
+                                    value.push(*other);
+                                }
+                                None => return Err(format!("line {}: unterminated double-quoted value", entry_line)),
+                            }
+                            i += 2;
+                        }
+                        Some(c) => {
+                            if *c == '\n' {
+                                line += 1;
+                            }
+                            value.push(*c);
+                            i += 1;
+                        }
+                    }
+                }
+                expect_line_end(&chars, &mut i, entry_line)?;
+                value
+            }
+            Some('\'') => {
+                i += 1;
+                let mut value = String::new();
+                loop {
+                    match chars.get(i) {
+                        None => return Err(format!("line {}: unterminated single-quoted value", entry_line)),
+                        Some('\'') => {
+                            i += 1;
+                            break;
+                        }
+                        Some(c) => {
+                            if *c == '\n' {
+                                line += 1;
+                            }
+                            value.push(*c);
+                            i += 1;
+                        }
+                    }
+                }
+                expect_line_end(&chars, &mut i, entry_line)?;
+                // Single-quoted values are literal, so keep references away from the interpolator
+                value.replace("${", "$${")
+            }
+            _ => {
+                let mut value = String::new();
+                while i < chars.len() && chars[i] != '\n' {
+                    // ` #` starts a trailing comment in unquoted values
+                    if chars[i] == '#' && value.ends_with([' ', '\t']) {
+                        while i < chars.len() && chars[i] != '\n' {
+                            i += 1;
+                        }
+                        break;
+                    }
+                    value.push(chars[i]);
+                    i += 1;
+                }
+                value.trim().to_string()
+            }
+        };
+
+        entries.push((key, value));
+    }
+
+    Ok(entries)
+}
+
+/// Consume characters matching the predicate and return them
+fn read_while(chars: &[char], i: &mut usize, predicate: impl Fn(char) -> bool) -> String {
+    let start = *i;
+    while *i < chars.len() && predicate(chars[*i]) {
+        *i += 1;
+    }
+    chars[start..*i].iter().collect()
+}
+
+/// Skip spaces and tabs without crossing a line break
+fn skip_inline_whitespace(chars: &[char], i: &mut usize) {
+    while *i < chars.len() && (chars[*i] == ' ' || chars[*i] == '\t') {
+        *i += 1;
+    }
+}
+
+/// After a quoted value only whitespace or a comment may follow on the same line
+fn expect_line_end(chars: &[char], i: &mut usize, line: usize) -> Result<(), String> {
+    skip_inline_whitespace(chars, i);
+    match chars.get(*i) {
+        None | Some('\n') | Some('\r') => Ok(()),
+        Some('#') => {
+            while *i < chars.len() && chars[*i] != '\n' {
+                *i += 1;
+            }
+            Ok(())
+        }
+        Some(_) => Err(format!("line {}: unexpected characters after quoted value", line)),
+    }
+}
+
+/**
+ * Expands `${VAR}` references against layered variable sources
+ */
+struct Interpolator<'a> {
+    /// App environment layers per variable, lowest precedence first
+    env: &'a HashMap<String, Vec<String>>,
+    globals: &'a HashMap<String, String>,
+    builtins: &'a HashMap<String, String>,
+    /// Already expanded app environment variables
+    resolved_env: HashMap<String, String>,
+    /// Variables currently being expanded with the env layer they came from
+    /// (`None` for global variables), used to resolve self-references
+    resolving: Vec<(String, Option<usize>)>,
+    unresolved: BTreeSet<String>,
+    /// Leave unknown references in place instead of reporting them
+    pass_through: bool,
+}
+
+impl<'a> Interpolator<'a> {
+    fn new(
+        env: &'a HashMap<String, Vec<String>>,
+        globals: &'a HashMap<String, String>,
+        builtins: &'a HashMap<String, String>,
+    ) -> Self {
+        Self {
+            env,
+            globals,
+            builtins,
+            resolved_env: HashMap::new(),
+            resolving: Vec::new(),
+            unresolved: BTreeSet::new(),
+            pass_through: false,
+        }
+    }
+
+    /// Fail with the list of every variable that could not be resolved
+    fn finish(self) -> AppResult<()> {
+        if self.unresolved.is_empty() {
+            return Ok(());
+        }
+
+        let names: Vec<String> = self.unresolved.into_iter().collect();
+        let mut details = HashMap::new();
+        details.insert("unresolved".to_string(), serde_json::json!(names));
+
+        Err(AppError::new(
+            "UNRESOLVED_VARIABLES",
+            &format!("Unresolved variables: {}", names.join(", ")),
+        )
+        .with_details(details))
+    }
+
+    /// Expand launch commands, leaving references to unknown names for the shell
+    fn expand_commands(&mut self, input: &str) -> String {
+        self.pass_through = true;
+        let expanded = self.expand(input);
+        self.pass_through = false;
+        expanded
+    }
+
+    /// Expand an app environment variable by name
+    fn resolve_env(&mut self, name: &str) -> String {
+        self.lookup(name).unwrap_or_default()
+    }
+
+    /// Look a variable up through all sources, expanding nested references
+    fn lookup(&mut self, name: &str) -> Option<String> {
+        // While a variable is being expanded, references to it resolve against the
+        // layer below, so `PATH=${PATH}:/opt/bin` extends the value from an env file
+        // or, failing that, the inherited PATH.
+        let (env, globals) = (self.env, self.globals);
+        let layers = env.get(name).map(|l| l.as_slice()).unwrap_or(&[]);
+        let in_progress_layer = self
+            .resolving
+            .iter()
+            .filter(|(n, _)| n == name)
+            .filter_map(|(_, layer)| *layer)
+            .min();
+        let global_in_progress = self
+            .resolving
+            .iter()
+            .any(|(n, layer)| n == name && layer.is_none());
+
+        let available = in_progress_layer.unwrap_or(layers.len());
+        if available > 0 && !global_in_progress {
+            let top = available - 1;
+            let is_final = in_progress_layer.is_none();
+            if is_final {
+                if let Some(value) = self.resolved_env.get(name) {
+                    return Some(value.clone());
+                }
+            }
+
+            self.resolving.push((name.to_string(), Some(top)));
+            let value = self.expand(&layers[top]);
+            self.resolving.pop();
+
+            if is_final {
+                self.resolved_env.insert(name.to_string(), value.clone());
+            }
+            return Some(value);
+        }
+
+        if !global_in_progress {
+            if let Some(raw) = globals.get(name) {
+                self.resolving.push((name.to_string(), None));
+                let value = self.expand(raw);
+                self.resolving.pop();
+                return Some(value);
+            }
+        }
+
+        if let Some(value) = self.builtins.get(name) {
+            return Some(value.clone());
+        }
+
+        std::env::var(name).ok()
+    }
+
+    /// Expand every `${...}` reference in the input
+    fn expand(&mut self, input: &str) -> String {
+        let mut output = String::with_capacity(input.len());
+        let mut rest = input;
+
+        while let Some(pos) = rest.find('$') {
+            output.push_str(&rest[..pos]);
+            let after = &rest[pos..];
+
+            // `$${` escapes a reference that should reach the shell untouched
+            if let Some(escaped) = after.strip_prefix("$${") {
+                output.push_str("${");
+                rest = escaped;
+                continue;
+            }
+
+            if !after.starts_with("${") {
+                output.push('$');
+                rest = &after[1..];
+                continue;
+            }
+
+            let Some(close) = find_closing_brace(&after[2..]) else {
+                // Unterminated reference - leave the remainder as-is
+                output.push_str(after);
+                rest = "";
+                break;
+            };
+            let body = &after[2..2 + close];
+            let reference = &after[..close + 3];
+            rest = &after[close + 3..];
+
+            let (name, fallback) = match body.split_once(":-") {
+                Some((name, fallback)) => (name, Some(fallback)),
+                None => (body, None),
+            };
+
+            // Anything that isn't a plain name (e.g. `${var%.*}`) is shell syntax we don't own
+            if !is_valid_name(name) {
+                output.push_str(reference);
+                continue;
+            }
+
+            match (self.lookup(name), fallback) {
+                (Some(value), Some(fallback)) if value.is_empty() => {
+                    let expanded = self.expand(fallback);
+                    output.push_str(&expanded);
+                }
+                (Some(value), _) => output.push_str(&value),
+                (None, Some(fallback)) => {
+                    let expanded = self.expand(fallback);
+                    output.push_str(&expanded);
+                }
+                (None, None) if self.pass_through => output.push_str(reference),
+                (None, None) => {
+                    self.unresolved.insert(name.to_string());
+                }
+            }
+        }
+
+        output.push_str(rest);
+        output
+    }
+}
+
+/// Find the brace closing a `${` reference, accounting for nested references in fallbacks
+fn find_closing_brace(input: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in input.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => return Some(i),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Check whether a string is a valid variable name (`[A-Za-z_][A-Za-z0-9_]*`)
+pub(crate) fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layers(vars: &[(&str, &str)]) -> HashMap<String, Vec<String>> {
+        let mut layers: HashMap<String, Vec<String>> = HashMap::new();
+        for (name, value) in vars {
+            layers.entry(name.to_string()).or_default().push(value.to_string());
+        }
+        layers
+    }
+
+    fn globals(vars: &[(&str, &str)]) -> HashMap<String, String> {
+        vars.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+    }
+
+    fn expand(env: &[(&str, &str)], input: &str) -> AppResult<String> {
+        let (env, globals, builtins) = (layers(env), HashMap::new(), globals(&[("APP_ID", "app")]));
+        let mut interpolator = Interpolator::new(&env, &globals, &builtins);
+        let expanded = interpolator.expand(input);
+        interpolator.finish().map(|_| expanded)
+    }
+
+    #[test]
+    fn nested_references_expand() {
+        let env = [("HOST", "localhost"), ("PORT_NUM", "3000"), ("URL", "http://${HOST}:${PORT_NUM}")];
+        assert_eq!(expand(&env, "open ${URL}/${APP_ID}").unwrap(), "open http://localhost:3000/app");
+    }
+
+    #[test]
+    fn layers_can_extend_themselves() {
+        let (env, globals, builtins) = (
+            layers(&[("ODD_TEST_PATH", "/usr/bin"), ("ODD_TEST_PATH", "${ODD_TEST_PATH}:/opt/bin")]),
+            HashMap::new(),
+            HashMap::new(),
+        );
+        let mut interpolator = Interpolator::new(&env, &globals, &builtins);
+        assert_eq!(interpolator.resolve_env("ODD_TEST_PATH"), "/usr/bin:/opt/bin");
+        assert!(interpolator.finish().is_ok());
+    }
+
+    #[test]
+    fn globals_are_looked_up_after_app_variables() {
+        let (env, globals, builtins) = (
+            layers(&[("NAME", "app")]),
+            globals(&[("NAME", "global"), ("REGION", "eu-${NAME}")]),
+            HashMap::new(),
+        );
+        let mut interpolator = Interpolator::new(&env, &globals, &builtins);
+        assert_eq!(interpolator.expand("${NAME} ${REGION}"), "app eu-app");
+    }
+
+    #[test]
+    fn cycles_are_unresolved() {
+        let env = [("ODD_TEST_A", "${ODD_TEST_B}"), ("ODD_TEST_B", "${ODD_TEST_A}")];
+        let error = expand(&env, "${ODD_TEST_A}").unwrap_err();
+        assert_eq!(error.code, "UNRESOLVED_VARIABLES");
+        assert_eq!(error.message, "Unresolved variables: ODD_TEST_A");
+    }
+
+    #[test]
+    fn defaults_apply_when_unset_or_empty() {
+        let env = [("EMPTY", ""), ("SET", "value")];
+        assert_eq!(expand(&env, "${ODD_TEST_UNSET:-fallback}").unwrap(), "fallback");
+        assert_eq!(expand(&env, "${EMPTY:-fallback}").unwrap(), "fallback");
+        assert_eq!(expand(&env, "${SET:-fallback}").unwrap(), "value");
+        assert_eq!(expand(&env, "${ODD_TEST_UNSET:-${SET}}").unwrap(), "value");
+    }
+
+    #[test]
+    fn escapes_and_shell_syntax_are_kept() {
+        assert_eq!(expand(&[], "echo $${HOME} $HOME $").unwrap(), "echo ${HOME} $HOME $");
+        assert_eq!(expand(&[], "mv ${file%.*} ${1}").unwrap(), "mv ${file%.*} ${1}");
+        assert_eq!(expand(&[], "echo ${UNTERMINATED").unwrap(), "echo ${UNTERMINATED");
+    }
+
+    #[test]
+    fn unknown_names_are_errors() {
+        let error = expand(&[], "${ODD_TEST_MISSING} ${ODD_TEST_OTHER}").unwrap_err();
+        assert_eq!(error.message, "Unresolved variables: ODD_TEST_MISSING, ODD_TEST_OTHER");
+    }
+
+    #[test]
+    fn launch_commands_keep_unknown_names() {
+        let fields = LaunchFields {
+            launch_commands: Some("export ODD_TEST_DIR=dist\nls ${ODD_TEST_DIR} ${APP_ID}".to_string()),
+            url: Some("http://localhost:${PORT}".to_string()),
+            port_to_check: Some(8080),
+            ..Default::default()
+        };
+        let resolved = resolve_launch_fields("app", &fields, &HashMap::new()).unwrap().fields;
+        assert_eq!(
+            resolved.launch_commands.as_deref(),
+            Some("export ODD_TEST_DIR=dist\nls ${ODD_TEST_DIR} app")
+        );
+        assert_eq!(resolved.url.as_deref(), Some("http://localhost:8080"));
+
+        let fields = LaunchFields {
+            url: Some("http://${ODD_TEST_DIR}".to_string()),
+            ..Default::default()
+        };
+        assert!(resolve_launch_fields("app", &fields, &HashMap::new()).is_err());
+    }
+
+    fn dotenv(content: &str) -> Vec<(String, String)> {
+        parse_dotenv(content).unwrap()
+    }
+
+    fn pairs(entries: &[(&str, &str)]) -> Vec<(String, String)> {
+        entries.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn dotenv_unquoted_values_and_comments() {
+        let content = "# comment\n\nA=1\n  B = two words  \nC=a#b # trailing\nD=\nexport E=5\nexport=6\n";
+        assert_eq!(
+            dotenv(content),
+            pairs(&[("A", "1"), ("B", "two words"), ("C", "a#b"), ("D", ""), ("E", "5"), ("export", "6")])
+        );
+    }
+
+    #[test]
+    fn dotenv_quoted_values() {
+        let content = "A=\"x \\\"y\\\" \\\\ \\$HOME \\${HOME} # kept\" # comment\nB='${HOME} \\n'\nC=\"tab\\tend\"\n";
+        assert_eq!(
+            dotenv(content),
+            pairs(&[("A", "x \"y\" \\ $HOME $${HOME} # kept"), ("B", "$${HOME} \\n"), ("C", "tab\tend")])
+        );
+    }
+
+    #[test]
+    fn dotenv_multi_line_values() {
+        let content = "KEY=\"-----BEGIN-----\nabc\n-----END-----\"\nSINGLE='one\ntwo'\nNEXT=\"a\\nb\"\n";
+        assert_eq!(
+            dotenv(content),
+            pairs(&[("KEY", "-----BEGIN-----\nabc\n-----END-----"), ("SINGLE", "one\ntwo"), ("NEXT", "a\nb")])
+        );
+    }
+
+    #[test]
+    fn dotenv_errors_name_the_line() {
+        assert_eq!(parse_dotenv("A=1\n\nB 2").unwrap_err(), "line 3: expected '=' after B");
+        assert_eq!(parse_dotenv("1A=x").unwrap_err(), "line 1: invalid variable name");
+        assert_eq!(parse_dotenv("A=\"x\ny\nB=1").unwrap_err(), "line 1: unterminated double-quoted value");
+        assert_eq!(parse_dotenv("A=\"x\" y").unwrap_err(), "line 1: unexpected characters after quoted value");
+        assert_eq!(parse_dotenv("A='x\ny'\nB=\"").unwrap_err(), "line 3: unterminated double-quoted value");
+    }
+
+    #[test]
+    fn relative_env_files_need_a_working_directory() {
+        let fields = LaunchFields {
+            env_files: vec![".env".to_string()],
+            ..Default::default()
+        };
+        let error = resolve_launch_fields("app", &fields, &HashMap::new()).unwrap_err();
+        assert_eq!(error.code, "ENV_FILE_RELATIVE_PATH");
+
+        let dir = std::env::temp_dir().join(format!("oddlauncher-env-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(".env"), "ODD_TEST_FROM_FILE=1\n").unwrap();
+        let absolute = dir.join(".env").to_string_lossy().to_string();
+        let fields = LaunchFields {
+            env_files: vec![absolute.clone()],
+            ..Default::default()
+        };
+        let resolved = resolve_launch_fields("app", &fields, &HashMap::new());
+        let relative = LaunchFields {
+            working_directory: Some(dir.to_string_lossy().to_string()),
+            env_files: vec![".env".to_string(), ".env.local".to_string()],
+            ..Default::default()
+        };
+        let resolved_relative = resolve_launch_fields("app", &relative, &HashMap::new());
+        fs::remove_dir_all(&dir).unwrap();
+
+        let resolved = resolved.unwrap();
+        assert_eq!(resolved.fields.environment_variables.unwrap()["ODD_TEST_FROM_FILE"], "1");
+        assert_eq!(resolved.sources["ODD_TEST_FROM_FILE"], absolute);
+        let loaded: Vec<bool> = resolved_relative.unwrap().env_files.iter().map(|f| f.loaded).collect();
+        assert_eq!(loaded, [true, false]);
+    }
+}
@@ -0,0 +1,497 @@
+use crate::commands::config::{
+    ensure_config_dir_exists, get_config_dir, read_global_config, store_global_config,
+};
+use crate::models::app::{AppConfig, AppError, AppResult, GlobalConfig, GlobalSettings};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+/// Maximum number of journal entries kept (oldest entries are dropped first)
+const MAX_HISTORY_ENTRIES: usize = 50;
+
+/// Version of the history file format
+const HISTORY_FORMAT_VERSION: u32 = 1;
+
+/**
+ * Kind of configuration mutation recorded in the journal
+ */
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigOperation {
+    Add,
+    Update,
+    Remove,
+    Reorder,
+    Settings,
+    /// Several kinds of change at once (imports, restores, bulk saves)
+    Replace,
+}
+
+/**
+ * The parts of the configuration tracked by the journal
+ */
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigState {
+    pub apps: Vec<AppConfig>,
+    pub settings: GlobalSettings,
+}
+
+/**
+ * A single recorded configuration change with before/after snapshots
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    pub id: String,
+    pub timestamp: String,
+    pub operation: ConfigOperation,
+    pub description: String,
+    /// Apps affected by the change
+    pub app_ids: Vec<String>,
+    pub before: ConfigState,
+    pub after: ConfigState,
+}
+
+/**
+ * History entry as returned to the frontend (without snapshots)
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntrySummary {
+    pub id: String,
+    pub timestamp: String,
+    pub operation: ConfigOperation,
+    pub description: String,
+    pub app_ids: Vec<String>,
+    /// Whether the change has been undone and can be redone
+    pub undone: bool,
+}
+
+/**
+ * Configuration history overview
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigHistory {
+    /// Entries, oldest first
+    pub entries: Vec<HistoryEntrySummary>,
+    pub can_undo: bool,
+    pub can_redo: bool,
+}
+
+/**
+ * Persisted journal: entries before `position` are applied, the rest can be redone
+ */
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HistoryJournal {
+    version: u32,
+    position: usize,
+    entries: Vec<HistoryEntry>,
+}
+
+/// Get the configuration change history
+#[tauri::command]
+pub async fn get_config_history(_app: AppHandle) -> AppResult<ConfigHistory> {
+    let journal = read_journal()?;
+
+    let entries = journal
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| HistoryEntrySummary {
+            id: entry.id.clone(),
+            timestamp: entry.timestamp.clone(),
+            operation: entry.operation,
+            description: entry.description.clone(),
+            app_ids: entry.app_ids.clone(),
+            undone: i >= journal.position,
+        })
+        .collect();
+
+    Ok(ConfigHistory {
+        entries,
+        can_undo: journal.position > 0,
+        can_redo: journal.position < journal.entries.len(),
+    })
+}
+
+/// Undo the most recent configuration change
+#[tauri::command]
+pub async fn undo_config_change(_app: AppHandle) -> AppResult<GlobalConfig> {
+    let mut journal = read_journal()?;
+    let entry = journal.undo()?;
+    log::info!("Undoing configuration change: {}", entry.description);
+
+    let config = apply_state(&entry.after, &entry.before)?;
+    write_journal(&journal)?;
+
+    Ok(config)
+}
+
+/// Redo the most recently undone configuration change
+#[tauri::command]
+pub async fn redo_config_change(_app: AppHandle) -> AppResult<GlobalConfig> {
+    let mut journal = read_journal()?;
+    let entry = journal.redo()?;
+    log::info!("Redoing configuration change: {}", entry.description);
+
+    let config = apply_state(&entry.before, &entry.after)?;
+    write_journal(&journal)?;
+
+    Ok(config)
+}
+
+/// Record the change between two saved configurations
+///
+/// Discards any undone entries, since a new change starts a new branch of history.
+pub(crate) fn record_config_change(before: &GlobalConfig, after: &GlobalConfig) -> AppResult<()> {
+    let mut journal = read_journal()?;
+    if !journal.record(ConfigState::from(before), ConfigState::from(after)) {
+        return Ok(());
+    }
+    write_journal(&journal)
+}
+
+impl HistoryJournal {
+    /// Add a change after the applied entries, dropping undone ones and the oldest beyond the limit
+    ///
+    /// Returns false, recording nothing, when nothing tracked changed.
+    fn record(&mut self, before: ConfigState, after: ConfigState) -> bool {
+        let Some((operation, description, app_ids)) = describe_change(&before.tracked(), &after.tracked()) else {
+            return false;
+        };
+
+        self.entries.truncate(self.position);
+        self.entries.push(HistoryEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            operation,
+            description,
+            app_ids,
+            before,
+            after,
+        });
+
+        if self.entries.len() > MAX_HISTORY_ENTRIES {
+            let excess = self.entries.len() - MAX_HISTORY_ENTRIES;
+            self.entries.drain(..excess);
+        }
+        self.position = self.entries.len();
+        true
+    }
+
+    /// Step back over the last applied entry, returning it
+    fn undo(&mut self) -> AppResult<HistoryEntry> {
+        if self.position == 0 {
+            return Err(AppError::new("NOTHING_TO_UNDO", "No configuration changes to undo"));
+        }
+        self.position -= 1;
+        Ok(self.entries[self.position].clone())
+    }
+
+    /// Step forward over the first undone entry, returning it
+    fn redo(&mut self) -> AppResult<HistoryEntry> {
+        if self.position >= self.entries.len() {
+            return Err(AppError::new("NOTHING_TO_REDO", "No configuration changes to redo"));
+        }
+        self.position += 1;
+        Ok(self.entries[self.position - 1].clone())
+    }
+}
+
+impl From<&GlobalConfig> for ConfigState {
+    fn from(config: &GlobalConfig) -> Self {
+        Self {
+            apps: config.apps.clone(),
+            settings: config.settings.clone(),
+        }
+    }
+}
+
+impl ConfigState {
+    /// Copy without usage statistics and timestamps, which change on every launch and aren't journaled
+    fn tracked(&self) -> ConfigState {
+        let mut state = self.clone();
+        for app in state.apps.iter_mut() {
+            app.last_used_at = None;
+            app.use_count = None;
+            app.updated_at.clear();
+        }
+        state
+    }
+}
+
+/// Replace the saved configuration with `target`, provided it still matches `expected`
+fn apply_state(expected: &ConfigState, target: &ConfigState) -> AppResult<GlobalConfig> {
+    let mut config = read_global_config()?;
+    restore_state(&mut config, expected, target)?;
+    store_global_config(config)?;
+
+    // Re-read so the returned config carries the new last_modified timestamp
+    read_global_config()
+}
+
+/// Change `config` to `target` in memory, provided it still matches `expected`
+fn restore_state(config: &mut GlobalConfig, expected: &ConfigState, target: &ConfigState) -> AppResult<()> {
+    if ConfigState::from(&*config).tracked() != expected.tracked() {
+        return Err(AppError::new(
+            "HISTORY_CONFLICT",
+            "The configuration was changed outside of the history and can't be undone or redone safely",
+        ));
+    }
+
+    // Keep current usage statistics rather than rolling them back
+    let apps = target
+        .apps
+        .iter()
+        .map(|app| {
+            let mut app = app.clone();
+            if let Some(current) = config.apps.iter().find(|a| a.id == app.id) {
+                app.last_used_at = current.last_used_at.clone();
+                app.use_count = current.use_count;
+            }
+            app
+        })
+        .collect();
+
+    config.apps = apps;
+    config.settings = target.settings.clone();
+    Ok(())
+}
+
+/// Classify a change, returning None when nothing tracked changed
+fn describe_change(
+    before: &ConfigState,
+    after: &ConfigState,
+) -> Option<(ConfigOperation, String, Vec<String>)> {
+    let before_ids: HashSet<&str> = before.apps.iter().map(|a| a.id.as_str()).collect();
+    let after_ids: HashSet<&str> = after.apps.iter().map(|a| a.id.as_str()).collect();
+
+    let added: Vec<&AppConfig> = after.apps.iter().filter(|a| !before_ids.contains(a.id.as_str())).collect();
+    let removed: Vec<&AppConfig> = before.apps.iter().filter(|a| !after_ids.contains(a.id.as_str())).collect();
+    let updated: Vec<&AppConfig> = after
+        .apps
+        .iter()
+        .filter(|a| before.apps.iter().any(|b| b.id == a.id && b != *a))
+        .collect();
+
+    let settings_changed = before.settings != after.settings;
+
+    // Same apps with the same contents, but in a different order
+    let reordered = added.is_empty()
+        && removed.is_empty()
+        && updated.is_empty()
+        && before.apps.iter().map(|a| &a.id).ne(after.apps.iter().map(|a| &a.id));
+
+    let app_ids = |apps: &[&AppConfig]| apps.iter().map(|a| a.id.clone()).collect::<Vec<_>>();
+    let describe = |verb: &str, apps: &[&AppConfig]| match apps {
+        [app] => format!("{} app '{}'", verb, app.name),
+        _ => format!("{} {} apps", verb, apps.len()),
+    };
+
+    let kinds = [!added.is_empty(), !removed.is_empty(), !updated.is_empty(), reordered, settings_changed]
+        .iter()
+        .filter(|changed| **changed)
+        .count();
+
+    match kinds {
+        0 => None,
+        1 if !added.is_empty() => Some((ConfigOperation::Add, describe("Added", &added), app_ids(&added))),
+        1 if !removed.is_empty() => Some((ConfigOperation::Remove, describe("Removed", &removed), app_ids(&removed))),
+        1 if !updated.is_empty() => Some((ConfigOperation::Update, describe("Updated", &updated), app_ids(&updated))),
+        1 if reordered => Some((ConfigOperation::Reorder, "Reordered apps".to_string(), Vec::new())),
+        1 => Some((ConfigOperation::Settings, "Updated settings".to_string(), Vec::new())),
+        _ => {
+            let mut ids = app_ids(&added);
+            ids.extend(app_ids(&removed));
+            ids.extend(app_ids(&updated));
+            let description = format!(
+                "Replaced configuration ({} added, {} removed, {} updated{})",
+                added.len(),
+                removed.len(),
+                updated.len(),
+                if settings_changed { ", settings changed" } else { "" }
+            );
+            Some((ConfigOperation::Replace, description, ids))
+        }
+    }
+}
+
+/// Get the path to the history journal (~/.oddlauncher/history.json)
+fn get_history_file_path() -> AppResult<PathBuf> {
+    Ok(get_config_dir()?.join("history.json"))
+}
+
+fn read_journal() -> AppResult<HistoryJournal> {
+    let history_file = get_history_file_path()?;
+
+    if !history_file.exists() {
+        return Ok(HistoryJournal {
+            version: HISTORY_FORMAT_VERSION,
+            ..Default::default()
+        });
+    }
+
+    let content = fs::read_to_string(&history_file).map_err(|e| {
+        AppError::new(
+            "FILE_READ_ERROR",
+            &format!("Failed to read history file: {}", e),
+        )
+    })?;
+
+    match serde_json::from_str::<HistoryJournal>(&content) {
+        Ok(mut journal) => {
+            journal.position = journal.position.min(journal.entries.len());
+            Ok(journal)
+        }
+        Err(e) => {
+            // A corrupt journal shouldn't block saving configuration; start over
+            log::warn!("Discarding unreadable history file: {}", e);
+            Ok(HistoryJournal {
+                version: HISTORY_FORMAT_VERSION,
+                ..Default::default()
+            })
+        }
+    }
+}
+
+fn write_journal(journal: &HistoryJournal) -> AppResult<()> {
+    ensure_config_dir_exists()?;
+
+    let content = serde_json::to_string(journal).map_err(|e| {
+        AppError::new(
+            "JSON_SERIALIZE_ERROR",
+            &format!("Failed to serialize history: {}", e),
+        )
+    })?;
+
+    fs::write(get_history_file_path()?, content).map_err(|e| {
+        AppError::new(
+            "FILE_WRITE_ERROR",
+            &format!("Failed to write history file: {}", e),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(id: &str, command: &str) -> AppConfig {
+        AppConfig::for_test(id, &id.to_uppercase(), command)
+    }
+
+    fn state(apps: Vec<AppConfig>) -> ConfigState {
+        ConfigState { apps, settings: GlobalSettings::default() }
+    }
+
+    fn config(state: &ConfigState) -> GlobalConfig {
+        GlobalConfig { apps: state.apps.clone(), settings: state.settings.clone(), ..GlobalConfig::default() }
+    }
+
+    fn descriptions(journal: &HistoryJournal) -> Vec<&str> {
+        journal.entries.iter().map(|entry| entry.description.as_str()).collect()
+    }
+
+    #[test]
+    fn changes_are_classified() {
+        let empty = state(Vec::new());
+        let one = state(vec![app("a", "npm start")]);
+        let two = state(vec![app("a", "npm start"), app("b", "cargo run")]);
+        let swapped = state(vec![app("b", "cargo run"), app("a", "npm start")]);
+        let edited = state(vec![app("a", "npm run dev")]);
+        let mut settings = one.clone();
+        settings.settings.trusted_import_sources = vec!["https://example.com/".to_string()];
+
+        let op = |before: &ConfigState, after: &ConfigState| describe_change(before, after).map(|(op, description, _)| (op, description));
+        assert_eq!(op(&empty, &one), Some((ConfigOperation::Add, "Added app 'A'".to_string())));
+        assert_eq!(op(&two, &empty), Some((ConfigOperation::Remove, "Removed 2 apps".to_string())));
+        assert_eq!(op(&one, &edited), Some((ConfigOperation::Update, "Updated app 'A'".to_string())));
+        assert_eq!(op(&two, &swapped), Some((ConfigOperation::Reorder, "Reordered apps".to_string())));
+        assert_eq!(op(&one, &settings), Some((ConfigOperation::Settings, "Updated settings".to_string())));
+        assert_eq!(
+            op(&edited, &swapped),
+            Some((ConfigOperation::Replace, "Replaced configuration (1 added, 0 removed, 1 updated)".to_string()))
+        );
+        assert_eq!(op(&one, &one), None);
+    }
+
+    #[test]
+    fn usage_statistics_are_not_journaled() {
+        let before = state(vec![app("a", "npm start")]);
+        let mut after = before.clone();
+        after.apps[0].use_count = Some(3);
+        after.apps[0].last_used_at = Some("2024-02-01T00:00:00Z".to_string());
+        after.apps[0].updated_at = "2024-02-01T00:00:00Z".to_string();
+
+        let mut journal = HistoryJournal::default();
+        assert!(!journal.record(before, after));
+        assert!(journal.entries.is_empty());
+    }
+
+    #[test]
+    fn undo_and_redo_move_through_the_journal() {
+        let states = [state(Vec::new()), state(vec![app("a", "x")]), state(vec![app("a", "y")])];
+        let mut journal = HistoryJournal::default();
+        assert!(journal.record(states[0].clone(), states[1].clone()));
+        assert!(journal.record(states[1].clone(), states[2].clone()));
+        assert_eq!(journal.position, 2);
+
+        assert_eq!(journal.undo().unwrap().before, states[1]);
+        assert_eq!(journal.undo().unwrap().before, states[0]);
+        assert_eq!(journal.undo().unwrap_err().code, "NOTHING_TO_UNDO");
+        assert_eq!(journal.position, 0);
+
+        assert_eq!(journal.redo().unwrap().after, states[1]);
+        assert_eq!(journal.position, 1);
+
+        // A new change drops the undone entry
+        assert!(journal.record(states[1].clone(), state(vec![app("a", "z")])));
+        assert_eq!(descriptions(&journal), ["Added app 'A'", "Updated app 'A'"]);
+        assert_eq!(journal.entries[1].after.apps[0].launch_commands.as_deref(), Some("z"));
+        assert_eq!(journal.redo().unwrap_err().code, "NOTHING_TO_REDO");
+    }
+
+    #[test]
+    fn journal_is_capped() {
+        let mut journal = HistoryJournal::default();
+        for i in 0..MAX_HISTORY_ENTRIES + 5 {
+            let before = state(vec![app("a", &i.to_string())]);
+            let after = state(vec![app("a", &(i + 1).to_string())]);
+            assert!(journal.record(before, after));
+        }
+        assert_eq!(journal.entries.len(), MAX_HISTORY_ENTRIES);
+        assert_eq!(journal.position, MAX_HISTORY_ENTRIES);
+        assert_eq!(journal.entries[0].before.apps[0].launch_commands.as_deref(), Some("5"));
+    }
+
+    #[test]
+    fn restore_keeps_usage_statistics() {
+        let before = state(vec![app("a", "npm start")]);
+        let after = state(vec![app("a", "npm run dev"), app("b", "cargo run")]);
+        let mut current = config(&after);
+        current.apps[0].use_count = Some(7);
+        current.apps[0].updated_at = "2024-03-01T00:00:00Z".to_string();
+
+        restore_state(&mut current, &after, &before).unwrap();
+        assert_eq!(current.apps.len(), 1);
+        assert_eq!(current.apps[0].launch_commands.as_deref(), Some("npm start"));
+        assert_eq!(current.apps[0].use_count, Some(7));
+    }
+
+    #[test]
+    fn restore_refuses_outside_changes() {
+        let before = state(vec![app("a", "npm start")]);
+        let after = state(vec![app("a", "npm run dev")]);
+        let mut current = config(&after);
+        current.apps.push(app("c", "make"));
+
+        let error = restore_state(&mut current, &after, &before).unwrap_err();
+        assert_eq!(error.code, "HISTORY_CONFLICT");
+        assert_eq!(current.apps.len(), 2);
+    }
+}
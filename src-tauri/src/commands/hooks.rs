@@ -0,0 +1,535 @@
+use crate::commands::dev_env::wrap_in_dev_environment;
+use crate::commands::process::{default_terminal_type, parse_step_marker, prepare_launch};
+use crate::commands::secrets::redact;
+use crate::commands::terminal::WslTarget;
+use crate::models::app::{AppConfig, CustomShell, DevEnvironment, LaunchMode};
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command as TokioCommand;
+use tokio::sync::{mpsc, watch};
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+// Pre-launch and post-stop hooks: short commands (pulling code, installing dependencies, starting
+// or stopping services) run one at a time around an app's own process, each to completion.
+
+/// How long each hook may run when the app doesn't set a timeout
+pub(crate) const DEFAULT_HOOK_TIMEOUT_SECS: u32 = 300;
+
+/// How long to keep reading a finished hook's output, which programs it left running may hold open
+const OUTPUT_DRAIN_TIMEOUT: Duration = Duration::from_secs(1);
+
+/**
+ * When a hook runs, relative to the app's own process
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum HookStage {
+    PreLaunch,
+    PostStop,
+}
+
+impl HookStage {
+    /// Label hook output and events are marked with
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            HookStage::PreLaunch => "pre-launch",
+            HookStage::PostStop => "post-stop",
+        }
+    }
+
+    /// The app's commands for this stage, without blank entries
+    pub(crate) fn commands(self, app: &AppConfig) -> Vec<String> {
+        let commands = match self {
+            HookStage::PreLaunch => app.pre_launch.as_ref(),
+            HookStage::PostStop => app.post_stop.as_ref(),
+        };
+        commands
+            .into_iter()
+            .flatten()
+            .map(|command| command.trim().to_string())
+            .filter(|command| !command.is_empty())
+            .collect()
+    }
+}
+
+/**
+ * How a hook ended
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum HookOutcome {
+    Succeeded,
+    /// Exited unsuccessfully, with its exit code if it has one
+    Failed(Option<i32>),
+    /// Killed for running longer than the hook timeout
+    TimedOut,
+    /// Killed because the app was stopped while its hooks were running
+    Cancelled,
+    /// Couldn't be started at all
+    Error(String),
+}
+
+impl HookOutcome {
+    pub(crate) fn success(&self) -> bool {
+        *self == HookOutcome::Succeeded
+    }
+
+    pub(crate) fn exit_code(&self) -> Option<i32> {
+        match self {
+            HookOutcome::Succeeded => Some(0),
+            HookOutcome::Failed(code) => *code,
+            HookOutcome::TimedOut | HookOutcome::Cancelled | HookOutcome::Error(_) => None,
+        }
+    }
+}
+
+/**
+ * Everything hooks need to run the way the app's launch commands do
+ */
+#[derive(Debug, Clone)]
+pub(crate) struct HookEnvironment {
+    pub app_id: String,
+    /// Normalized working directory
+    pub working_dir: Option<String>,
+    pub terminal_type: Option<String>,
+    pub custom_shells: Vec<CustomShell>,
+    pub wsl_target: WslTarget,
+    pub dev_environment: Option<DevEnvironment>,
+    /// Full environment: login shell variables, the app's variables and its secrets
+    pub variables: HashMap<String, String>,
+    /// Secret values to redact from output
+    pub secret_values: Vec<String>,
+    pub timeout: Duration,
+    /// Set to true to kill the running hook and skip the rest
+    pub cancel: Option<watch::Receiver<bool>>,
+}
+
+/// Per-hook timeout from an app's `hook_timeout` setting
+pub(crate) fn hook_timeout(timeout_secs: Option<u32>) -> Duration {
+    Duration::from_secs(timeout_secs.unwrap_or(DEFAULT_HOOK_TIMEOUT_SECS) as u64)
+}
+
+/**
+ * Where hook progress and output go: app events in the UI, the terminal on the command line
+ */
+pub(crate) trait HookReporter: Send {
+    fn started(&mut self, stage: HookStage, index: usize, total: usize, command: &str);
+    fn output(&mut self, stage: HookStage, stream: &str, line: &str);
+    fn finished(
+        &mut self,
+        stage: HookStage,
+        index: usize,
+        total: usize,
+        command: &str,
+        outcome: &HookOutcome,
+        duration: Duration,
+    );
+}
+
+/// Run an app's hooks for a stage in order
+///
+/// Returns a message describing the first hook that didn't succeed. With `stop_on_failure`
+/// the remaining hooks are skipped after a failure; otherwise they all run. Cancelling
+/// always skips the remaining hooks.
+pub(crate) async fn run_hooks(
+    hooks: &HookEnvironment,
+    stage: HookStage,
+    commands: &[String],
+    stop_on_failure: bool,
+    reporter: &mut dyn HookReporter,
+) -> Result<(), String> {
+    let total = commands.len();
+    let mut first_failure = None;
+
+    for (index, command) in commands.iter().enumerate() {
+        let shown = redact(command, &hooks.secret_values);
+        log::info!("App {} running {} hook {}/{}: {}", hooks.app_id, stage.label(), index + 1, total, shown);
+        reporter.started(stage, index, total, &shown);
+
+        let started = Instant::now();
+        let outcome = run_hook(hooks, stage, command, reporter).await;
+        let duration = started.elapsed();
+        log::info!(
+            "App {} {} hook {}/{} ended after {}ms: {:?}",
+            hooks.app_id,
+            stage.label(),
+            index + 1,
+            total,
+            duration.as_millis(),
+            outcome
+        );
+        reporter.finished(stage, index, total, &shown, &outcome, duration);
+
+        if !outcome.success() && first_failure.is_none() {
+            let reason = match &outcome {
+                HookOutcome::TimedOut => format!("timed out after {}s", hooks.timeout.as_secs()),
+                HookOutcome::Cancelled => "was cancelled".to_string(),
+                HookOutcome::Error(e) => format!("could not be started: {}", e),
+                HookOutcome::Failed(Some(code)) => format!("failed with exit code {}", code),
+                _ => "failed".to_string(),
+            };
+            first_failure = Some(format!("{} hook '{}' {}", stage.label(), shown, reason));
+            if stop_on_failure || outcome == HookOutcome::Cancelled {
+                break;
+            }
+        }
+    }
+
+    match first_failure {
+        Some(message) => Err(message),
+        None => Ok(()),
+    }
+}
+
+/// Run a single hook to completion or until it times out, reporting its output line by line
+async fn run_hook(
+    hooks: &HookEnvironment,
+    stage: HookStage,
+    command: &str,
+    reporter: &mut dyn HookReporter,
+) -> HookOutcome {
+    // Hooks always go through a shell, so `&&`, pipes and quoting work even without a terminal type
+    let working_dir = hooks.working_dir.as_deref();
+    let terminal_type = hooks
+        .terminal_type
+        .as_deref()
+        .unwrap_or_else(|| default_terminal_type(working_dir));
+    let prepared = prepare_launch(
+        &hooks.app_id,
+        command,
+        working_dir,
+        Some(terminal_type),
+        LaunchMode::Inline,
+        &hooks.custom_shells,
+        &hooks.wsl_target,
+    );
+    let prepared = match (prepared, hooks.dev_environment) {
+        (Ok((program, args, _)), Some(kind)) => {
+            wrap_in_dev_environment(kind, program, args, working_dir, hooks.variables.get("PATH").map(String::as_str)).await
+        }
+        (prepared, _) => prepared.map(|(program, args, _)| (program, args)),
+    };
+    let (program, args) = match prepared {
+        Ok(prepared) => prepared,
+        Err(e) => return HookOutcome::Error(e),
+    };
+
+    let mut cmd = TokioCommand::new(&program);
+    // cmd.exe doesn't follow the usual argument quoting rules, and its script is already escaped for it
+    #[cfg(windows)]
+    {
+        if program == "cmd.exe" {
+            for arg in &args {
+                cmd.raw_arg(arg);
+            }
+        } else {
+            cmd.args(&args);
+        }
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+    #[cfg(not(windows))]
+    cmd.args(&args);
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .envs(&hooks.variables);
+
+    if let Some(dir) = working_dir {
+        if !(cfg!(target_os = "windows") && program == "wsl.exe") {
+            cmd.current_dir(dir);
+        }
+    }
+
+    // Own process group, so a hook that times out can be killed along with whatever it started
+    #[cfg(unix)]
+    unsafe {
+        cmd.pre_exec(|| {
+            libc::setpgid(0, 0);
+            Ok(())
+        });
+    }
+
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => return HookOutcome::Error(format!("Failed to start '{}': {}", program, e)),
+    };
+
+    // Both streams feed one channel so lines are reported in the order they arrive
+    let (sender, mut lines) = mpsc::unbounded_channel::<(&'static str, String)>();
+    if let Some(stdout) = child.stdout.take() {
+        tokio::spawn(forward_lines(stdout, "stdout", sender.clone()));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        tokio::spawn(forward_lines(stderr, "stderr", sender.clone()));
+    }
+    drop(sender);
+
+    let mut report = |stream: &str, line: String| {
+        // Step markers are for launch commands; a hook is a single step of its own
+        if parse_step_marker(&line).is_none() {
+            reporter.output(stage, stream, &redact(&line, &hooks.secret_values));
+        }
+    };
+
+    let deadline = tokio::time::sleep(hooks.timeout);
+    tokio::pin!(deadline);
+    let mut cancel = hooks.cancel.clone();
+    let outcome = loop {
+        tokio::select! {
+            Some((stream, line)) = lines.recv() => report(stream, line),
+            status = child.wait() => break match status {
+                Ok(status) if status.success() => HookOutcome::Succeeded,
+                Ok(status) => HookOutcome::Failed(status.code()),
+                Err(e) => HookOutcome::Error(format!("Failed to wait for '{}': {}", program, e)),
+            },
+            _ = &mut deadline => {
+                kill_hook(&mut child).await;
+                break HookOutcome::TimedOut;
+            }
+            _ = cancelled(&mut cancel) => {
+                kill_hook(&mut child).await;
+                break HookOutcome::Cancelled;
+            }
+        }
+    };
+
+    let drain_until = tokio::time::Instant::now() + OUTPUT_DRAIN_TIMEOUT;
+    while let Ok(Some((stream, line))) = tokio::time::timeout_at(drain_until, lines.recv()).await {
+        report(stream, line);
+    }
+    outcome
+}
+
+/// Resolve once the hooks are cancelled; never resolves without a cancel signal
+async fn cancelled(cancel: &mut Option<watch::Receiver<bool>>) {
+    if let Some(cancel) = cancel {
+        if cancel.wait_for(|cancelled| *cancelled).await.is_ok() {
+            return;
+        }
+    }
+    // Without a sender, cancelling is no longer possible
+    std::future::pending().await
+}
+
+/// Send each line read from a hook's output stream to the channel, without its line ending
+async fn forward_lines<R>(stream: R, name: &'static str, sender: mpsc::UnboundedSender<(&'static str, String)>)
+where
+    R: tokio::io::AsyncRead + Unpin,
+{
+    let mut reader = BufReader::new(stream).lines();
+    loop {
+        match reader.next_line().await {
+            Ok(Some(line)) => {
+                if sender.send((name, line)).is_err() {
+                    break;
+                }
+            }
+            Ok(None) => break,
+            Err(e) => {
+                log::error!("Error reading hook {}: {}", name, e);
+                break;
+            }
+        }
+    }
+}
+
+/// Kill a hook that ran too long or was cancelled, along with the processes it started
+async fn kill_hook(child: &mut tokio::process::Child) {
+    if let Some(pid) = child.id() {
+        #[cfg(unix)]
+        unsafe {
+            libc::kill(-(pid as i32), libc::SIGKILL);
+        }
+        #[cfg(windows)]
+        {
+            let mut taskkill = TokioCommand::new("taskkill");
+            taskkill.arg("/PID").arg(pid.to_string()).arg("/T").arg("/F");
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            taskkill.creation_flags(CREATE_NO_WINDOW);
+            let _ = taskkill.output().await;
+        }
+    }
+    let _ = child.kill().await;
+}
+
+/**
+ * Reports hooks started from the UI as app events, with their output in the app's terminal
+ */
+pub(crate) struct EventHookReporter {
+    pub app_handle: AppHandle,
+    pub app_id: String,
+}
+
+impl HookReporter for EventHookReporter {
+    fn started(&mut self, stage: HookStage, index: usize, total: usize, command: &str) {
+        let _ = self.app_handle.emit("hook-started", serde_json::json!({
+            "appId": self.app_id,
+            "stage": stage.label(),
+            "index": index,
+            "total": total,
+            "command": command,
+            "timestamp": chrono::Utc::now().to_rfc3339()
+        }));
+    }
+
+    fn output(&mut self, stage: HookStage, stream: &str, line: &str) {
+        let _ = self.app_handle.emit("process-output", serde_json::json!({
+            "appId": self.app_id,
+            "type": stream,
+            "content": format!("[{}] {}", stage.label(), line),
+            "timestamp": chrono::Utc::now().to_rfc3339()
+        }));
+    }
+
+    fn finished(
+        &mut self,
+        stage: HookStage,
+        index: usize,
+        total: usize,
+        command: &str,
+        outcome: &HookOutcome,
+        duration: Duration,
+    ) {
+        let error = match outcome {
+            HookOutcome::Error(e) => Some(e.clone()),
+            _ => None,
+        };
+        let _ = self.app_handle.emit("hook-finished", serde_json::json!({
+            "appId": self.app_id,
+            "stage": stage.label(),
+            "index": index,
+            "total": total,
+            "command": command,
+            "exitCode": outcome.exit_code(),
+            "timedOut": *outcome == HookOutcome::TimedOut,
+            "cancelled": *outcome == HookOutcome::Cancelled,
+            "error": error,
+            "durationMs": duration.as_millis() as u64,
+            "timestamp": chrono::Utc::now().to_rfc3339()
+        }));
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    /// Records what the hook runner reports
+    #[derive(Default)]
+    struct Recorder {
+        started: Vec<String>,
+        output: Vec<String>,
+        outcomes: Vec<HookOutcome>,
+    }
+
+    impl HookReporter for Recorder {
+        fn started(&mut self, _stage: HookStage, _index: usize, _total: usize, command: &str) {
+            self.started.push(command.to_string());
+        }
+
+        fn output(&mut self, _stage: HookStage, _stream: &str, line: &str) {
+            self.output.push(line.to_string());
+        }
+
+        fn finished(
+            &mut self,
+            _stage: HookStage,
+            _index: usize,
+            _total: usize,
+            _command: &str,
+            outcome: &HookOutcome,
+            _duration: Duration,
+        ) {
+            self.outcomes.push(outcome.clone());
+        }
+    }
+
+    fn environment(timeout: Duration) -> HookEnvironment {
+        HookEnvironment {
+            app_id: "app".to_string(),
+            working_dir: None,
+            terminal_type: Some("bash".to_string()),
+            custom_shells: Vec::new(),
+            wsl_target: WslTarget::default(),
+            dev_environment: None,
+            variables: HashMap::from([("SECRET".to_string(), "hunter2hunter2".to_string())]),
+            secret_values: vec!["hunter2hunter2".to_string()],
+            timeout,
+            cancel: None,
+        }
+    }
+
+    fn run(hooks: &HookEnvironment, commands: &[&str], stop_on_failure: bool) -> (Result<(), String>, Recorder) {
+        let commands: Vec<String> = commands.iter().map(|c| c.to_string()).collect();
+        let mut recorder = Recorder::default();
+        let result = tauri::async_runtime::block_on(run_hooks(
+            hooks,
+            HookStage::PreLaunch,
+            &commands,
+            stop_on_failure,
+            &mut recorder,
+        ));
+        (result, recorder)
+    }
+
+    #[test]
+    fn hooks_run_in_order() {
+        let (result, recorder) = run(&environment(Duration::from_secs(10)), &["echo one", "echo two"], true);
+        assert_eq!(result, Ok(()));
+        // Inline launches echo each command before running it
+        assert_eq!(recorder.output, vec!["$ echo one", "one", "$ echo two", "two"]);
+        assert_eq!(recorder.outcomes, vec![HookOutcome::Succeeded, HookOutcome::Succeeded]);
+    }
+
+    #[test]
+    fn failure_aborts_remaining_hooks() {
+        let (result, recorder) = run(&environment(Duration::from_secs(10)), &["exit 3", "echo skipped"], true);
+        assert_eq!(result, Err("pre-launch hook 'exit 3' failed with exit code 3".to_string()));
+        assert_eq!(recorder.started, vec!["exit 3"]);
+        assert_eq!(recorder.outcomes, vec![HookOutcome::Failed(Some(3))]);
+    }
+
+    #[test]
+    fn failure_can_continue() {
+        let (result, recorder) = run(&environment(Duration::from_secs(10)), &["false", "echo ran"], false);
+        assert_eq!(result, Err("pre-launch hook 'false' failed with exit code 1".to_string()));
+        assert_eq!(recorder.output, vec!["$ false", "$ echo ran", "ran"]);
+        assert_eq!(recorder.outcomes, vec![HookOutcome::Failed(Some(1)), HookOutcome::Succeeded]);
+    }
+
+    #[test]
+    fn slow_hook_times_out() {
+        let started = Instant::now();
+        let (result, recorder) = run(&environment(Duration::from_secs(1)), &["sleep 30", "echo skipped"], true);
+        assert_eq!(result, Err("pre-launch hook 'sleep 30' timed out after 1s".to_string()));
+        assert_eq!(recorder.outcomes, vec![HookOutcome::TimedOut]);
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn cancelling_stops_all_hooks() {
+        let (sender, receiver) = watch::channel(false);
+        let mut hooks = environment(Duration::from_secs(30));
+        hooks.cancel = Some(receiver);
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(300));
+            let _ = sender.send(true);
+        });
+
+        // Cancelling skips the rest even when failures don't
+        let (result, recorder) = run(&hooks, &["sleep 30", "echo skipped"], false);
+        assert_eq!(result, Err("pre-launch hook 'sleep 30' was cancelled".to_string()));
+        assert_eq!(recorder.outcomes, vec![HookOutcome::Cancelled]);
+    }
+
+    #[test]
+    fn secrets_are_redacted() {
+        let (_, recorder) = run(&environment(Duration::from_secs(10)), &["echo \"token $SECRET\""], true);
+        assert_eq!(recorder.output[1], format!("token {}", crate::commands::secrets::REDACTED));
+    }
+}
@@ -0,0 +1,347 @@
+use crate::commands::config::{ensure_config_dir_exists, find_app, get_config_dir, read_global_config};
+use crate::commands::control::{get_control_endpoint, get_control_token_path};
+use crate::commands::deep_link::{handle_deep_link, is_deep_link};
+use crate::commands::process::{start_saved_app, stop_app_process, ProcessManager};
+use crate::models::app::{AppError, AppResult};
+use serde_json::Value;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Write};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+// Single-instance enforcement: one OddLauncher per config directory.
+// A second launch forwards its arguments to the running instance over the control API.
+
+/// Start an app by ID or name once the launcher is running (repeatable)
+pub const START_FLAG: &str = "--start";
+
+/// Stop a running app by ID or name (repeatable)
+pub const STOP_FLAG: &str = "--stop";
+
+/// Profile to use for apps started with `--start`
+pub const PROFILE_FLAG: &str = "--profile";
+
+/// How long a second instance waits for the first one's control API to come up
+const FORWARD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/**
+ * Held for the lifetime of the primary instance; the lock is released when dropped
+ */
+pub struct InstanceLock {
+    _file: File,
+}
+
+/// Try to become the primary instance for the current config directory
+///
+/// Returns None if another instance already holds the lock.
+pub fn acquire_instance_lock() -> AppResult<Option<InstanceLock>> {
+    ensure_config_dir_exists()?;
+    let path = get_config_dir()?.join("instance.lock");
+
+    let lock_error = |e: std::io::Error| {
+        AppError::new("INSTANCE_LOCK_ERROR", &format!("Failed to open instance lock: {}", e))
+    };
+
+    #[cfg(unix)]
+    let file = {
+        use std::os::unix::io::AsRawFd;
+
+        let file = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(lock_error)?;
+
+        // flock is released by the OS if the process dies, so a crash never leaves a stale lock
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } != 0 {
+            let err = std::io::Error::last_os_error();
+            if err.kind() == std::io::ErrorKind::WouldBlock {
+                return Ok(None);
+            }
+            return Err(lock_error(err));
+        }
+        file
+    };
+
+    #[cfg(windows)]
+    let file = {
+        use std::os::windows::fs::OpenOptionsExt;
+
+        // Opening without sharing fails while another process has the file open
+        const ERROR_SHARING_VIOLATION: i32 = 32;
+        match fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .share_mode(0)
+            .open(&path)
+        {
+            Ok(file) => file,
+            Err(e) if e.raw_os_error() == Some(ERROR_SHARING_VIOLATION) => return Ok(None),
+            Err(e) => return Err(lock_error(e)),
+        }
+    };
+
+    // Record the owner for troubleshooting
+    let mut file = file;
+    let _ = file.set_len(0);
+    let _ = write!(file, "{}", std::process::id());
+
+    Ok(Some(InstanceLock { _file: file }))
+}
+
+/// Whether the arguments ask the launcher to do anything beyond showing its window
+pub fn has_instance_actions(args: &[String]) -> bool {
+    args.iter().any(|arg| {
+        is_deep_link(arg)
+            || [START_FLAG, STOP_FLAG]
+                .iter()
+                .any(|flag| arg == flag || arg.starts_with(&format!("{}=", flag)))
+    })
+}
+
+/// Hand the arguments of a second launch to the running instance
+pub fn forward_to_running_instance(args: &[String]) -> AppResult<Value> {
+    let started = Instant::now();
+
+    // The running instance may still be starting up, in which case its socket isn't there yet
+    let stream = loop {
+        match connect_control_endpoint() {
+            Ok(stream) => break stream,
+            Err(e) if started.elapsed() < FORWARD_TIMEOUT => {
+                log::debug!("Waiting for the running instance: {}", e);
+                std::thread::sleep(Duration::from_millis(250));
+            }
+            Err(e) => {
+                return Err(forward_error(format!(
+                    "OddLauncher is already running but could not be reached: {}",
+                    e
+                )))
+            }
+        }
+    };
+
+    ControlClient::authenticate(stream)?.call("handle_instance_args", serde_json::json!({ "args": args }))
+}
+
+/**
+ * Authenticated connection to a running instance's control API
+ */
+pub(crate) struct ControlClient {
+    reader: BufReader<ControlStream>,
+    writer: ControlStream,
+    next_id: u32,
+}
+
+impl ControlClient {
+    /// Connect to the instance running for the current config directory, if there is one
+    pub(crate) fn connect() -> AppResult<Option<ControlClient>> {
+        match connect_control_endpoint() {
+            Ok(stream) => ControlClient::authenticate(stream).map(Some),
+            Err(e) => {
+                log::debug!("No running instance to connect to: {}", e);
+                Ok(None)
+            }
+        }
+    }
+
+    fn authenticate(stream: ControlStream) -> AppResult<ControlClient> {
+        let token = fs::read_to_string(get_control_token_path()?)
+            .map_err(|e| forward_error(format!("Failed to read control API token: {}", e)))?;
+
+        let reader = BufReader::new(stream.try_clone().map_err(|e| forward_error(e.to_string()))?);
+        let mut client = ControlClient { reader, writer: stream, next_id: 1 };
+        client.call("authenticate", serde_json::json!({ "token": token.trim() }))?;
+        Ok(client)
+    }
+
+    /// Call a control API method, returning its result
+    pub(crate) fn call(&mut self, method: &str, params: Value) -> AppResult<Value> {
+        let request = serde_json::json!({ "jsonrpc": "2.0", "id": self.next_id, "method": method, "params": params });
+        self.next_id += 1;
+        writeln!(self.writer, "{}", request).map_err(|e| forward_error(e.to_string()))?;
+
+        let mut line = String::new();
+        self.reader.read_line(&mut line).map_err(|e| forward_error(e.to_string()))?;
+        let response: Value = serde_json::from_str(&line)
+            .map_err(|e| forward_error(format!("Invalid response from running instance: {}", e)))?;
+
+        match response.get("error") {
+            Some(error) => Err(forward_error(
+                error.get("message").and_then(Value::as_str).unwrap_or("Unknown error").to_string(),
+            )),
+            None => Ok(response.get("result").cloned().unwrap_or(Value::Null)),
+        }
+    }
+}
+
+fn forward_error(message: String) -> AppError {
+    AppError::new("INSTANCE_FORWARD_ERROR", &message)
+}
+
+#[cfg(unix)]
+fn connect_control_endpoint() -> std::io::Result<ControlStream> {
+    let endpoint = get_control_endpoint().map_err(|e| std::io::Error::other(e.message))?;
+    std::os::unix::net::UnixStream::connect(endpoint)
+}
+
+#[cfg(windows)]
+fn connect_control_endpoint() -> std::io::Result<ControlStream> {
+    let endpoint = get_control_endpoint().map_err(|e| std::io::Error::other(e.message))?;
+    fs::OpenOptions::new().read(true).write(true).open(endpoint)
+}
+
+#[cfg(unix)]
+type ControlStream = std::os::unix::net::UnixStream;
+
+#[cfg(windows)]
+type ControlStream = File;
+
+/**
+ * What a set of launcher arguments asks for
+ */
+#[derive(Debug, Default, PartialEq)]
+struct InstanceArgs {
+    /// Apps to start, by ID or name
+    starts: Vec<String>,
+    /// Apps to stop, by ID or name
+    stops: Vec<String>,
+    /// oddlauncher:// links, handled before any starts or stops
+    links: Vec<String>,
+    /// Profile to start the apps with
+    profile: Option<String>,
+}
+
+/// Collect the `--start`/`--stop`/`--profile` values (as `--flag value` or `--flag=value`) and deep links
+fn parse_instance_args(args: &[String]) -> InstanceArgs {
+    let mut parsed = InstanceArgs::default();
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        // On Windows and Linux, opening an oddlauncher:// link launches the app with the link as an argument
+        if is_deep_link(arg) {
+            parsed.links.push(arg.clone());
+            continue;
+        }
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let target = match flag {
+            START_FLAG => &mut parsed.starts,
+            STOP_FLAG => &mut parsed.stops,
+            PROFILE_FLAG => {
+                parsed.profile = inline_value.or_else(|| iter.next().cloned());
+                continue;
+            }
+            _ => continue,
+        };
+        if let Some(value) = inline_value.or_else(|| iter.next().cloned()) {
+            target.push(value);
+        }
+    }
+
+    parsed
+}
+
+/// Act on launcher arguments: bring the window forward, follow deep links, then stop and start the requested apps
+///
+/// Used for the primary instance's own arguments and for ones forwarded by later launches.
+pub(crate) async fn handle_instance_args(app: &AppHandle, args: &[String]) -> Value {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+
+    let InstanceArgs { starts, stops, links, profile } = parse_instance_args(args);
+
+    let mut results = Vec::new();
+    for link in &links {
+        results.push(handle_deep_link(app, link).await);
+    }
+
+    let config = match read_global_config() {
+        Ok(config) => config,
+        Err(e) => return serde_json::json!([{ "success": false, "error": e.message }]),
+    };
+
+    for (reference, start) in stops.iter().map(|r| (r, false)).chain(starts.iter().map(|r| (r, true))) {
+        let app_id = match find_app(&config, reference) {
+            Ok(found) => found.id.clone(),
+            Err(e) => {
+                log::warn!("{}", e.message);
+                results.push(serde_json::json!({ "app": reference, "success": false, "error": e.message }));
+                continue;
+            }
+        };
+
+        let outcome = if start {
+            start_saved_app(app, &app_id, profile.clone()).await
+        } else {
+            stop_app_process(app_id.clone(), app.clone(), app.state::<ProcessManager>()).await
+        };
+
+        let entry = match outcome {
+            Ok(result) => serde_json::json!({
+                "app": reference,
+                "appId": app_id,
+                "action": if start { "start" } else { "stop" },
+                "success": result.success,
+                "message": result.message
+            }),
+            Err(e) => serde_json::json!({ "app": reference, "appId": app_id, "success": false, "error": e }),
+        };
+        results.push(entry);
+    }
+
+    Value::Array(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> InstanceArgs {
+        parse_instance_args(&args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>())
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn flag_values() {
+        let parsed = parse(&["--start", "api", "--stop=worker", "--profile", "staging"]);
+        assert_eq!(parsed.starts, strings(&["api"]));
+        assert_eq!(parsed.stops, strings(&["worker"]));
+        assert_eq!(parsed.profile.as_deref(), Some("staging"));
+
+        assert_eq!(parse(&["--start=api", "--profile=dev"]).profile.as_deref(), Some("dev"));
+    }
+
+    #[test]
+    fn repeated_flags() {
+        let parsed = parse(&["--start", "api", "--start=web", "--stop", "db", "--stop", "cache"]);
+        assert_eq!(parsed.starts, strings(&["api", "web"]));
+        assert_eq!(parsed.stops, strings(&["db", "cache"]));
+        assert_eq!(parse(&["--profile", "a", "--profile", "b"]).profile.as_deref(), Some("b"));
+    }
+
+    #[test]
+    fn deep_links() {
+        let parsed = parse(&["oddlauncher://start/api", "--start", "web", "--unknown", "oddlauncher://stop/db"]);
+        assert_eq!(parsed.links, strings(&["oddlauncher://start/api", "oddlauncher://stop/db"]));
+        assert_eq!(parsed.starts, strings(&["web"]));
+        assert!(parsed.stops.is_empty());
+    }
+
+    #[test]
+    fn missing_values() {
+        assert_eq!(parse(&["--start"]), InstanceArgs::default());
+        assert_eq!(parse(&[]), InstanceArgs::default());
+    }
+}
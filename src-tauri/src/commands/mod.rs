@@ -1,12 +1,41 @@
 pub mod config;
+pub mod config_format;
+pub mod backup;
+pub mod history;
 pub mod dialog;
 pub mod process;
 pub mod browser;
 pub mod terminal;
+pub mod shell_quote;
+pub mod shell_env;
+pub mod version_managers;
+pub mod dev_env;
+pub mod hooks;
+pub mod environment;
+pub mod secrets;
+pub mod validation;
+pub mod templates;
+pub mod control;
+pub mod instance;
+pub mod deep_link;
 
 // Re-export all commands for easy access
 pub use config::*;
+pub use config_format::*;
+pub use backup::*;
+pub use history::*;
 pub use dialog::*;
 pub use process::*;
 pub use browser::*;
 pub use terminal::*;
+pub use shell_quote::*;
+pub use shell_env::*;
+pub use version_managers::*;
+pub use dev_env::*;
+pub use environment::*;
+pub use secrets::*;
+pub use validation::*;
+pub use templates::*;
+pub use control::*;
+pub use instance::*;
+pub use deep_link::*;
@@ -1,13 +1,27 @@
-use crate::models::app::{AppProcess, AppStatus};
-use crate::commands::terminal::get_terminal_command;
+use crate::models::app::{AppError, AppProcess, AppStatus, CustomShell, GlobalConfig, LaunchMode, LaunchStep};
+use crate::commands::config::read_global_config;
+use crate::commands::dev_env::wrap_in_dev_environment;
+use crate::commands::environment::{resolve_launch_fields, LaunchFields};
+use crate::commands::hooks::{hook_timeout, run_hooks, EventHookReporter, HookEnvironment, HookStage};
+use crate::commands::secrets::{known_secret_values, redact, redact_json, resolve_app_secrets, SecretStore};
+use crate::commands::shell_env::ShellEnvironment;
+use crate::commands::shell_quote::ShellDialect;
+use crate::commands::terminal::{
+    find_custom_shell, get_custom_shell_command, get_custom_shell_script, get_terminal_command, get_terminal_script,
+    get_terminal_script_command, step_start, WslTarget, STEP_MARKER, TERMINAL_TYPES,
+};
+use crate::commands::version_managers::get_version_manager_init;
 use serde::{Deserialize, Serialize};
 use serde_json;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::{Arc, Mutex};
-use tauri::{AppHandle, Emitter, State};
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command as TokioCommand;
+use tokio::sync::watch;
 
 #[cfg(unix)]
 #[allow(unused_imports)]
@@ -24,7 +38,7 @@ use std::os::windows::process::CommandExt;
 /**
  * Cross-platform path and command utilities
  */
-mod platform_utils {
+pub(crate) mod platform_utils {
     use std::path::Path;
 
     /// Convert various path formats to the appropriate format for the current platform
@@ -32,7 +46,7 @@ mod platform_utils {
         log::info!("Normalizing path: '{}'", path);
 
         // Handle WSL network paths from Windows
-        if path.starts_with("\\\\wsl.localhost\\") || path.starts_with("//wsl.localhost/") {
+        if wsl_network_distro(path).is_some() {
             return convert_wsl_network_path(path);
         }
 
@@ -58,34 +72,34 @@ mod platform_utils {
         Ok(normalized)
     }
 
-    /// Convert WSL network paths to appropriate format
-    fn convert_wsl_network_path(path: &str) -> Result<String, String> {
-        log::info!("Converting WSL network path: '{}'", path);
-
-        // Remove the network prefix
-        let cleaned = path
-            .replace("\\\\wsl.localhost\\", "")
-            .replace("//wsl.localhost/", "");
-
-        let parts: Vec<&str> = cleaned.split(&['\\', '/'][..]).collect();
+    /// Prefixes Windows uses for WSL file systems; `wsl$` is the name before Windows 11
+    const WSL_NETWORK_PREFIXES: [&str; 4] = ["\\\\wsl.localhost\\", "//wsl.localhost/", "\\\\wsl$\\", "//wsl$/"];
+
+    /// The distribution and path inside it of a `\\wsl.localhost\<distro>\...` path
+    pub(super) fn split_wsl_network_path(path: &str) -> Option<(&str, &str)> {
+        let rest = WSL_NETWORK_PREFIXES.iter().find_map(|prefix| {
+            let head = path.get(..prefix.len())?;
+            head.eq_ignore_ascii_case(prefix).then(|| &path[prefix.len()..])
+        })?;
+        let (distro, inner) = rest.split_once(['\\', '/']).unwrap_or((rest, ""));
+        (!distro.is_empty()).then_some((distro, inner))
+    }
 
-        if parts.len() < 2 {
-            return Err(format!("Invalid WSL path format: {}", path));
-        }
+    /// The WSL distribution a `\\wsl.localhost\<distro>\...` or `\\wsl$\<distro>\...` path is in
+    pub fn wsl_network_distro(path: &str) -> Option<String> {
+        split_wsl_network_path(path).map(|(distro, _)| distro.to_string())
+    }
 
-        // Skip the distro name (e.g., Ubuntu) and convert to Unix path
-        let unix_path = format!("/{}", parts[1..].join("/"));
+    /// Convert WSL network paths to the path inside the distribution
+    pub(super) fn convert_wsl_network_path(path: &str) -> Result<String, String> {
+        log::info!("Converting WSL network path: '{}'", path);
 
-        let result = if cfg!(target_os = "windows") {
-            // On Windows, we might need to use wsl.exe to execute commands
-            unix_path
-        } else {
-            // On Linux/Unix, use the path directly
-            unix_path
-        };
+        let (_, inner) = split_wsl_network_path(path).ok_or_else(|| format!("Invalid WSL path format: {}", path))?;
+        let parts: Vec<&str> = inner.split(['\\', '/']).filter(|part| !part.is_empty()).collect();
+        let unix_path = format!("/{}", parts.join("/"));
 
-        log::info!("Converted WSL path: '{}' -> '{}'", path, result);
-        Ok(result)
+        log::info!("Converted WSL path: '{}' -> '{}'", path, unix_path);
+        Ok(unix_path)
     }
 
     /// Prepare command for cross-platform execution
@@ -140,7 +154,7 @@ mod platform_utils {
     pub fn validate_directory(path: &str) -> Result<String, String> {
         let normalized = normalize_path(path)?;
 
-        if cfg!(target_os = "windows") && path.starts_with('/') {
+        if cfg!(target_os = "windows") && (path.starts_with('/') || wsl_network_distro(path).is_some()) {
             // On Windows, Unix-style paths might be WSL paths
             // We'll validate them differently
             log::info!("Windows detected with Unix path - assuming WSL path: {}", normalized);
@@ -157,14 +171,105 @@ mod platform_utils {
 
         Ok(normalized)
     }
+
+    /// Identify when a process started, so a recorded PID can be told apart from a later reuse
+    ///
+    /// Returns None if the process doesn't exist or its start time can't be read.
+    pub fn process_start_time(pid: u32) -> Option<String> {
+        #[cfg(target_os = "linux")]
+        {
+            // Field 22 of /proc/<pid>/stat is the start time in clock ticks since boot;
+            // the boot ID makes it unique across reboots. The command name (field 2) may contain
+            // spaces and parentheses, so fields are counted from the last ')'.
+            let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+            let start_ticks = stat.rsplit_once(')')?.1.split_whitespace().nth(19)?.to_string();
+            let boot_id = std::fs::read_to_string("/proc/sys/kernel/random/boot_id").unwrap_or_default();
+            Some(format!("{}:{}", boot_id.trim(), start_ticks))
+        }
+
+        #[cfg(all(unix, not(target_os = "linux")))]
+        {
+            let output = std::process::Command::new("ps")
+                .args(["-o", "lstart=", "-p", &pid.to_string()])
+                .output()
+                .ok()?;
+            let started = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            (output.status.success() && !started.is_empty()).then_some(started)
+        }
+
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            let output = std::process::Command::new("powershell.exe")
+                .args([
+                    "-NoProfile",
+                    "-NonInteractive",
+                    "-Command",
+                    &format!("(Get-Process -Id {}).StartTime.ToUniversalTime().ToString('o')", pid),
+                ])
+                .creation_flags(CREATE_NO_WINDOW)
+                .output()
+                .ok()?;
+            let started = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            (output.status.success() && !started.is_empty()).then_some(started)
+        }
+    }
+}
+
+/// Prepare launch commands for execution as the app's launch mode asks
+///
+/// Script mode also returns the script file, which must outlive the process.
+pub(crate) fn prepare_launch(
+    app_id: &str,
+    launch_commands: &str,
+    working_dir: Option<&str>,
+    terminal_type: Option<&str>,
+    launch_mode: LaunchMode,
+    custom_shells: &[CustomShell],
+    wsl_target: &WslTarget,
+) -> Result<(String, Vec<String>, Option<LaunchScript>), String> {
+    // An empty terminal type has always meant bash, but anything else has to be a known shell
+    if let Some(term_type) = terminal_type {
+        let known = term_type.is_empty()
+            || TERMINAL_TYPES.contains(&term_type)
+            || find_custom_shell(custom_shells, term_type).is_some();
+        if !known {
+            return Err(format!("Unknown terminal type '{}'", term_type));
+        }
+    }
+
+    let (program, mut args, script) = match launch_mode {
+        LaunchMode::Script => prepare_script_execution(app_id, launch_commands, working_dir, terminal_type, custom_shells)
+            .map(|(program, args, script)| (program, args, Some(script)))?,
+        LaunchMode::Inline => prepare_multi_command_execution(launch_commands, working_dir, terminal_type, custom_shells)
+            .map(|(program, args)| (program, args, None))?,
+    };
+
+    // Select the app's distribution and user however the launch ended up going through WSL
+    if program == "wsl.exe" {
+        args.splice(0..0, wsl_target.args());
+    }
+    Ok((program, args, script))
 }
 
 /// Prepare multi-command execution using shell script approach
-fn prepare_multi_command_execution(launch_commands: &str, working_dir: Option<&str>, terminal_type: Option<&str>) -> Result<(String, Vec<String>), String> {
+fn prepare_multi_command_execution(
+    launch_commands: &str,
+    working_dir: Option<&str>,
+    terminal_type: Option<&str>,
+    custom_shells: &[CustomShell],
+) -> Result<(String, Vec<String>), String> {
     log::info!("Preparing multi-command execution: '{}'", launch_commands);
 
     // If terminal_type is specified, use the new terminal command system
     if let Some(term_type) = terminal_type {
+        if let Some(shell) = find_custom_shell(custom_shells, term_type) {
+            log::info!("Using custom shell: {}", shell.name);
+            let command_args = get_custom_shell_command(shell, launch_commands);
+            return Ok((command_args[0].clone(), command_args[1..].to_vec()));
+        }
+
         log::info!("Using terminal type: {}", term_type);
         let command_args = get_terminal_command(term_type, launch_commands, working_dir);
         if command_args.len() >= 2 {
@@ -208,6 +313,104 @@ fn prepare_multi_command_execution(launch_commands: &str, working_dir: Option<&s
     Ok(shell_script)
 }
 
+/**
+ * Launch script written for a process; the file is removed when this is dropped
+ */
+pub(crate) struct LaunchScript {
+    path: PathBuf,
+}
+
+impl LaunchScript {
+    /// Leave the file in place, for processes that outlive whoever started them
+    pub(crate) fn keep(self) -> PathBuf {
+        let path = self.path.clone();
+        std::mem::forget(self);
+        path
+    }
+}
+
+impl Drop for LaunchScript {
+    fn drop(&mut self) {
+        remove_launch_script(&self.path);
+    }
+}
+
+/// Delete a launch script once its process is done with it
+pub(crate) fn remove_launch_script(path: &Path) {
+    match std::fs::remove_file(path) {
+        Ok(()) => log::debug!("Removed launch script {}", path.display()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => log::warn!("Failed to remove launch script {}: {}", path.display(), e),
+    }
+}
+
+/// Terminal type for apps that don't set one, matching the legacy behavior:
+/// WSL for Linux paths on Windows, otherwise the platform shell
+pub(crate) fn default_terminal_type(working_dir: Option<&str>) -> &'static str {
+    if cfg!(target_os = "windows") {
+        let use_wsl = working_dir.is_some_and(|dir| dir.starts_with('/') || dir.contains("wsl.localhost"));
+        if use_wsl { "wsl" } else { "cmd" }
+    } else {
+        "bash"
+    }
+}
+
+/// Prepare execution of launch commands written out as a script file for the terminal type
+pub(crate) fn prepare_script_execution(
+    app_id: &str,
+    launch_commands: &str,
+    working_dir: Option<&str>,
+    terminal_type: Option<&str>,
+    custom_shells: &[CustomShell],
+) -> Result<(String, Vec<String>, LaunchScript), String> {
+    let terminal_type = terminal_type.unwrap_or_else(|| default_terminal_type(working_dir));
+    log::info!("Preparing launch script for terminal type: {}", terminal_type);
+
+    let custom_shell = find_custom_shell(custom_shells, terminal_type);
+    let script = match custom_shell {
+        Some(_) => get_custom_shell_script(launch_commands),
+        None => get_terminal_script(terminal_type, launch_commands, working_dir),
+    };
+
+    let script_dir = std::env::temp_dir().join("oddlauncher-scripts");
+    std::fs::create_dir_all(&script_dir)
+        .map_err(|e| format!("Failed to create {}: {}", script_dir.display(), e))?;
+
+    // App IDs come from config files, so keep only characters that are safe in a file name
+    let safe_id: String = app_id
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    let path = script_dir.join(format!(
+        "{}-{}.{}",
+        safe_id,
+        &uuid::Uuid::new_v4().simple().to_string()[..8],
+        script.extension
+    ));
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(&path)
+        .map_err(|e| format!("Failed to create launch script {}: {}", path.display(), e))?;
+    let launch_script = LaunchScript { path };
+    std::io::Write::write_all(&mut file, script.content.as_bytes())
+        .map_err(|e| format!("Failed to write launch script {}: {}", launch_script.path.display(), e))?;
+
+    let script_path = launch_script.path.to_string_lossy().to_string();
+    let command = match custom_shell {
+        Some(shell) => vec![shell.executable.clone(), script_path],
+        None => get_terminal_script_command(terminal_type, &script_path),
+    };
+    log::info!("Launch script written to {}", launch_script.path.display());
+    Ok((command[0].clone(), command[1..].to_vec(), launch_script))
+}
+
 /// Prepare multi-command execution for Windows
 fn prepare_windows_multi_command(commands: &[&str], working_dir: Option<&str>) -> Result<(String, Vec<String>), String> {
     // Check if we should use WSL - only if working directory indicates WSL usage
@@ -267,15 +470,16 @@ fn prepare_windows_multi_command(commands: &[&str], working_dir: Option<&str>) -
         if let Some(dir) = working_dir {
             let normalized_dir = platform_utils::normalize_path(dir)?;
             if normalized_dir.starts_with('/') {
-                script_lines.push(format!("echo \"OddLauncher: Changing to directory: {}\"", normalized_dir));
-                script_lines.push(format!("cd '{}'", normalized_dir));
+                script_lines.push(ShellDialect::Posix.echo(&format!("OddLauncher: Changing to directory: {}", normalized_dir)));
+                script_lines.push(format!("cd {}", ShellDialect::Posix.quote(&normalized_dir)));
                 script_lines.push("".to_string());
             }
         }
 
         // Add all commands with terminal-like display
         for (i, command) in commands.iter().enumerate() {
-            script_lines.push(format!("echo '$ {}'", command));
+            script_lines.push(step_start(ShellDialect::Posix, i));
+            script_lines.push(ShellDialect::Posix.echo(&format!("$ {}", command)));
             script_lines.push(command.to_string());
             if i < commands.len() - 1 {
                 script_lines.push("".to_string());
@@ -300,11 +504,12 @@ fn prepare_windows_multi_command(commands: &[&str], working_dir: Option<&str>) -
         // Add working directory change if specified
         if let Some(dir) = working_dir {
             let normalized_dir = platform_utils::normalize_path(dir)?;
-            script_lines.push(format!("cd /d \"{}\"", normalized_dir));
+            script_lines.push(format!("cd /d {}", ShellDialect::Cmd.quote(&normalized_dir)));
         }
 
         // Add error handling and commands
-        for command in commands {
+        for (i, command) in commands.iter().enumerate() {
+            script_lines.push(step_start(ShellDialect::Cmd, i));
             script_lines.push(command.to_string());
             script_lines.push("if !errorlevel! neq 0 exit /b !errorlevel!".to_string());
         }
@@ -322,48 +527,20 @@ fn prepare_unix_multi_command(commands: &[&str], working_dir: Option<&str>) -> R
     // Add initial logging to show what we're executing
     script_lines.push("echo \"OddLauncher: Starting app process...\"".to_string());
 
-    // Check if any commands use nvm, rbenv, or other version managers that need shell initialization
-    let needs_nvm = commands.iter().any(|cmd| {
-        let first_word = cmd.trim().split_whitespace().next().unwrap_or("");
-        first_word == "nvm" || cmd.contains("nvm ")
-    });
-
-    let needs_rbenv = commands.iter().any(|cmd| {
-        let first_word = cmd.trim().split_whitespace().next().unwrap_or("");
-        first_word == "rbenv" || cmd.contains("rbenv ")
-    });
-
-    // Add shell initialization for version managers if needed
-    if needs_nvm {
-        // Source nvm if available - try common locations
-        script_lines.push("# Initialize nvm if available".to_string());
-        script_lines.push("if [ -f ~/.nvm/nvm.sh ]; then".to_string());
-        script_lines.push("  source ~/.nvm/nvm.sh".to_string());
-        script_lines.push("elif [ -f /usr/local/share/nvm/nvm.sh ]; then".to_string());
-        script_lines.push("  source /usr/local/share/nvm/nvm.sh".to_string());
-        script_lines.push("elif [ -f /opt/homebrew/opt/nvm/nvm.sh ]; then".to_string());
-        script_lines.push("  source /opt/homebrew/opt/nvm/nvm.sh".to_string());
-        script_lines.push("fi".to_string());
-    }
-
-    if needs_rbenv {
-        // Initialize rbenv if available
-        script_lines.push("# Initialize rbenv if available".to_string());
-        script_lines.push("if command -v rbenv >/dev/null 2>&1; then".to_string());
-        script_lines.push("  eval \"$(rbenv init -)\"".to_string());
-        script_lines.push("fi".to_string());
-    }
-
     // Add working directory change if specified
-    if let Some(dir) = working_dir {
-        let normalized_dir = platform_utils::normalize_path(dir)?;
-    script_lines.push(format!("echo \"OddLauncher: Changing to working directory: {}\"", normalized_dir));
-        script_lines.push(format!("cd '{}'", normalized_dir));
+    let normalized_dir = working_dir.map(platform_utils::normalize_path).transpose()?;
+    if let Some(dir) = &normalized_dir {
+        script_lines.push(ShellDialect::Posix.echo(&format!("OddLauncher: Changing to working directory: {}", dir)));
+        script_lines.push(format!("cd {}", ShellDialect::Posix.quote(dir)));
     }
 
+    // Initialize version managers in the directory holding their version files
+    script_lines.extend(get_version_manager_init("bash", normalized_dir.as_deref(), &commands.join("\n")));
+
     // Add command execution with logging
     for (i, command) in commands.iter().enumerate() {
-    script_lines.push(format!("echo \"OddLauncher: Executing command {}: {}\"", i + 1, command));
+        script_lines.push(step_start(ShellDialect::Posix, i));
+        script_lines.push(ShellDialect::Posix.echo(&format!("OddLauncher: Executing command {}: {}", i + 1, command)));
         script_lines.push(command.to_string());
     }
 
@@ -378,6 +555,22 @@ fn prepare_unix_multi_command(commands: &[&str], working_dir: Option<&str>) -> R
  */
 pub struct ProcessManager {
     pub processes: Arc<Mutex<HashMap<String, ProcessInfo>>>,
+    /// Apps being started (running their pre-launch hooks), with the signal that cancels the launch
+    pub launching: Arc<Mutex<HashMap<String, watch::Sender<bool>>>>,
+}
+
+/**
+ * Marks an app as being started for as long as it's held
+ */
+struct LaunchGuard {
+    launching: Arc<Mutex<HashMap<String, watch::Sender<bool>>>>,
+    app_id: String,
+}
+
+impl Drop for LaunchGuard {
+    fn drop(&mut self) {
+        self.launching.lock().unwrap().remove(&self.app_id);
+    }
 }
 
 /**
@@ -390,6 +583,150 @@ pub struct ProcessInfo {
     // On Unix, this is the process group id (pgid) that we assign to the child.
     // On Windows, this will be None.
     pub pgid: Option<i32>,
+    /// Environment profile the process was started with
+    pub profile: Option<String>,
+    /// Launch command the process is running, from the step markers in its output
+    pub current_step: Option<LaunchStep>,
+}
+
+/**
+ * A step marker printed by the launch shell
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum StepMarker {
+    Start(usize),
+    End(usize, i32),
+}
+
+/// Parse a line of output printed by `step_start` or the PowerShell end marker
+///
+/// Markers are printed at the start of a line and nothing follows them, so other output
+/// that merely begins with `STEP_MARKER` isn't taken for one.
+pub(crate) fn parse_step_marker(line: &str) -> Option<StepMarker> {
+    let mut words = line.trim_end().strip_prefix(STEP_MARKER)?.strip_prefix(' ')?.split(' ');
+    let marker = match (words.next()?, words.next()?.parse().ok()?) {
+        ("start", index) => StepMarker::Start(index),
+        ("end", index) => StepMarker::End(index, words.next()?.parse().ok()?),
+        _ => return None,
+    };
+    words.next().is_none().then_some(marker)
+}
+
+/**
+ * Where step progress goes: app events and the process's current step
+ */
+trait StepReporter: Send {
+    fn started(&mut self, step: &LaunchStep);
+    fn finished(&mut self, step: &LaunchStep, exit_code: Option<i32>, duration_ms: u64);
+}
+
+struct AppStepReporter {
+    app_id: String,
+    app_handle: AppHandle,
+    processes: Arc<Mutex<HashMap<String, ProcessInfo>>>,
+}
+
+impl StepReporter for AppStepReporter {
+    fn started(&mut self, step: &LaunchStep) {
+        if let Some(process) = self.processes.lock().unwrap().get_mut(&self.app_id) {
+            process.current_step = Some(step.clone());
+        }
+        let _ = self.app_handle.emit("launch-step-started", serde_json::json!({
+            "appId": self.app_id,
+            "index": step.index,
+            "total": step.total,
+            "command": step.command,
+            "timestamp": step.started_at
+        }));
+    }
+
+    fn finished(&mut self, step: &LaunchStep, exit_code: Option<i32>, duration_ms: u64) {
+        if let Some(process) = self.processes.lock().unwrap().get_mut(&self.app_id) {
+            process.current_step = None;
+        }
+        let _ = self.app_handle.emit("launch-step-finished", serde_json::json!({
+            "appId": self.app_id,
+            "index": step.index,
+            "total": step.total,
+            "command": step.command,
+            "exitCode": exit_code,
+            "durationMs": duration_ms,
+            "timestamp": chrono::Utc::now().to_rfc3339()
+        }));
+    }
+}
+
+/**
+ * Follows a launch through its step markers, reporting each launch command as it starts and finishes
+ *
+ * Only inline launches print markers; script mode and custom shells run as one unmarked step,
+ * so the tracker never sees a marker for them.
+ */
+struct StepTracker<R: StepReporter> {
+    app_id: String,
+    commands: Vec<String>,
+    /// Index of the step the shell prints a start marker for next
+    next: usize,
+    current: Option<(LaunchStep, Instant)>,
+    reporter: R,
+}
+
+impl<R: StepReporter> StepTracker<R> {
+    fn new(app_id: String, commands: Vec<String>, reporter: R) -> Self {
+        StepTracker { app_id, commands, next: 0, current: None, reporter }
+    }
+
+    /// Follow a marker, returning false if it isn't one the launch would print at this point
+    ///
+    /// Steps start in order, so a program printing marker-like output can't skip ahead or end a step.
+    fn handle(&mut self, marker: StepMarker) -> bool {
+        match marker {
+            StepMarker::Start(index) if index == self.next && index < self.commands.len() => {
+                // Shells only move on to the next command once the previous one succeeded,
+                // except PowerShell, which reports how each one ended itself
+                self.finish(Some(0));
+                self.start(index);
+                true
+            }
+            StepMarker::End(index, exit_code) if self.current.as_ref().is_some_and(|(step, _)| step.index == index) => {
+                self.finish(Some(exit_code));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn start(&mut self, index: usize) {
+        let step = LaunchStep {
+            index,
+            total: self.commands.len(),
+            command: self.commands[index].clone(),
+            started_at: chrono::Utc::now().to_rfc3339(),
+        };
+        log::info!("App {} started step {}/{}: {}", self.app_id, index + 1, step.total, step.command);
+
+        self.reporter.started(&step);
+        self.next = index + 1;
+        self.current = Some((step, Instant::now()));
+    }
+
+    /// Finish the current step, if there is one, with the exit code it ended with
+    fn finish(&mut self, exit_code: Option<i32>) {
+        let Some((step, started)) = self.current.take() else {
+            return;
+        };
+        let duration_ms = started.elapsed().as_millis() as u64;
+        log::info!(
+            "App {} finished step {}/{} with exit code {:?} after {}ms",
+            self.app_id,
+            step.index + 1,
+            step.total,
+            exit_code,
+            duration_ms
+        );
+
+        self.reporter.finished(&step, exit_code, duration_ms);
+    }
 }
 
 /**
@@ -408,6 +745,7 @@ impl Default for ProcessManager {
     fn default() -> Self {
         Self {
             processes: Arc::new(Mutex::new(HashMap::new())),
+            launching: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
@@ -427,11 +765,73 @@ pub async fn start_app_process(
     port_to_check: Option<u16>,
     port_check_timeout: Option<u32>,
     terminal_type: Option<String>,
+    profile: Option<String>,
     app_handle: AppHandle,
     process_manager: State<'_, ProcessManager>,
+    secret_store: State<'_, SecretStore>,
+    shell_environment: State<'_, ShellEnvironment>,
 ) -> Result<ProcessResult, String> {
     log::info!("Starting process for app: {}", app_id);
 
+    // Settings-level options (global variables, env files, profiles) come from the stored configuration
+    let global_config = match read_global_config() {
+        Ok(config) => config,
+        Err(e) => {
+            log::warn!("Could not load stored configuration for app {}: {}", app_id, e.message);
+            GlobalConfig::default()
+        }
+    };
+    let stored_app = global_config.apps.iter().find(|a| a.id == app_id);
+
+    let profile = match stored_app {
+        Some(app) => app.resolve_profile(profile.as_deref()).map(|p| p.cloned()),
+        None if profile.is_some() => Err(AppError::new(
+            "APP_NOT_FOUND_ERROR",
+            &format!("Cannot apply profile: app with ID '{}' is not saved", app_id),
+        )),
+        None => Ok(None),
+    };
+
+    // Apply the profile, merge env files and resolve ${VAR} references before anything is spawned or opened
+    let resolved = profile.and_then(|profile| {
+        let launch_fields = LaunchFields {
+            launch_commands,
+            working_directory,
+            url,
+            environment_variables,
+            env_files: stored_app.and_then(|a| a.env_files.clone()).unwrap_or_default(),
+            port_to_check,
+            profile,
+        };
+        resolve_launch_fields(&app_id, &launch_fields, &global_config.settings.variables)
+    });
+    let LaunchFields {
+        launch_commands,
+        working_directory,
+        url,
+        environment_variables,
+        port_to_check,
+        profile,
+        ..
+    } = match resolved {
+        Ok(resolved) => resolved.fields,
+        Err(e) => {
+            log::error!("{}", e.message);
+
+            let _ = app_handle.emit("process-error", serde_json::json!({
+                "appId": app_id,
+                "error": e.message
+            }));
+
+            return Ok(ProcessResult {
+                success: false,
+                message: e.message.clone(),
+                pid: None,
+                error: Some(e.message),
+            });
+        }
+    };
+
     // Check if this is a bookmark app (no launch commands)
     let is_bookmark = launch_commands.as_ref().map_or(true, |cmd| cmd.trim().is_empty());
 
@@ -490,10 +890,11 @@ pub async fn start_app_process(
         log::info!("Environment variables: {:?}", env_vars);
     }
 
-    // Check if process is already running
-    {
+    // Check if process is already running, and mark it as starting until it is
+    let (cancel_launch, _launch_guard) = {
+        let mut launching = process_manager.launching.lock().unwrap();
         let processes = process_manager.processes.lock().unwrap();
-        if processes.contains_key(&app_id) {
+        if processes.contains_key(&app_id) || launching.contains_key(&app_id) {
             return Ok(ProcessResult {
                 success: false,
                 message: "Process is already running".to_string(),
@@ -501,6 +902,23 @@ pub async fn start_app_process(
                 error: Some("Process already exists".to_string()),
             });
         }
+        let (sender, receiver) = watch::channel(false);
+        launching.insert(app_id.clone(), sender);
+        let guard = LaunchGuard {
+            launching: Arc::clone(&process_manager.launching),
+            app_id: app_id.clone(),
+        };
+        (receiver, guard)
+    };
+
+    // Apps started from the command line aren't tracked by the process manager
+    if let Some(pid) = crate::cli::running_pid(&app_id) {
+        return Ok(ProcessResult {
+            success: false,
+            message: format!("Process is already running from the command line (pid {})", pid),
+            pid: Some(pid),
+            error: Some("Process already exists".to_string()),
+        });
     }
 
     // Normalize working directory using cross-platform utilities
@@ -542,11 +960,35 @@ pub async fn start_app_process(
         None
     };
 
-    // Prepare multi-command execution using shell script approach
-    let (program, args) = match prepare_multi_command_execution(&launch_commands, normalized_working_dir.as_deref(), terminal_type.as_deref()) {
-        Ok((prog, args)) => {
+    // Start from the login shell's environment so PATH additions from shell profiles apply
+    let login_environment = if stored_app.and_then(|a| a.use_login_shell_env).unwrap_or(true) {
+        shell_environment.variables().await
+    } else {
+        HashMap::new()
+    };
+
+    // Prepare multi-command execution using shell script approach, or a script file in script mode,
+    // then wrap it in the project's development environment if the app uses one
+    let launch_mode = stored_app.and_then(|a| a.launch_mode).unwrap_or_default();
+    let dev_environment = stored_app.and_then(|a| a.dev_environment);
+    let wsl_target = WslTarget::for_app(
+        stored_app.and_then(|a| a.wsl_distro.as_deref()),
+        stored_app.and_then(|a| a.wsl_user.as_deref()),
+        working_directory.as_deref(),
+    );
+    let prepared = prepare_launch(&app_id, &launch_commands, normalized_working_dir.as_deref(), terminal_type.as_deref(), launch_mode, &global_config.settings.custom_shells, &wsl_target);
+    let prepared = match (prepared, dev_environment) {
+        (Ok((prog, args, script)), Some(kind)) => {
+            wrap_in_dev_environment(kind, prog, args, normalized_working_dir.as_deref(), login_environment.get("PATH").map(String::as_str))
+                .await
+                .map(|(prog, args)| (prog, args, script))
+        }
+        (prepared, _) => prepared,
+    };
+    let (program, args, launch_script) = match prepared {
+        Ok((prog, args, script)) => {
             log::info!("Multi-command execution prepared - Program: '{}', Args: {:?}", prog, args);
-            (prog, args)
+            (prog, args, script)
         },
         Err(e) => {
             let error_msg = format!("Failed to prepare launch commands: {}", e);
@@ -569,8 +1011,20 @@ pub async fn start_app_process(
 
     // Create the command
     let mut cmd = TokioCommand::new(&program);
-    cmd.args(&args)
-        .stdout(Stdio::piped())
+    // cmd.exe doesn't follow the usual argument quoting rules, and its script is already escaped for it
+    #[cfg(windows)]
+    {
+        if program == "cmd.exe" {
+            for arg in &args {
+                cmd.raw_arg(arg);
+            }
+        } else {
+            cmd.args(&args);
+        }
+    }
+    #[cfg(not(windows))]
+    cmd.args(&args);
+    cmd.stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .stdin(Stdio::null());
 
@@ -632,6 +1086,8 @@ pub async fn start_app_process(
         }
     }
 
+    cmd.envs(&login_environment);
+
     // Set environment variables if provided
     if let Some(env_vars) = &environment_variables {
         for (key, value) in env_vars {
@@ -640,6 +1096,99 @@ pub async fn start_app_process(
         }
     }
 
+    // Secrets are only decrypted here, at spawn time, and never logged
+    let secret_variables = stored_app
+        .and_then(|a| a.secret_variables.clone())
+        .unwrap_or_default();
+    let secrets = match resolve_app_secrets(&secret_store, &secret_variables) {
+        Ok(secrets) => secrets,
+        Err(e) => {
+            let error_msg = format!("Failed to load secrets: {}", e.message);
+            log::error!("{}", error_msg);
+
+            // Emit process error event
+            let _ = app_handle.emit("process-error", serde_json::json!({
+                "appId": app_id,
+                "error": error_msg
+            }));
+
+            return Ok(ProcessResult {
+                success: false,
+                message: error_msg.clone(),
+                pid: None,
+                error: Some(error_msg),
+            });
+        }
+    };
+    for (key, value) in &secrets {
+        log::debug!("Setting secret env var: {}", key);
+        cmd.env(key, value);
+    }
+    let secret_values: Vec<String> = secrets.iter().map(|(_, value)| value.clone()).collect();
+
+    // Hooks run in the same directory, shell and environment as the launch commands
+    let hook_environment = HookEnvironment {
+        app_id: app_id.clone(),
+        working_dir: normalized_working_dir.clone(),
+        terminal_type: terminal_type.clone(),
+        custom_shells: global_config.settings.custom_shells.clone(),
+        wsl_target: wsl_target.clone(),
+        dev_environment,
+        variables: login_environment
+            .iter()
+            .chain(environment_variables.iter().flatten())
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .chain(secrets)
+            .collect(),
+        secret_values: secret_values.clone(),
+        timeout: hook_timeout(stored_app.and_then(|a| a.hook_timeout)),
+        cancel: Some(cancel_launch.clone()),
+    };
+
+    let pre_launch = stored_app.map(|a| HookStage::PreLaunch.commands(a)).unwrap_or_default();
+    if !pre_launch.is_empty() {
+        let abort_on_failure = stored_app.and_then(|a| a.abort_on_pre_launch_failure).unwrap_or(true);
+        let mut reporter = EventHookReporter {
+            app_handle: app_handle.clone(),
+            app_id: app_id.clone(),
+        };
+        if let Err(failure) = run_hooks(&hook_environment, HookStage::PreLaunch, &pre_launch, abort_on_failure, &mut reporter).await {
+            if *cancel_launch.borrow() {
+                // Stopping the app while its hooks run cancels the launch, which is handled before spawning
+            } else if abort_on_failure {
+                let error_msg = format!("Launch aborted: {}", failure);
+                log::error!("{}", error_msg);
+
+                // Emit process error event
+                let _ = app_handle.emit("process-error", serde_json::json!({
+                    "appId": app_id,
+                    "error": error_msg
+                }));
+
+                return Ok(ProcessResult {
+                    success: false,
+                    message: error_msg.clone(),
+                    pid: None,
+                    error: Some(error_msg),
+                });
+            } else {
+                log::warn!("Launching app {} anyway: {}", app_id, failure);
+            }
+        }
+    }
+    let post_stop = stored_app.map(|a| HookStage::PostStop.commands(a)).unwrap_or_default();
+
+    let cancelled = || ProcessResult {
+        success: false,
+        message: "Launch cancelled".to_string(),
+        pid: None,
+        error: None,
+    };
+    if *cancel_launch.borrow() {
+        log::info!("Launch of app {} cancelled", app_id);
+        return Ok(cancelled());
+    }
+
     log::info!("About to spawn process with command: {} {:?}", program, args);
 
     // Spawn the process
@@ -690,16 +1239,59 @@ pub async fn start_app_process(
 
     log::info!("Process started with PID: {} for app: {}", pid, app_id);
 
+    // Store process info before output arrives, so steps can be recorded against it
+    let active_profile = profile.map(|p| p.name);
+    let process_info = ProcessInfo {
+        pid,
+        started_at: started_at.clone(),
+        pgid,
+        profile: active_profile.clone(),
+        current_step: None,
+    };
+
+    {
+        let mut processes = process_manager.processes.lock().unwrap();
+        processes.insert(app_id.clone(), process_info);
+    }
+
+    // A stop that arrived while the process was being spawned found neither entry to stop
+    if *cancel_launch.borrow() {
+        log::info!("Launch of app {} cancelled, stopping the new process", app_id);
+        process_manager.processes.lock().unwrap().remove(&app_id);
+        if let Err(e) = terminate_process_tree(pid, pgid).await {
+            log::warn!("Failed to stop cancelled launch of app {}: {}", app_id, e);
+        }
+        return Ok(cancelled());
+    }
+
     // Clone handles for async tasks
     let app_handle_clone = app_handle.clone();
     let app_id_clone = app_id.clone();
 
+    // Steps are numbered by the non-empty lines of the launch commands, as the terminal commands number them
+    let step_tracker = Arc::new(Mutex::new(StepTracker::new(
+        app_id.clone(),
+        launch_commands
+            .lines()
+            .map(|line| redact(line.trim(), &secret_values))
+            .filter(|line| !line.is_empty())
+            .collect(),
+        AppStepReporter {
+            app_id: app_id.clone(),
+            app_handle: app_handle.clone(),
+            processes: Arc::clone(&process_manager.processes),
+        },
+    )));
+
     // Handle stdout
+    let mut stdout_task = None;
     if let Some(stdout) = child.stdout.take() {
         let app_handle_stdout = app_handle_clone.clone();
         let app_id_stdout = app_id_clone.clone();
+        let secrets_stdout = secret_values.clone();
+        let step_tracker_stdout = Arc::clone(&step_tracker);
 
-        tokio::spawn(async move {
+        stdout_task = Some(tokio::spawn(async move {
             let mut reader = BufReader::new(stdout);
             let mut line = String::new();
 
@@ -708,7 +1300,7 @@ pub async fn start_app_process(
                     Ok(0) => {
                         // EOF: if there's leftover data without a trailing newline, emit it
                         if !line.is_empty() {
-                            let output_line = line.trim_end().to_string();
+                            let output_line = redact(line.trim_end(), &secrets_stdout);
                             let _ = app_handle_stdout.emit("process-output", serde_json::json!({
                                 "appId": app_id_stdout,
                                 "type": "stdout",
@@ -720,7 +1312,15 @@ pub async fn start_app_process(
                         break;
                     }
                     Ok(_) => {
-                        let output_line = line.trim_end().to_string();
+                        // Step markers are bookkeeping, not output
+                        let is_step = parse_step_marker(&line)
+                            .is_some_and(|marker| step_tracker_stdout.lock().unwrap().handle(marker));
+                        if is_step {
+                            line.clear();
+                            continue;
+                        }
+
+                        let output_line = redact(line.trim_end(), &secrets_stdout);
 
                         // Emit to frontend
                         let _ = app_handle_stdout.emit("process-output", serde_json::json!({
@@ -738,13 +1338,14 @@ pub async fn start_app_process(
                     }
                 }
             }
-        });
+        }));
     }
 
     // Handle stderr
     if let Some(stderr) = child.stderr.take() {
         let app_handle_stderr = app_handle_clone.clone();
         let app_id_stderr = app_id_clone.clone();
+        let secrets_stderr = secret_values.clone();
 
         tokio::spawn(async move {
             let mut reader = BufReader::new(stderr);
@@ -755,7 +1356,7 @@ pub async fn start_app_process(
                     Ok(0) => {
                         // EOF: emit any leftover data without trailing newline
                         if !line.is_empty() {
-                            let output_line = line.trim_end().to_string();
+                            let output_line = redact(line.trim_end(), &secrets_stderr);
                             let _ = app_handle_stderr.emit("process-output", serde_json::json!({
                                 "appId": app_id_stderr,
                                 "type": "stderr",
@@ -767,7 +1368,7 @@ pub async fn start_app_process(
                         break;
                     }
                     Ok(_) => {
-                        let output_line = line.trim_end().to_string();
+                        let output_line = redact(line.trim_end(), &secrets_stderr);
 
                         // Emit to frontend
                         let _ = app_handle_stderr.emit("process-output", serde_json::json!({
@@ -795,6 +1396,17 @@ pub async fn start_app_process(
 
     tokio::spawn(async move {
         let exit_status = child.wait().await;
+        // The shell is done with its script once it exits
+        drop(launch_script);
+
+        // Let the reader catch up on the last step markers; programs left running in the background
+        // may hold stdout open, so don't wait for it to close
+        if let Some(task) = stdout_task {
+            let _ = tokio::time::timeout(std::time::Duration::from_secs(1), task).await;
+        }
+        // The step that was running ended with the process
+        let exit_code = exit_status.as_ref().ok().and_then(|status| status.code());
+        step_tracker.lock().unwrap().finish(exit_code);
 
         // Remove from process manager
         {
@@ -849,25 +1461,25 @@ pub async fn start_app_process(
                 }));
             }
         }
-    });
-
-    // Store process info
-    let process_info = ProcessInfo {
-        pid,
-        started_at: started_at.clone(),
-        pgid,
-    };
 
-    {
-        let mut processes = process_manager.processes.lock().unwrap();
-        processes.insert(app_id.clone(), process_info);
-    }
+        // Clean up after the app however it ended; every hook runs even if an earlier one fails
+        if !post_stop.is_empty() {
+            let mut reporter = EventHookReporter {
+                app_handle: app_handle_monitor.clone(),
+                app_id: app_id_monitor.clone(),
+            };
+            if let Err(failure) = run_hooks(&hook_environment, HookStage::PostStop, &post_stop, false, &mut reporter).await {
+                log::warn!("App {}: {}", app_id_monitor, failure);
+            }
+        }
+    });
 
     // Emit process started event
     let _ = app_handle.emit("process-started", serde_json::json!({
         "appId": app_id,
         "pid": pid,
-        "startedAt": started_at
+        "startedAt": started_at,
+        "profile": active_profile
     }));
 
     // Handle browser auto-launch if configured
@@ -977,35 +1589,49 @@ pub async fn start_app_process(
 }
 
 /**
- * Stop a running process
+ * Start a saved app with its stored launch settings, as the UI does
  */
-#[tauri::command]
-pub async fn stop_app_process(
-    app_id: String,
-    app_handle: AppHandle,
-    process_manager: State<'_, ProcessManager>,
+pub(crate) async fn start_saved_app(
+    app_handle: &AppHandle,
+    app_id: &str,
+    profile: Option<String>,
 ) -> Result<ProcessResult, String> {
-    log::info!("Stopping process for app: {}", app_id);
-
-    let process_info = {
-        let mut processes = process_manager.processes.lock().unwrap();
-        match processes.remove(&app_id) {
-            Some(process) => process,
-            None => {
-                return Ok(ProcessResult {
-                    success: false,
-                    message: "Process not found or not running".to_string(),
-                    pid: None,
-                    error: Some("Process not found".to_string()),
-                });
-            }
-        }
-    };
+    let config = read_global_config().map_err(|e| e.message)?;
+    let stored = config
+        .apps
+        .into_iter()
+        .find(|a| a.id == app_id)
+        .ok_or_else(|| format!("App with ID '{}' not found", app_id))?;
+
+    start_app_process(
+        stored.id,
+        stored.launch_commands,
+        stored.working_directory,
+        stored.environment_variables,
+        stored.url,
+        stored.auto_launch_browser,
+        stored.browser_delay,
+        stored.port_to_check,
+        stored.port_check_timeout,
+        stored.terminal_type,
+        profile,
+        app_handle.clone(),
+        app_handle.state::<ProcessManager>(),
+        app_handle.state::<SecretStore>(),
+        app_handle.state::<ShellEnvironment>(),
+    )
+    .await
+}
 
-    // Cross-platform, reliable termination of the whole process tree
-    let pid_u32 = process_info.pid;
+/// Terminate a process and its children, escalating from a gentle interrupt to a forced kill
+///
+/// Shared by the GUI's stop command and the CLI. On Unix the signals go to the process group
+/// (`pgid`, defaulting to the PID).
+pub(crate) async fn terminate_process_tree(pid: u32, pgid: Option<i32>) -> Result<(), String> {
     #[cfg(unix)]
-    let pgid_i32 = process_info.pgid.unwrap_or(pid_u32 as i32);
+    let pgid_i32 = pgid.unwrap_or(pid as i32);
+    #[cfg(windows)]
+    let _ = pgid;
 
     // Helper to wait for process to exit with timeout
     async fn wait_for_exit(pid: u32, timeout_ms: u64) -> bool {
@@ -1081,11 +1707,11 @@ pub async fn stop_app_process(
                 log::warn!("Failed to send {} to pgid {}: {}", name, pgid_i32, err);
                 last_error = Some(format!("{} to group failed: {}", name, err));
             } else {
-                log::info!("Sent {} to process group {} (app {})", name, pgid_i32, app_id);
+                log::info!("Sent {} to process group {}", name, pgid_i32);
             }
             // Wait up to 2s after INT/TERM, 1s after KILL
             let timeout = if *sig == libc::SIGKILL { 1000 } else { 2000 };
-            if wait_for_exit(pid_u32, timeout).await {
+            if wait_for_exit(pid, timeout).await {
                 success = true;
                 break;
             }
@@ -1098,7 +1724,7 @@ pub async fn stop_app_process(
         // First a gentle try without /F, then with /F
         for (force, label) in [(false, "taskkill"), (true, "taskkill /F")].iter() {
             let mut cmd = tokio::process::Command::new("taskkill");
-            cmd.arg("/PID").arg(pid_u32.to_string()).arg("/T");
+            cmd.arg("/PID").arg(pid.to_string()).arg("/T");
             if *force {
                 cmd.arg("/F");
             }
@@ -1110,7 +1736,7 @@ pub async fn stop_app_process(
             match cmd.output().await {
                 Ok(out) => {
                     if out.status.success() {
-                        log::info!("{} succeeded for PID {}", label, pid_u32);
+                        log::info!("{} succeeded for PID {}", label, pid);
                     } else {
                         log::warn!(
                             "{} reported failure: {}",
@@ -1125,29 +1751,78 @@ pub async fn stop_app_process(
                 }
             }
             let timeout = if *force { 1000 } else { 2000 };
-            if wait_for_exit(pid_u32, timeout).await {
+            if wait_for_exit(pid, timeout).await {
                 success = true;
                 break;
             }
         }
     }
 
-    let result = if success {
-        log::info!("Process {} stopped successfully", app_id);
-        ProcessResult {
-            success: true,
-            message: "Process stopped successfully".to_string(),
-            pid: Some(pid_u32),
-            error: None,
-        }
+    if success {
+        Ok(())
     } else {
-        let error_msg = last_error.unwrap_or_else(|| "Failed to stop process within timeout".to_string());
-        log::error!("{}", error_msg);
-        ProcessResult {
-            success: false,
-            message: error_msg.clone(),
-            pid: Some(pid_u32),
-            error: Some(error_msg),
+        Err(last_error.unwrap_or_else(|| "Failed to stop process within timeout".to_string()))
+    }
+}
+
+/**
+ * Stop a running process
+ */
+#[tauri::command]
+pub async fn stop_app_process(
+    app_id: String,
+    app_handle: AppHandle,
+    process_manager: State<'_, ProcessManager>,
+) -> Result<ProcessResult, String> {
+    log::info!("Stopping process for app: {}", app_id);
+
+    let process_info = {
+        let mut processes = process_manager.processes.lock().unwrap();
+        match processes.remove(&app_id) {
+            Some(process) => process,
+            None => {
+                drop(processes);
+                // An app still running its pre-launch hooks is stopped by cancelling the launch
+                if let Some(cancel) = process_manager.launching.lock().unwrap().get(&app_id) {
+                    let _ = cancel.send(true);
+                    log::info!("Cancelled launch of app {}", app_id);
+                    return Ok(ProcessResult {
+                        success: true,
+                        message: "Launch cancelled".to_string(),
+                        pid: None,
+                        error: None,
+                    });
+                }
+                return Ok(ProcessResult {
+                    success: false,
+                    message: "Process not found or not running".to_string(),
+                    pid: None,
+                    error: Some("Process not found".to_string()),
+                });
+            }
+        }
+    };
+
+    // Cross-platform, reliable termination of the whole process tree
+    let pid_u32 = process_info.pid;
+    let result = match terminate_process_tree(pid_u32, process_info.pgid).await {
+        Ok(()) => {
+            log::info!("Process {} stopped successfully", app_id);
+            ProcessResult {
+                success: true,
+                message: "Process stopped successfully".to_string(),
+                pid: Some(pid_u32),
+                error: None,
+            }
+        }
+        Err(error_msg) => {
+            log::error!("{}", error_msg);
+            ProcessResult {
+                success: false,
+                message: error_msg.clone(),
+                pid: Some(pid_u32),
+                error: Some(error_msg),
+            }
         }
     };
 
@@ -1187,6 +1862,8 @@ pub async fn get_process_status(
             error_message: None,
             output: vec![], // Output is streamed via events
             is_background: Some(false),
+            active_profile: process_info.profile.clone(),
+            current_step: process_info.current_step.clone(),
         }))
     } else {
         Ok(None)
@@ -1212,6 +1889,8 @@ pub async fn get_all_process_status(
             error_message: None,
             output: vec![], // Output is streamed via events
             is_background: Some(false),
+            active_profile: process_info.profile.clone(),
+            current_step: process_info.current_step.clone(),
         });
     }
 
@@ -1225,8 +1904,11 @@ pub async fn get_all_process_status(
 pub async fn get_debug_info(
     command: String,
     working_directory: Option<String>,
+    secret_store: State<'_, SecretStore>,
+    shell_environment: State<'_, ShellEnvironment>,
 ) -> Result<serde_json::Value, String> {
-    log::info!("Getting debug info for command: {}", command);
+    let secret_values = known_secret_values(&secret_store);
+    log::info!("Getting debug info for command: {}", redact(&command, &secret_values));
 
     let parts: Vec<&str> = command.split_whitespace().collect();
     if parts.is_empty() {
@@ -1258,23 +1940,32 @@ pub async fn get_debug_info(
         });
     }
 
-    // Try to find the program in PATH
-    let program_in_path = which::which(program).is_ok();
+    // Try to find the program in the PATH processes are started with
+    let path_var = shell_environment
+        .variables()
+        .await
+        .remove("PATH")
+        .or_else(|| std::env::var("PATH").ok());
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let program_path = which::which_in(program, path_var.as_ref(), cwd);
     debug_info["program_status"] = serde_json::json!({
-        "found_in_path": program_in_path
+        "found_in_path": program_path.is_ok()
     });
 
-    if let Ok(program_path) = which::which(program) {
+    if let Ok(program_path) = program_path {
         debug_info["program_status"]["full_path"] = serde_json::json!(program_path.to_string_lossy());
     }
 
     // Get environment variables related to PATH
-    if let Ok(path_var) = std::env::var("PATH") {
+    if let Some(path_var) = path_var {
         debug_info["environment"] = serde_json::json!({
             "PATH": path_var
         });
     }
 
+    // Never hand stored secrets back to the UI, even if they ended up in the command or PATH
+    redact_json(&mut debug_info, &secret_values);
+
     log::info!("Debug info collected: {}", debug_info);
     Ok(debug_info)
 }
@@ -1289,6 +1980,11 @@ pub async fn kill_all_processes(
 ) -> Result<ProcessResult, String> {
     log::info!("Killing all running processes");
 
+    // Apps still running their pre-launch hooks are cancelled rather than killed
+    for cancel in process_manager.launching.lock().unwrap().values() {
+        let _ = cancel.send(true);
+    }
+
     // Get all process info first, then clear the map
     let processes_to_kill = {
         let mut processes = process_manager.processes.lock().unwrap();
@@ -1409,3 +2105,159 @@ pub async fn kill_all_processes(
         error: if failed_count > 0 { Some(format!("{} processes failed to stop", failed_count)) } else { None },
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_script(launch_commands: &str) -> LaunchScript {
+        let (_, _, script) = prepare_script_execution("my app/../x", launch_commands, None, Some("bash"), &[]).unwrap();
+        script
+    }
+
+    #[test]
+    fn script_written_and_removed() {
+        let script = write_script("echo hi");
+        let path = script.path.clone();
+        assert!(path.file_name().unwrap().to_string_lossy().starts_with("myappx-"));
+        assert_eq!(path.extension().unwrap(), "sh");
+        assert!(std::fs::read_to_string(&path).unwrap().ends_with("set -x\necho hi\n"));
+
+        drop(script);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn kept_script_survives() {
+        let path = write_script("echo hi").keep();
+        assert!(path.exists());
+
+        remove_launch_script(&path);
+        assert!(!path.exists());
+        // Removing it again is fine
+        remove_launch_script(&path);
+    }
+
+    #[test]
+    fn script_command_runs_file() {
+        let (program, args, script) = prepare_script_execution("app", "echo hi", None, Some("zsh"), &[]).unwrap();
+        assert_eq!(program, "zsh");
+        assert_eq!(args, vec![script.path.to_string_lossy().to_string()]);
+    }
+
+    /// Records the steps a tracker reports
+    #[derive(Default)]
+    struct Recorder {
+        started: Vec<usize>,
+        finished: Vec<(usize, Option<i32>)>,
+    }
+
+    impl StepReporter for Recorder {
+        fn started(&mut self, step: &LaunchStep) {
+            self.started.push(step.index);
+        }
+
+        fn finished(&mut self, step: &LaunchStep, exit_code: Option<i32>, _duration_ms: u64) {
+            self.finished.push((step.index, exit_code));
+        }
+    }
+
+    fn tracker(commands: &[&str]) -> StepTracker<Recorder> {
+        let commands = commands.iter().map(|c| c.to_string()).collect();
+        StepTracker::new("app".to_string(), commands, Recorder::default())
+    }
+
+    fn follow(tracker: &mut StepTracker<Recorder>, lines: &[&str]) -> Vec<bool> {
+        lines
+            .iter()
+            .map(|line| parse_step_marker(line).is_some_and(|marker| tracker.handle(marker)))
+            .collect()
+    }
+
+    #[test]
+    fn step_markers() {
+        assert_eq!(parse_step_marker("__ODDLAUNCHER_STEP__ start 2\r\n"), Some(StepMarker::Start(2)));
+        assert_eq!(parse_step_marker("__ODDLAUNCHER_STEP__ end 1 -1"), Some(StepMarker::End(1, -1)));
+        assert_eq!(parse_step_marker("__ODDLAUNCHER_STEP__ end 1"), None);
+        assert_eq!(parse_step_marker("__ODDLAUNCHER_STEP__ start 1 extra"), None);
+        assert_eq!(parse_step_marker("__ODDLAUNCHER_STEP__ finish 1"), None);
+        assert_eq!(parse_step_marker("__ODDLAUNCHER_STEP__start 1"), None);
+        assert_eq!(parse_step_marker("__ODDLAUNCHER_STEP__  start 1"), None);
+        assert_eq!(parse_step_marker("  __ODDLAUNCHER_STEP__ start 1"), None);
+        assert_eq!(parse_step_marker("echo __ODDLAUNCHER_STEP__ start 1"), None);
+    }
+
+    #[test]
+    fn steps_follow_markers() {
+        let mut tracker = tracker(&["npm install", "npm run build", "npm start"]);
+        let lines = ["__ODDLAUNCHER_STEP__ start 0", "added 1 package", "__ODDLAUNCHER_STEP__ start 1", "__ODDLAUNCHER_STEP__ start 2"];
+        assert_eq!(follow(&mut tracker, &lines), [true, false, true, true]);
+        tracker.finish(Some(130));
+
+        assert_eq!(tracker.reporter.started, [0, 1, 2]);
+        assert_eq!(tracker.reporter.finished, [(0, Some(0)), (1, Some(0)), (2, Some(130))]);
+        // Nothing is left running to finish again
+        tracker.finish(None);
+        assert_eq!(tracker.reporter.finished.len(), 3);
+    }
+
+    #[test]
+    fn powershell_end_markers() {
+        let mut tracker = tracker(&["Get-Thing", "Start-App"]);
+        let lines = [
+            "__ODDLAUNCHER_STEP__ start 0",
+            "__ODDLAUNCHER_STEP__ end 0 1",
+            "__ODDLAUNCHER_STEP__ start 1",
+            "__ODDLAUNCHER_STEP__ end 0 0",
+        ];
+        assert_eq!(follow(&mut tracker, &lines), [true, true, true, false]);
+        assert_eq!(tracker.reporter.finished, [(0, Some(1))]);
+    }
+
+    #[test]
+    fn marker_like_output_is_output() {
+        let mut tracker = tracker(&["./print-markers", "npm start"]);
+        let lines = [
+            "__ODDLAUNCHER_STEP__ start 0",
+            "__ODDLAUNCHER_STEP__ hello",
+            "__ODDLAUNCHER_STEP__ start 0",
+            "__ODDLAUNCHER_STEP__ start 5",
+            "__ODDLAUNCHER_STEP__ end 3 1",
+            "__ODDLAUNCHER_STEP__ start 1",
+            "__ODDLAUNCHER_STEP__ start 2",
+        ];
+        assert_eq!(follow(&mut tracker, &lines), [true, false, false, false, false, true, false]);
+        assert_eq!(tracker.reporter.started, [0, 1]);
+        assert_eq!(tracker.reporter.finished, [(0, Some(0))]);
+    }
+
+    #[test]
+    fn wsl_network_paths() {
+        use platform_utils::split_wsl_network_path as split;
+
+        assert_eq!(split(r"\\wsl.localhost\Ubuntu\home\me"), Some(("Ubuntu", r"home\me")));
+        assert_eq!(split(r"\\WSL.LOCALHOST\Ubuntu-22.04\srv"), Some(("Ubuntu-22.04", "srv")));
+        assert_eq!(split(r"\\wsl$\Debian\home"), Some(("Debian", "home")));
+        assert_eq!(split("//wsl.localhost/Ubuntu/home/me"), Some(("Ubuntu", "home/me")));
+        assert_eq!(split("//wsl$/Debian/"), Some(("Debian", "")));
+        assert_eq!(split(r"\\wsl.localhost\Ubuntu"), Some(("Ubuntu", "")));
+        assert_eq!(split(r"\\wsl.localhost\"), None);
+        assert_eq!(split(r"\\wsl.localhostx\Ubuntu"), None);
+        assert_eq!(split(r"\\server\share"), None);
+        assert_eq!(split(r"C:\wsl.localhost\Ubuntu"), None);
+        assert_eq!(platform_utils::wsl_network_distro(r"\\wsl$\Alpine\x"), Some("Alpine".to_string()));
+    }
+
+    #[test]
+    fn wsl_network_paths_convert() {
+        use platform_utils::convert_wsl_network_path as convert;
+
+        assert_eq!(convert(r"\\wsl.localhost\Ubuntu\home\me\app").unwrap(), "/home/me/app");
+        assert_eq!(convert(r"\\wsl$\Debian\srv\\api\").unwrap(), "/srv/api");
+        assert_eq!(convert("//wsl.localhost/Ubuntu/home/me/").unwrap(), "/home/me");
+        assert_eq!(convert(r"//wsl$/Ubuntu\mixed/slashes").unwrap(), "/mixed/slashes");
+        assert_eq!(convert(r"\\wsl.localhost\Ubuntu").unwrap(), "/");
+        assert_eq!(convert(r"\\wsl.localhost\Ubuntu\").unwrap(), "/");
+        assert!(convert(r"\\server\share\dir").is_err());
+    }
+}
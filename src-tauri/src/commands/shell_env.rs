@@ -0,0 +1,380 @@
+use crate::models::app::AppResult;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+use tauri::State;
+use tokio::sync::Mutex;
+
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
+
+// Environment of the user's login shell. Launched from a desktop environment the launcher doesn't
+// see PATH additions made in ~/.zshrc, ~/.bashrc and friends, so it's captured once and used as the
+// base environment for spawned processes.
+
+/// How long the login shell may take to start up and print its environment
+const CAPTURE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Printed around the environment so output from shell startup files can be told apart
+///
+/// They differ because some shells export the previous command's last argument as `$_`.
+const ENV_START_MARKER: &str = "__ODDLAUNCHER_ENV_START__";
+const ENV_END_MARKER: &str = "__ODDLAUNCHER_ENV_END__";
+
+/// Set while capturing, so startup files can skip slow or interactive setup
+pub const CAPTURE_ENV_VAR: &str = "ODDLAUNCHER_SHELL_ENV_CAPTURE";
+
+/// Variables describing the capturing shell itself rather than the user's setup
+const IGNORED_VARIABLES: [&str; 5] = ["_", "PWD", "OLDPWD", "SHLVL", CAPTURE_ENV_VAR];
+
+/**
+ * Cached login shell environment, shared across commands
+ *
+ * Capturing runs the login shell, which can take seconds, so it happens on a blocking thread
+ * and callers wait on an async lock rather than tying up the runtime.
+ */
+pub struct ShellEnvironment {
+    captured: Mutex<Option<Arc<CapturedEnvironment>>>,
+}
+
+impl Default for ShellEnvironment {
+    fn default() -> Self {
+        Self {
+            captured: Mutex::new(None),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CapturedEnvironment {
+    shell: Option<String>,
+    variables: HashMap<String, String>,
+    captured_at: String,
+    error: Option<String>,
+}
+
+/**
+ * Status of the login shell environment as reported to the frontend
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShellEnvironmentStatus {
+    /// Login shell the environment was captured from (none on Windows)
+    pub shell: Option<String>,
+    /// Number of variables captured
+    pub variable_count: usize,
+    /// When the environment was captured, if it has been
+    pub captured_at: Option<String>,
+    /// Why capturing failed, in which case the launcher's own environment is used
+    pub error: Option<String>,
+}
+
+impl ShellEnvironment {
+    /// Variables from the login shell, capturing them on first use
+    ///
+    /// Empty if capturing failed or there's no login shell (Windows). Concurrent first
+    /// callers wait for the same capture.
+    pub async fn variables(&self) -> HashMap<String, String> {
+        let mut captured = self.captured.lock().await;
+        if captured.is_none() {
+            *captured = Some(Arc::new(capture_in_background().await));
+        }
+        captured.as_ref().map(|c| c.variables.clone()).unwrap_or_default()
+    }
+
+    /// Capture the environment again, e.g. after the user changed their shell profile
+    pub async fn refresh(&self) -> ShellEnvironmentStatus {
+        let mut captured = self.captured.lock().await;
+        let fresh = capture_in_background().await;
+        let status = fresh.status();
+        *captured = Some(Arc::new(fresh));
+        status
+    }
+
+    /// Status of the captured environment; not captured yet while a capture is running
+    pub fn status(&self) -> ShellEnvironmentStatus {
+        match self.captured.try_lock().ok().and_then(|captured| captured.clone()) {
+            Some(captured) => captured.status(),
+            None => ShellEnvironmentStatus {
+                shell: login_shell(),
+                variable_count: 0,
+                captured_at: None,
+                error: None,
+            },
+        }
+    }
+}
+
+/// Run the login shell on a blocking thread
+async fn capture_in_background() -> CapturedEnvironment {
+    tauri::async_runtime::spawn_blocking(capture)
+        .await
+        .unwrap_or_else(|e| CapturedEnvironment {
+            shell: login_shell(),
+            variables: HashMap::new(),
+            captured_at: chrono::Utc::now().to_rfc3339(),
+            error: Some(format!("Capturing the environment failed: {}", e)),
+        })
+}
+
+impl CapturedEnvironment {
+    fn status(&self) -> ShellEnvironmentStatus {
+        ShellEnvironmentStatus {
+            shell: self.shell.clone(),
+            variable_count: self.variables.len(),
+            captured_at: Some(self.captured_at.clone()),
+            error: self.error.clone(),
+        }
+    }
+}
+
+fn capture() -> CapturedEnvironment {
+    let shell = login_shell();
+    let result = match shell.as_deref() {
+        Some(shell) => {
+            log::info!("Capturing login shell environment from {}", shell);
+            run_login_shell(shell)
+        }
+        None => Ok(HashMap::new()),
+    };
+
+    let (variables, error) = match result {
+        Ok(variables) => {
+            log::info!("Captured {} variables from the login shell", variables.len());
+            (variables, None)
+        }
+        Err(e) => {
+            log::warn!("Failed to capture login shell environment, using the launcher's own: {}", e);
+            (HashMap::new(), Some(e))
+        }
+    };
+
+    CapturedEnvironment {
+        shell,
+        variables,
+        captured_at: chrono::Utc::now().to_rfc3339(),
+        error,
+    }
+}
+
+/// The user's login shell: $SHELL, falling back to the password database
+#[cfg(unix)]
+pub(crate) fn login_shell() -> Option<String> {
+    if let Some(shell) = std::env::var("SHELL").ok().filter(|s| !s.is_empty()) {
+        return Some(shell);
+    }
+
+    // Desktop sessions don't always set $SHELL
+    let shell = unsafe {
+        let entry = libc::getpwuid(libc::getuid());
+        if entry.is_null() || (*entry).pw_shell.is_null() {
+            return Some("/bin/sh".to_string());
+        }
+        std::ffi::CStr::from_ptr((*entry).pw_shell).to_string_lossy().to_string()
+    };
+    Some(if shell.is_empty() { "/bin/sh".to_string() } else { shell })
+}
+
+/// Windows programs get their environment from the registry, so there's nothing to capture
+#[cfg(not(unix))]
+pub(crate) fn login_shell() -> Option<String> {
+    None
+}
+
+/// Run the shell as an interactive login shell and read back its environment
+fn run_login_shell(shell: &str) -> Result<HashMap<String, String>, String> {
+    // `env -0` keeps multi-line values intact where it's supported
+    let script = format!(
+        "printf '%s' {}; env -0 2>/dev/null || env; printf '%s' {}",
+        ENV_START_MARKER, ENV_END_MARKER
+    );
+
+    let mut cmd = Command::new(shell);
+    cmd.args(["-i", "-l", "-c", &script])
+        .env(CAPTURE_ENV_VAR, "1")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+
+    // A new session has no controlling terminal for the interactive shell to take over
+    #[cfg(unix)]
+    unsafe {
+        cmd.pre_exec(|| {
+            libc::setsid();
+            Ok(())
+        });
+    }
+
+    let mut child = cmd.spawn().map_err(|e| format!("Failed to run {}: {}", shell, e))?;
+    let mut stdout = child.stdout.take().ok_or("Failed to read shell output")?;
+
+    // Programs started from startup files may hold stdout open, so stop reading at the closing marker
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut output = Vec::new();
+        let mut buffer = [0u8; 8192];
+        loop {
+            match stdout.read(&mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    output.extend_from_slice(&buffer[..n]);
+                    if find_dump(&String::from_utf8_lossy(&output)).is_some() {
+                        break;
+                    }
+                }
+            }
+        }
+        let _ = sender.send(output);
+    });
+
+    let output = receiver.recv_timeout(CAPTURE_TIMEOUT);
+    if output.is_err() {
+        #[cfg(unix)]
+        unsafe {
+            // The shell leads its own session, so this also reaches anything it started
+            libc::kill(-(child.id() as i32), libc::SIGKILL);
+        }
+        let _ = child.kill();
+    }
+    // Reap the shell; it may outlive the environment dump while startup files' jobs wind down
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+
+    let output = output.map_err(|_| {
+        format!(
+            "{} did not print its environment within {}s",
+            shell,
+            CAPTURE_TIMEOUT.as_secs()
+        )
+    })?;
+    parse_env_output(&String::from_utf8_lossy(&output))
+}
+
+/// The environment dump between the markers, once it has been printed in full
+fn find_dump(output: &str) -> Option<&str> {
+    let (_, rest) = output.split_once(ENV_START_MARKER)?;
+    let (dump, _) = rest.split_once(ENV_END_MARKER)?;
+    Some(dump)
+}
+
+/// Extract `NAME=value` pairs printed between the markers
+fn parse_env_output(output: &str) -> Result<HashMap<String, String>, String> {
+    let dump = find_dump(output).ok_or("The login shell did not print its environment")?;
+
+    let mut variables = HashMap::new();
+    if dump.contains('\0') {
+        for entry in dump.split('\0') {
+            if let Some((name, value)) = split_entry(entry) {
+                variables.insert(name.to_string(), value.to_string());
+            }
+        }
+    } else {
+        // Plain `env`: a line that doesn't start a new variable continues the previous value
+        let mut last: Option<String> = None;
+        for line in dump.lines() {
+            match split_entry(line) {
+                Some((name, value)) => {
+                    variables.insert(name.to_string(), value.to_string());
+                    last = Some(name.to_string());
+                }
+                None => {
+                    if let Some(value) = last.as_ref().and_then(|name| variables.get_mut(name)) {
+                        value.push('\n');
+                        value.push_str(line);
+                    }
+                }
+            }
+        }
+    }
+
+    variables.retain(|name, _| !IGNORED_VARIABLES.contains(&name.as_str()));
+    Ok(variables)
+}
+
+fn split_entry(entry: &str) -> Option<(&str, &str)> {
+    let (name, value) = entry.split_once('=')?;
+    let valid_name = !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid_name.then_some((name, value))
+}
+
+/// Get the status of the captured login shell environment
+#[tauri::command]
+pub async fn get_shell_environment_status(
+    shell_environment: State<'_, ShellEnvironment>,
+) -> AppResult<ShellEnvironmentStatus> {
+    Ok(shell_environment.status())
+}
+
+/// Capture the login shell environment again, for processes started from now on
+#[tauri::command]
+pub async fn refresh_shell_environment(
+    shell_environment: State<'_, ShellEnvironment>,
+) -> AppResult<ShellEnvironmentStatus> {
+    log::info!("Refreshing login shell environment");
+    Ok(shell_environment.refresh().await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dump(body: &str) -> String {
+        format!("{}{}{}", ENV_START_MARKER, body, ENV_END_MARKER)
+    }
+
+    #[test]
+    fn parses_null_separated_output() {
+        let output = dump("PATH=/usr/bin:/bin\0GREETING=hello\nworld\0EMPTY=\0");
+        let variables = parse_env_output(&output).unwrap();
+        assert_eq!(variables.len(), 3);
+        assert_eq!(variables["PATH"], "/usr/bin:/bin");
+        assert_eq!(variables["GREETING"], "hello\nworld");
+        assert_eq!(variables["EMPTY"], "");
+    }
+
+    #[test]
+    fn plain_env_continues_multi_line_values() {
+        let output = dump("PATH=/usr/bin\nCERT=-----BEGIN-----\nabc def\n-----END-----\nEDITOR=vim\n");
+        let variables = parse_env_output(&output).unwrap();
+        assert_eq!(variables["CERT"], "-----BEGIN-----\nabc def\n-----END-----");
+        assert_eq!(variables["EDITOR"], "vim");
+    }
+
+    #[test]
+    fn startup_file_noise_is_ignored() {
+        let output = format!(
+            "Welcome back!\nFOO=from-motd\n{}\nnvm: using node v20{}",
+            dump("PATH=/usr/bin\0"),
+            "\nBAR=after"
+        );
+        let variables = parse_env_output(&output).unwrap();
+        assert_eq!(variables.len(), 1);
+        assert_eq!(variables["PATH"], "/usr/bin");
+    }
+
+    #[test]
+    fn shell_variables_are_dropped() {
+        let output = dump("_=/usr/bin/env\0PWD=/home/me\0SHLVL=2\0ODDLAUNCHER_SHELL_ENV_CAPTURE=1\0LANG=C\0");
+        let variables = parse_env_output(&output).unwrap();
+        assert_eq!(variables.keys().collect::<Vec<_>>(), vec!["LANG"]);
+    }
+
+    #[test]
+    fn invalid_names_are_skipped() {
+        let output = dump("1BAD=x\0GOOD_1=y\0=z\0BASH_FUNC_f%%=() { :; }\0");
+        let variables = parse_env_output(&output).unwrap();
+        assert_eq!(variables.keys().collect::<Vec<_>>(), vec!["GOOD_1"]);
+    }
+
+    #[test]
+    fn missing_dump_is_an_error() {
+        assert!(parse_env_output("bash: env: command not found").is_err());
+        assert!(parse_env_output(&format!("{}PATH=/bin", ENV_START_MARKER)).is_err());
+    }
+}
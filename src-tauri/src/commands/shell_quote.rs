@@ -0,0 +1,339 @@
+// Quoting for text interpolated into the scripts handed to each terminal's shell.
+// User commands themselves are run as written; this covers everything the launcher
+// adds around them (working directories, echoed commands).
+
+/**
+ * Quoting rules a terminal's shell follows
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShellDialect {
+    /// sh, bash, zsh, Git Bash and WSL
+    Posix,
+    Fish,
+    Cmd,
+    /// Windows PowerShell and pwsh
+    PowerShell,
+    /// Nushell
+    Nu,
+    /// Python-based; values are written as Python string literals
+    Xonsh,
+    Elvish,
+}
+
+impl ShellDialect {
+    /// Dialect spoken by one of the `TERMINAL_TYPES`
+    pub fn for_terminal(terminal_type: &str) -> Self {
+        match terminal_type {
+            "cmd" => ShellDialect::Cmd,
+            "powershell" | "pwsh" => ShellDialect::PowerShell,
+            "fish" => ShellDialect::Fish,
+            "nu" => ShellDialect::Nu,
+            "xonsh" => ShellDialect::Xonsh,
+            "elvish" => ShellDialect::Elvish,
+            _ => ShellDialect::Posix,
+        }
+    }
+
+    /// Quote a value so the shell reads it back as exactly one literal word
+    pub fn quote(self, value: &str) -> String {
+        match self {
+            // Nothing is special inside single quotes, so only the quote itself needs splicing in
+            ShellDialect::Posix => format!("'{}'", value.replace('\'', r"'\''")),
+            // Fish single quotes treat \\ and \' as escapes
+            ShellDialect::Fish => format!("'{}'", value.replace('\\', r"\\").replace('\'', r"\'")),
+            // PowerShell also accepts typographic single quotes as delimiters; doubling escapes any of them
+            ShellDialect::PowerShell => {
+                let mut quoted = String::with_capacity(value.len() + 2);
+                quoted.push('\'');
+                for c in value.chars() {
+                    if matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}') {
+                        quoted.push(c);
+                    }
+                    quoted.push(c);
+                }
+                quoted.push('\'');
+                quoted
+            }
+            // Quotes would turn off caret escaping, so the quotes themselves are escaped instead
+            ShellDialect::Cmd => format!("^\"{}^\"", cmd_escape(value)),
+            // Raw strings have no escapes; enough hashes that the value can't close them early
+            ShellDialect::Nu => {
+                let mut hashes = "#".to_string();
+                while value.contains(&format!("'{}", hashes)) {
+                    hashes.push('#');
+                }
+                format!("r{}'{}'{}", hashes, value, hashes)
+            }
+            // Passed through @() so subprocess mode doesn't expand anything in it
+            ShellDialect::Xonsh => format!("@({})", python_string(value)),
+            // Only the quote itself is special, and it's doubled
+            ShellDialect::Elvish => format!("'{}'", value.replace('\'', "''")),
+        }
+    }
+
+    /// Quote a value for use inside a script file rather than on the command line
+    pub fn quote_in_script(self, value: &str) -> String {
+        match self {
+            // Batch files do support quoting, but `%` still needs doubling; paths can't contain `"`
+            ShellDialect::Cmd => format!("\"{}\"", single_line(value).replace('"', "").replace('%', "%%")),
+            _ => self.quote(value),
+        }
+    }
+
+    /// Statement that prints `text` on its own line without the shell interpreting it
+    pub fn echo(self, text: &str) -> String {
+        let text = single_line(text);
+        match self {
+            // printf, unlike echo in dash or with xpg_echo, leaves backslashes alone
+            ShellDialect::Posix | ShellDialect::Fish => format!("printf '%s\\n' {}", self.quote(&text)),
+            ShellDialect::PowerShell => format!("Write-Host {}", self.quote(&text)),
+            ShellDialect::Nu => format!("print {}", self.quote(&text)),
+            ShellDialect::Xonsh => format!("print({})", python_string(&text)),
+            ShellDialect::Elvish => format!("echo {}", self.quote(&text)),
+            // `echo.` avoids "ECHO is off." for blank text and lets the text start with / or ?
+            ShellDialect::Cmd => format!("echo.{}", cmd_escape(&text)),
+        }
+    }
+}
+
+/// Escape cmd.exe metacharacters with carets so the line is taken literally
+///
+/// `%` can't be escaped on the command line; a caret right after it breaks up any
+/// `%VAR%` reference, and the caret is removed once expansion is done.
+fn cmd_escape(value: &str) -> String {
+    let needs_caret = |c: char| matches!(c, '^' | '&' | '|' | '<' | '>' | '(' | ')' | '"');
+    let value = single_line(value);
+    let mut escaped = String::with_capacity(value.len() * 2);
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if needs_caret(c) {
+            escaped.push('^');
+        }
+        escaped.push(c);
+        // A following escaped character already starts with a caret; doubling it would escape the caret instead
+        if c == '%' && chars.peek().is_some_and(|&next| !needs_caret(next)) {
+            escaped.push('^');
+        }
+    }
+    escaped
+}
+
+/// A single-quoted Python string literal
+fn python_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('\'');
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '\'' => quoted.push_str("\\'"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// cmd has no way to quote a line break, so those are flattened to spaces
+fn single_line(value: &str) -> String {
+    value.replace(['\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    /// Inputs that have broken naive quoting at one point or another
+    const CORPUS: &[&str] = &[
+        "",
+        "plain",
+        "two words",
+        "it's",
+        "'",
+        "''",
+        "\"double\"",
+        "back\\slash",
+        "trailing\\",
+        "\\'",
+        "$HOME",
+        "${PATH}",
+        "$(whoami)",
+        "`whoami`",
+        "a; rm -rf /tmp/nope",
+        "a && b || c",
+        "pipe | tee",
+        "<in >out 2>&1",
+        "(sub) {brace} [glob]*?",
+        "~user",
+        "#comment",
+        "100%",
+        "%PATH%",
+        "^caret",
+        "!bang!",
+        "tab\there",
+        "line\nbreak",
+        "C:\\Program Files (x86)\\App",
+        "/home/user/My Project's \"dir\"",
+        "‘smart’ “quotes”",
+        "unicode ✓ 日本語",
+        "-n",
+        "-e \\n",
+    ];
+
+    fn available(program: &str) -> bool {
+        Command::new(program)
+            .arg("-c")
+            .arg("exit 0")
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false)
+    }
+
+    /// Run a script and return its stdout, or None if the shell isn't installed
+    fn run(program: &str, flag: &str, script: &str) -> Option<String> {
+        if !available(program) {
+            return None;
+        }
+        let output = Command::new(program).arg(flag).arg(script).output().ok()?;
+        assert!(output.status.success(), "{} failed on {:?}: {}", program, script, String::from_utf8_lossy(&output.stderr));
+        Some(String::from_utf8(output.stdout).unwrap())
+    }
+
+    #[test]
+    fn posix_quote_expected_forms() {
+        assert_eq!(ShellDialect::Posix.quote(""), "''");
+        assert_eq!(ShellDialect::Posix.quote("it's"), r"'it'\''s'");
+        assert_eq!(ShellDialect::Posix.quote("$HOME"), "'$HOME'");
+    }
+
+    #[test]
+    fn fish_quote_expected_forms() {
+        assert_eq!(ShellDialect::Fish.quote("it's"), r"'it\'s'");
+        assert_eq!(ShellDialect::Fish.quote(r"a\b"), r"'a\\b'");
+    }
+
+    #[test]
+    fn powershell_quote_expected_forms() {
+        assert_eq!(ShellDialect::PowerShell.quote("it's"), "'it''s'");
+        assert_eq!(ShellDialect::PowerShell.quote("‘x’"), "'‘‘x’’'");
+        assert_eq!(ShellDialect::PowerShell.quote("$env:PATH"), "'$env:PATH'");
+    }
+
+    #[test]
+    fn cmd_quote_expected_forms() {
+        assert_eq!(ShellDialect::Cmd.quote(r"C:\a b"), r#"^"C:\a b^""#);
+        assert_eq!(ShellDialect::Cmd.quote("a&b"), r#"^"a^&b^""#);
+        assert_eq!(ShellDialect::Cmd.quote("%PATH%"), r#"^"%^PATH%^""#);
+        assert_eq!(ShellDialect::Cmd.echo("50% & 100%"), "echo.50%^ ^& 100%");
+        assert_eq!(ShellDialect::Cmd.echo("npm run \"dev\" > log"), r#"echo.npm run ^"dev^" ^> log"#);
+        assert_eq!(ShellDialect::Cmd.quote_in_script(r"C:\100% & (x)"), r#""C:\100%% & (x)""#);
+    }
+
+    #[test]
+    fn nu_xonsh_elvish_quote_expected_forms() {
+        assert_eq!(ShellDialect::Nu.quote("it's"), "r#'it's'#");
+        assert_eq!(ShellDialect::Nu.quote("'#"), "r##''#'##");
+        assert_eq!(ShellDialect::Xonsh.quote(r"it's a\b"), r"@('it\'s a\\b')");
+        assert_eq!(ShellDialect::Xonsh.echo("$HOME"), "print('$HOME')");
+        assert_eq!(ShellDialect::Elvish.quote("it's"), "'it''s'");
+    }
+
+    #[test]
+    fn quoted_values_stay_single_line_only_for_cmd() {
+        assert!(!ShellDialect::Cmd.quote("a\nb").contains('\n'));
+        assert!(ShellDialect::Posix.quote("a\nb").contains('\n'));
+    }
+
+    #[test]
+    fn posix_round_trip() {
+        for shell in ["sh", "bash", "zsh"] {
+            for value in CORPUS {
+                let script = format!("printf '%s' {}", ShellDialect::Posix.quote(value));
+                if let Some(output) = run(shell, "-c", &script) {
+                    assert_eq!(&output, value, "{} mangled {:?}", shell, value);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn posix_echo_round_trip() {
+        for value in CORPUS {
+            if let Some(output) = run("sh", "-c", &ShellDialect::Posix.echo(value)) {
+                assert_eq!(output, format!("{}\n", single_line(value)), "sh mangled {:?}", value);
+            }
+        }
+    }
+
+    #[test]
+    fn fish_round_trip() {
+        for value in CORPUS {
+            let script = format!("printf '%s' {}", ShellDialect::Fish.quote(value));
+            if let Some(output) = run("fish", "-c", &script) {
+                assert_eq!(&output, value, "fish mangled {:?}", value);
+            }
+        }
+    }
+
+    #[test]
+    fn powershell_round_trip() {
+        for shell in ["pwsh", "powershell"] {
+            for value in CORPUS {
+                let script = format!("[Console]::Out.Write({})", ShellDialect::PowerShell.quote(value));
+                if let Some(output) = run(shell, "-Command", &script) {
+                    assert_eq!(&output, value, "{} mangled {:?}", shell, value);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn nu_round_trip() {
+        for value in CORPUS {
+            let script = format!("print -n {}", ShellDialect::Nu.quote(value));
+            if let Some(output) = run("nu", "-c", &script) {
+                assert_eq!(&output, value, "nu mangled {:?}", value);
+            }
+        }
+    }
+
+    #[test]
+    fn xonsh_round_trip() {
+        for value in CORPUS {
+            let script = format!("printf '%s' {}", ShellDialect::Xonsh.quote(value));
+            if let Some(output) = run("xonsh", "-c", &script) {
+                assert_eq!(&output, value, "xonsh mangled {:?}", value);
+            }
+        }
+    }
+
+    #[test]
+    fn elvish_round_trip() {
+        for value in CORPUS {
+            let script = format!("print {}", ShellDialect::Elvish.quote(value));
+            if let Some(output) = run("elvish", "-c", &script) {
+                assert_eq!(&output, value, "elvish mangled {:?}", value);
+            }
+        }
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn cmd_echo_round_trip() {
+        use std::os::windows::process::CommandExt;
+
+        for value in CORPUS.iter().filter(|v| !v.is_empty()) {
+            // raw_arg keeps Rust from adding its own quoting on top
+            let output = Command::new("cmd.exe")
+                .arg("/c")
+                .raw_arg(ShellDialect::Cmd.echo(value))
+                .output()
+                .unwrap();
+            let printed = String::from_utf8_lossy(&output.stdout);
+            assert_eq!(printed.trim_end_matches(['\r', '\n']), single_line(value), "cmd mangled {:?}", value);
+        }
+    }
+}
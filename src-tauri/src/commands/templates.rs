@@ -0,0 +1,570 @@
+use crate::commands::config::{get_config_dir, read_global_config};
+use crate::commands::config_format::ConfigFormat;
+use crate::commands::validation::ensure_valid_app;
+use crate::models::app::{AppConfig, AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// AppConfig fields that hold numbers, so substituted placeholder strings are converted back
+const NUMERIC_FIELDS: [&str; 3] = ["portToCheck", "browserDelay", "portCheckTimeout"];
+
+/// AppConfig fields that hold booleans
+const BOOLEAN_FIELDS: [&str; 1] = ["autoLaunchBrowser"];
+
+/// Templates shipped with OddLauncher, in the same format as user templates
+const BUILTIN_TEMPLATES: &str = r#"[
+  {
+    "id": "nextjs",
+    "name": "Next.js",
+    "description": "Next.js development server",
+    "category": "JavaScript",
+    "parameters": [
+      { "name": "directory", "label": "Project directory", "required": true },
+      { "name": "port", "label": "Port", "default": "3000" },
+      { "name": "packageManager", "label": "Package manager", "default": "npm" }
+    ],
+    "app": {
+      "launchCommands": "{{packageManager}} run dev -- -p {{port}}",
+      "workingDirectory": "{{directory}}",
+      "url": "http://localhost:{{port}}",
+      "portToCheck": "{{port}}",
+      "autoLaunchBrowser": true,
+      "tags": ["nextjs", "javascript"]
+    }
+  },
+  {
+    "id": "vite",
+    "name": "Vite",
+    "description": "Vite development server (React, Vue, Svelte, ...)",
+    "category": "JavaScript",
+    "parameters": [
+      { "name": "directory", "label": "Project directory", "required": true },
+      { "name": "port", "label": "Port", "default": "5173" },
+      { "name": "packageManager", "label": "Package manager", "default": "npm" }
+    ],
+    "app": {
+      "launchCommands": "{{packageManager}} run dev -- --port {{port}} --strictPort",
+      "workingDirectory": "{{directory}}",
+      "url": "http://localhost:{{port}}",
+      "portToCheck": "{{port}}",
+      "autoLaunchBrowser": true,
+      "tags": ["vite", "javascript"]
+    }
+  },
+  {
+    "id": "rails",
+    "name": "Ruby on Rails",
+    "description": "Rails server via bin/rails",
+    "category": "Ruby",
+    "parameters": [
+      { "name": "directory", "label": "Project directory", "required": true },
+      { "name": "port", "label": "Port", "default": "3000" },
+      { "name": "environment", "label": "Rails environment", "default": "development" }
+    ],
+    "app": {
+      "launchCommands": "bundle install\nbin/rails server -p {{port}}",
+      "workingDirectory": "{{directory}}",
+      "url": "http://localhost:{{port}}",
+      "portToCheck": "{{port}}",
+      "portCheckTimeout": "60",
+      "environmentVariables": { "RAILS_ENV": "{{environment}}" },
+      "autoLaunchBrowser": true,
+      "tags": ["rails", "ruby"]
+    }
+  },
+  {
+    "id": "django",
+    "name": "Django",
+    "description": "Django development server via manage.py",
+    "category": "Python",
+    "parameters": [
+      { "name": "directory", "label": "Project directory", "required": true },
+      { "name": "port", "label": "Port", "default": "8000" },
+      { "name": "python", "label": "Python executable", "default": "python" }
+    ],
+    "app": {
+      "launchCommands": "{{python}} manage.py migrate\n{{python}} manage.py runserver {{port}}",
+      "workingDirectory": "{{directory}}",
+      "url": "http://localhost:{{port}}",
+      "portToCheck": "{{port}}",
+      "environmentVariables": { "PYTHONUNBUFFERED": "1" },
+      "autoLaunchBrowser": true,
+      "tags": ["django", "python"]
+    }
+  },
+  {
+    "id": "flask",
+    "name": "Flask",
+    "description": "Flask development server",
+    "category": "Python",
+    "parameters": [
+      { "name": "directory", "label": "Project directory", "required": true },
+      { "name": "app", "label": "Flask app module", "default": "app" },
+      { "name": "port", "label": "Port", "default": "5000" }
+    ],
+    "app": {
+      "launchCommands": "flask --app {{app}} run --debug --port {{port}}",
+      "workingDirectory": "{{directory}}",
+      "url": "http://localhost:{{port}}",
+      "portToCheck": "{{port}}",
+      "environmentVariables": { "PYTHONUNBUFFERED": "1" },
+      "autoLaunchBrowser": true,
+      "tags": ["flask", "python"]
+    }
+  },
+  {
+    "id": "docker-compose",
+    "name": "Docker Compose",
+    "description": "docker compose stack with a web entry point",
+    "category": "Containers",
+    "parameters": [
+      { "name": "directory", "label": "Directory containing compose file", "required": true },
+      { "name": "port", "label": "Published web port", "default": "8080" },
+      { "name": "file", "label": "Compose file", "default": "compose.yaml" }
+    ],
+    "app": {
+      "launchCommands": "docker compose -f {{file}} up --build",
+      "workingDirectory": "{{directory}}",
+      "url": "http://localhost:{{port}}",
+      "portToCheck": "{{port}}",
+      "portCheckTimeout": "120",
+      "autoLaunchBrowser": true,
+      "tags": ["docker"]
+    }
+  }
+]"#;
+
+/**
+ * A parameter that fills `{{name}}` placeholders in a template
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateParameter {
+    pub name: String,
+    /// Human-readable label for forms
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Value used when none is given
+    #[serde(default)]
+    pub default: Option<String>,
+    /// Whether a value must be given (parameters with a default never need one)
+    #[serde(default)]
+    pub required: bool,
+}
+
+/**
+ * Template for creating apps for a common stack
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppTemplate {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Grouping for display, e.g. "JavaScript" (optional)
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub parameters: Vec<TemplateParameter>,
+    /// AppConfig fields (camelCase) whose strings may contain `{{param}}` placeholders
+    pub app: serde_json::Value,
+    /// Whether the template ships with OddLauncher
+    #[serde(default, skip_deserializing)]
+    pub builtin: bool,
+    /// File the template was loaded from (user templates only)
+    #[serde(default, skip_deserializing)]
+    pub source: Option<String>,
+}
+
+/// List built-in templates and user templates from ~/.oddlauncher/templates/
+///
+/// A user template with the same ID as a built-in one replaces it.
+#[tauri::command]
+pub async fn list_app_templates() -> AppResult<Vec<AppTemplate>> {
+    let templates = load_templates()?;
+    log::info!("Loaded {} app templates", templates.len());
+    Ok(templates)
+}
+
+/// Create an app configuration from a template
+///
+/// The app isn't saved; it's validated against the saved apps and returned for review.
+/// A `name` parameter sets the app name (defaults to the template name).
+#[tauri::command]
+pub async fn create_app_from_template(
+    template_id: String,
+    params: Option<HashMap<String, String>>,
+) -> AppResult<AppConfig> {
+    let params = params.unwrap_or_default();
+    let template = load_templates()?
+        .into_iter()
+        .find(|t| t.id == template_id)
+        .ok_or_else(|| {
+            AppError::new(
+                "TEMPLATE_NOT_FOUND",
+                &format!("Template '{}' not found", template_id),
+            )
+        })?;
+
+    log::info!("Creating app from template: {}", template.id);
+
+    let app_config = instantiate_template(&template, &params)?;
+    let config = read_global_config()?;
+    ensure_valid_app(&app_config, &config.apps, &config.settings.custom_shells)?;
+
+    Ok(app_config)
+}
+
+/// Get the user templates directory (~/.oddlauncher/templates/)
+fn get_templates_dir() -> AppResult<PathBuf> {
+    Ok(get_config_dir()?.join("templates"))
+}
+
+/// Load built-in templates overlaid with user templates
+fn load_templates() -> AppResult<Vec<AppTemplate>> {
+    let mut templates: Vec<AppTemplate> = serde_json::from_str(BUILTIN_TEMPLATES).map_err(|e| {
+        AppError::new(
+            "TEMPLATE_PARSE_ERROR",
+            &format!("Failed to parse built-in templates: {}", e),
+        )
+    })?;
+    for template in templates.iter_mut() {
+        template.builtin = true;
+    }
+
+    let templates_dir = get_templates_dir()?;
+    if !templates_dir.is_dir() {
+        return Ok(templates);
+    }
+
+    let entries = fs::read_dir(&templates_dir).map_err(|e| {
+        AppError::new(
+            "DIR_READ_ERROR",
+            &format!("Failed to read templates directory: {}", e),
+        )
+    })?;
+
+    let mut paths: Vec<PathBuf> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+    paths.sort();
+
+    for path in paths {
+        // Skip unsupported files; report broken templates without hiding the others
+        let template = match read_template_file(&path) {
+            Some(Ok(template)) => template,
+            Some(Err(e)) => {
+                log::warn!("Skipping invalid template {:?}: {}", path, e);
+                continue;
+            }
+            None => continue,
+        };
+
+        match templates.iter().position(|t| t.id == template.id) {
+            Some(existing) => {
+                log::info!("User template {:?} overrides template '{}'", path, template.id);
+                templates[existing] = template;
+            }
+            None => templates.push(template),
+        }
+    }
+
+    Ok(templates)
+}
+
+/// Read a user template file, or None if it isn't a JSON, TOML or YAML file
+fn read_template_file(path: &Path) -> Option<Result<AppTemplate, String>> {
+    let format = ConfigFormat::from_path(path)?;
+
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => return Some(Err(e.to_string())),
+    };
+
+    let parsed = format.parse::<AppTemplate>(&content);
+
+    Some(parsed.map(|mut template| {
+        template.source = Some(path.to_string_lossy().to_string());
+        template
+    }))
+}
+
+/// Fill in a template's placeholders and build the app configuration
+fn instantiate_template(
+    template: &AppTemplate,
+    params: &HashMap<String, String>,
+) -> AppResult<AppConfig> {
+    let mut values: HashMap<&str, &str> = HashMap::new();
+    let mut missing = Vec::new();
+
+    for parameter in &template.parameters {
+        let value = params
+            .get(&parameter.name)
+            .map(|v| v.as_str())
+            .filter(|v| !v.trim().is_empty())
+            .or(parameter.default.as_deref());
+
+        match value {
+            Some(value) => {
+                values.insert(&parameter.name, value);
+            }
+            None if parameter.required => missing.push(parameter.name.clone()),
+            None => {
+                values.insert(&parameter.name, "");
+            }
+        }
+    }
+
+    if !missing.is_empty() {
+        let mut details = HashMap::new();
+        details.insert("missing".to_string(), serde_json::json!(missing));
+        return Err(AppError::new(
+            "TEMPLATE_PARAMETER_ERROR",
+            &format!("Missing required template parameters: {}", missing.join(", ")),
+        )
+        .with_details(details));
+    }
+
+    let mut app = template.app.clone();
+    let mut undeclared = BTreeSet::new();
+    substitute_placeholders(&mut app, &values, &mut undeclared);
+
+    if !undeclared.is_empty() {
+        return Err(AppError::new(
+            "TEMPLATE_ERROR",
+            &format!(
+                "Template '{}' uses undeclared parameters: {}",
+                template.id,
+                undeclared.into_iter().collect::<Vec<_>>().join(", ")
+            ),
+        ));
+    }
+
+    let serde_json::Value::Object(ref mut fields) = app else {
+        return Err(AppError::new(
+            "TEMPLATE_ERROR",
+            &format!("Template '{}' app must be an object", template.id),
+        ));
+    };
+
+    coerce_field_types(fields, &template.id)?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let name = params
+        .get("name")
+        .filter(|n| !n.trim().is_empty())
+        .cloned()
+        .unwrap_or_else(|| template.name.clone());
+
+    fields.insert("id".to_string(), serde_json::json!(uuid::Uuid::new_v4().to_string()));
+    fields.insert("name".to_string(), serde_json::json!(name));
+    fields.insert("createdAt".to_string(), serde_json::json!(now));
+    fields.insert("updatedAt".to_string(), serde_json::json!(now));
+
+    serde_json::from_value(app).map_err(|e| {
+        AppError::new(
+            "TEMPLATE_ERROR",
+            &format!("Template '{}' doesn't produce a valid app: {}", template.id, e),
+        )
+    })
+}
+
+/// Replace `{{param}}` placeholders in every string, collecting unknown parameter names
+///
+/// `${VAR}` launch-time interpolation is left untouched.
+fn substitute_placeholders(
+    value: &mut serde_json::Value,
+    values: &HashMap<&str, &str>,
+    undeclared: &mut BTreeSet<String>,
+) {
+    match value {
+        serde_json::Value::String(text) => {
+            let mut result = String::with_capacity(text.len());
+            let mut rest = text.as_str();
+
+            while let Some(start) = rest.find("{{") {
+                let Some(len) = rest[start + 2..].find("}}") else {
+                    break;
+                };
+                let name = rest[start + 2..start + 2 + len].trim();
+
+                result.push_str(&rest[..start]);
+                match values.get(name) {
+                    Some(replacement) => result.push_str(replacement),
+                    None => {
+                        undeclared.insert(name.to_string());
+                    }
+                }
+                rest = &rest[start + 2 + len + 2..];
+            }
+
+            result.push_str(rest);
+            *text = result;
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                substitute_placeholders(item, values, undeclared);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for item in map.values_mut() {
+                substitute_placeholders(item, values, undeclared);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Turn substituted strings back into numbers and booleans; empty strings become unset
+fn coerce_field_types(
+    fields: &mut serde_json::Map<String, serde_json::Value>,
+    template_id: &str,
+) -> AppResult<()> {
+    let invalid = |field: &str, value: &str, expected: &str| {
+        AppError::new(
+            "TEMPLATE_PARAMETER_ERROR",
+            &format!(
+                "Template '{}' field {} must be {}, got '{}'",
+                template_id, field, expected, value
+            ),
+        )
+    };
+
+    for field in NUMERIC_FIELDS {
+        if let Some(serde_json::Value::String(raw)) = fields.get(field).cloned() {
+            let raw = raw.trim();
+            if raw.is_empty() {
+                fields.remove(field);
+                continue;
+            }
+            let number: u64 = raw.parse().map_err(|_| invalid(field, raw, "a number"))?;
+            fields.insert(field.to_string(), serde_json::json!(number));
+        }
+    }
+
+    for field in BOOLEAN_FIELDS {
+        if let Some(serde_json::Value::String(raw)) = fields.get(field).cloned() {
+            let value = match raw.trim().to_ascii_lowercase().as_str() {
+                "" => {
+                    fields.remove(field);
+                    continue;
+                }
+                "true" | "yes" | "1" => true,
+                "false" | "no" | "0" => false,
+                _ => return Err(invalid(field, &raw, "true or false")),
+            };
+            fields.insert(field.to_string(), serde_json::json!(value));
+        }
+    }
+
+    // Optional string fields left empty by blank parameters shouldn't be saved as ""
+    fields.retain(|_, value| !matches!(value, serde_json::Value::String(s) if s.is_empty()));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn template(parameters: serde_json::Value, app: serde_json::Value) -> AppTemplate {
+        serde_json::from_value(json!({ "id": "test", "name": "Test", "parameters": parameters, "app": app })).unwrap()
+    }
+
+    fn params(values: &[(&str, &str)]) -> HashMap<String, String> {
+        values.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn builtin_templates_instantiate() {
+        let templates: Vec<AppTemplate> = serde_json::from_str(BUILTIN_TEMPLATES).unwrap();
+        for template in &templates {
+            let app = instantiate_template(template, &params(&[("directory", "/srv/app")])).unwrap();
+            assert_eq!(app.working_directory.as_deref(), Some("/srv/app"), "{}", template.id);
+            assert!(!app.launch_commands.unwrap().contains("{{"), "{}", template.id);
+        }
+    }
+
+    #[test]
+    fn placeholders_are_substituted() {
+        let template = template(
+            json!([
+                { "name": "directory", "required": true },
+                { "name": "port", "default": "3000" },
+                { "name": "flags" }
+            ]),
+            json!({
+                "launchCommands": "npm run dev -- --port {{ port }} {{flags}}\necho ${HOME} {{port}}",
+                "workingDirectory": "{{directory}}",
+                "environmentVariables": { "PORT": "{{port}}" },
+                "tags": ["{{port}}", "web"],
+                "url": "{{flags}}"
+            }),
+        );
+
+        let app = instantiate_template(&template, &params(&[("directory", "/srv"), ("port", " "), ("name", "Web")])).unwrap();
+        assert_eq!(app.name, "Web");
+        assert_eq!(app.launch_commands.as_deref(), Some("npm run dev -- --port 3000 \necho ${HOME} 3000"));
+        assert_eq!(app.working_directory.as_deref(), Some("/srv"));
+        assert_eq!(app.environment_variables.unwrap()["PORT"], "3000");
+        assert_eq!(app.tags, Some(vec!["3000".to_string(), "web".to_string()]));
+        // A blank optional parameter leaves the field unset rather than ""
+        assert_eq!(app.url, None);
+    }
+
+    #[test]
+    fn unterminated_placeholders_are_kept() {
+        let template = template(json!([]), json!({ "launchCommands": "echo {{not closed" }));
+        let app = instantiate_template(&template, &HashMap::new()).unwrap();
+        assert_eq!(app.launch_commands.as_deref(), Some("echo {{not closed"));
+        assert_eq!(app.name, "Test");
+    }
+
+    #[test]
+    fn missing_and_undeclared_parameters() {
+        let template = template(
+            json!([{ "name": "directory", "required": true }, { "name": "port", "required": true }]),
+            json!({ "workingDirectory": "{{directory}}", "url": "http://{{host}}:{{port}}/{{other}}" }),
+        );
+        let error = instantiate_template(&template, &params(&[("directory", "  ")])).unwrap_err();
+        assert_eq!(error.code, "TEMPLATE_PARAMETER_ERROR");
+        assert_eq!(error.message, "Missing required template parameters: directory, port");
+
+        let error = instantiate_template(&template, &params(&[("directory", "/srv"), ("port", "1")])).unwrap_err();
+        assert_eq!(error.code, "TEMPLATE_ERROR");
+        assert_eq!(error.message, "Template 'test' uses undeclared parameters: host, other");
+    }
+
+    #[test]
+    fn fields_are_coerced() {
+        let template = template(
+            json!([{ "name": "port" }, { "name": "open" }, { "name": "delay" }]),
+            json!({
+                "launchCommands": "serve",
+                "portToCheck": " {{port}} ",
+                "autoLaunchBrowser": "{{open}}",
+                "browserDelay": "{{delay}}"
+            }),
+        );
+
+        let app = instantiate_template(&template, &params(&[("port", "8080"), ("open", "Yes"), ("delay", "")])).unwrap();
+        assert_eq!(app.port_to_check, Some(8080));
+        assert_eq!(app.auto_launch_browser, Some(true));
+        assert_eq!(app.browser_delay, None);
+
+        let app = instantiate_template(&template, &params(&[("open", "0")])).unwrap();
+        assert_eq!((app.port_to_check, app.auto_launch_browser), (None, Some(false)));
+
+        let error = instantiate_template(&template, &params(&[("port", "80a")])).unwrap_err();
+        assert_eq!(error.message, "Template 'test' field portToCheck must be a number, got '80a'");
+        let error = instantiate_template(&template, &params(&[("open", "maybe")])).unwrap_err();
+        assert_eq!(error.message, "Template 'test' field autoLaunchBrowser must be true or false, got 'maybe'");
+        // In range for the coercion but not for the field
+        let error = instantiate_template(&template, &params(&[("port", "70000")])).unwrap_err();
+        assert_eq!(error.code, "TEMPLATE_ERROR");
+    }
+}
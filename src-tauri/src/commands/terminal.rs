@@ -1,14 +1,93 @@
-use crate::models::TerminalInfo;
+use crate::commands::config::read_global_config;
+use crate::commands::process::platform_utils;
+use crate::commands::shell_env::login_shell;
+use crate::commands::shell_quote::ShellDialect;
+use crate::commands::version_managers::get_version_manager_init;
+use crate::models::{CustomShell, TerminalDetection, TerminalInfo};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::State;
 
 #[cfg(windows)]
 #[allow(unused_imports)]
 use std::os::windows::process::CommandExt;
 
+/// Terminal types understood by `get_terminal_command`
+pub(crate) const TERMINAL_TYPES: [&str; 12] = [
+    "cmd", "powershell", "pwsh", "gitbash", "wsl", "bash", "zsh", "fish", "sh", "nu", "xonsh", "elvish",
+];
+
+/// Printed around launch commands run inline so the output reader can follow along, e.g.
+/// `__ODDLAUNCHER_STEP__ start 1` or `__ODDLAUNCHER_STEP__ end 1 2`
+pub const STEP_MARKER: &str = "__ODDLAUNCHER_STEP__";
+
+/// How long a shell may take to print its version
+const VERSION_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Terminal types that only exist on Windows
+pub(crate) const WINDOWS_TERMINAL_TYPES: [&str; 4] = ["cmd", "powershell", "gitbash", "wsl"];
+
+/**
+ * Cached terminal detection, shared across commands
+ *
+ * Asking every shell for its version takes a moment, so detection runs once and again on refresh.
+ */
+pub struct TerminalCache {
+    detected: Mutex<Option<TerminalDetection>>,
+}
+
+impl Default for TerminalCache {
+    fn default() -> Self {
+        Self {
+            detected: Mutex::new(None),
+        }
+    }
+}
+
+impl TerminalCache {
+    /// Detection results, detecting on first use
+    pub async fn get(&self) -> TerminalDetection {
+        if let Some(detected) = self.detected.lock().unwrap().clone() {
+            return detected;
+        }
+        self.refresh().await
+    }
+
+    /// Detect again, e.g. after the user installed a shell
+    pub async fn refresh(&self) -> TerminalDetection {
+        let detected = detect_terminals().await;
+        *self.detected.lock().unwrap() = Some(detected.clone());
+        detected
+    }
+}
+
 /**
  * Detect available terminals on the current system
  */
 #[tauri::command]
-pub async fn detect_available_terminals() -> Result<Vec<TerminalInfo>, String> {
+pub async fn detect_available_terminals(cache: State<'_, TerminalCache>) -> Result<Vec<TerminalInfo>, String> {
+    Ok(cache.get().await.terminals)
+}
+
+/**
+ * Get everything terminal detection found: shells, the login shell, Windows Terminal and WSL distros
+ */
+#[tauri::command]
+pub async fn get_terminal_detection(cache: State<'_, TerminalCache>) -> Result<TerminalDetection, String> {
+    Ok(cache.get().await)
+}
+
+/**
+ * Detect terminals again instead of using the cached results
+ */
+#[tauri::command]
+pub async fn refresh_terminal_detection(cache: State<'_, TerminalCache>) -> Result<TerminalDetection, String> {
+    log::info!("Refreshing terminal detection");
+    Ok(cache.refresh().await)
+}
+
+async fn detect_terminals() -> TerminalDetection {
     let mut terminals = Vec::new();
 
     if cfg!(target_os = "windows") {
@@ -16,8 +95,38 @@ pub async fn detect_available_terminals() -> Result<Vec<TerminalInfo>, String> {
     } else {
         terminals.extend(detect_unix_terminals().await);
     }
+    terminals.extend(detect_other_shells().await);
+
+    // User-defined shells from the settings
+    match read_global_config() {
+        Ok(config) => terminals.extend(detect_custom_shells(&config.settings.custom_shells).await),
+        Err(e) => log::warn!("Failed to read custom shells: {}", e.message),
+    }
+
+    let default_shell = login_shell();
+    if let Some(ref shell) = default_shell {
+        mark_default_shell(&mut terminals, shell);
+    }
+
+    let (windows_terminal, wsl_distros) = if cfg!(target_os = "windows") {
+        let windows_terminal = which::which("wt").ok().map(|p| p.to_string_lossy().to_string());
+        (windows_terminal, list_wsl_distros().await)
+    } else {
+        (None, Vec::new())
+    };
 
-    Ok(terminals)
+    log::info!(
+        "Detected {} of {} terminals",
+        terminals.iter().filter(|t| t.available).count(),
+        terminals.len()
+    );
+    TerminalDetection {
+        terminals,
+        default_shell,
+        windows_terminal,
+        wsl_distros,
+        detected_at: chrono::Utc::now().to_rfc3339(),
+    }
 }
 
 /**
@@ -27,53 +136,31 @@ async fn detect_windows_terminals() -> Vec<TerminalInfo> {
     let mut terminals = Vec::new();
 
     // Command Prompt - always available on Windows
-    terminals.push(TerminalInfo {
-        id: "cmd".to_string(),
-        name: "Command Prompt".to_string(),
-        executable: "cmd.exe".to_string(),
-        available: true,
-        platform: "windows".to_string(),
-    });
-
-    // PowerShell - check if available
-    let powershell_available = which::which("powershell").is_ok();
-    terminals.push(TerminalInfo {
-        id: "powershell".to_string(),
-        name: "PowerShell".to_string(),
-        executable: "powershell.exe".to_string(),
-        available: powershell_available,
-        platform: "windows".to_string(),
-    });
-
-    // PowerShell Core (pwsh) - check if available
-    let pwsh_available = which::which("pwsh").is_ok();
-    terminals.push(TerminalInfo {
-        id: "pwsh".to_string(),
-        name: "PowerShell Core".to_string(),
-        executable: "pwsh.exe".to_string(),
-        available: pwsh_available,
-        platform: "windows".to_string(),
-    });
+    let mut cmd = probe_shell("cmd", "Command Prompt", "cmd.exe", &["/c", "ver"]).await;
+    cmd.available = true;
+    terminals.push(cmd);
+
+    // Windows PowerShell has no --version
+    terminals.push(
+        probe_shell(
+            "powershell",
+            "PowerShell",
+            "powershell.exe",
+            &["-NoProfile", "-Command", "$PSVersionTable.PSVersion.ToString()"],
+        )
+        .await,
+    );
+
+    // PowerShell Core (pwsh)
+    terminals.push(probe_shell("pwsh", "PowerShell Core", "pwsh.exe", &["--version"]).await);
 
     // Git Bash - check if available and verify it's Git Bash
-    let git_bash_available = detect_git_bash().await;
-    terminals.push(TerminalInfo {
-        id: "gitbash".to_string(),
-        name: "Git Bash".to_string(),
-        executable: "bash.exe".to_string(),
-        available: git_bash_available,
-        platform: "windows".to_string(),
-    });
+    let mut git_bash = probe_shell("gitbash", "Git Bash", "bash.exe", &["--version"]).await;
+    git_bash.available = detect_git_bash().await;
+    terminals.push(git_bash);
 
-    // WSL - check if available
-    let wsl_available = which::which("wsl").is_ok();
-    terminals.push(TerminalInfo {
-        id: "wsl".to_string(),
-        name: "Windows Subsystem for Linux".to_string(),
-        executable: "wsl.exe".to_string(),
-        available: wsl_available,
-        platform: "windows".to_string(),
-    });
+    // WSL - `wsl --version` needs a recent WSL from the Microsoft Store
+    terminals.push(probe_shell("wsl", "Windows Subsystem for Linux", "wsl.exe", &["--version"]).await);
 
     terminals
 }
@@ -82,54 +169,194 @@ async fn detect_windows_terminals() -> Vec<TerminalInfo> {
  * Detect available Unix terminals
  */
 async fn detect_unix_terminals() -> Vec<TerminalInfo> {
+    let shells = [("bash", "Bash"), ("zsh", "Zsh"), ("fish", "Fish"), ("sh", "Sh")];
     let mut terminals = Vec::new();
-    let platform = if cfg!(target_os = "macos") {
-        "macos"
-    } else {
-        "linux"
+
+    for (id, name) in shells {
+        // sh has no --version; it's usually another shell under a different name
+        let version_args: &[&str] = if id == "sh" { &[] } else { &["--version"] };
+        terminals.push(probe_shell(id, name, id, version_args).await);
+    }
+
+    terminals
+}
+
+/**
+ * Detect shells that run on any platform
+ */
+async fn detect_other_shells() -> Vec<TerminalInfo> {
+    let shells = [("nu", "Nushell"), ("xonsh", "Xonsh"), ("elvish", "Elvish")];
+    let mut terminals = Vec::new();
+
+    for (id, name) in shells {
+        terminals.push(probe_shell(id, name, id, &["--version"]).await);
+    }
+
+    terminals
+}
+
+/**
+ * Report user-defined shells from the settings
+ */
+async fn detect_custom_shells(custom_shells: &[CustomShell]) -> Vec<TerminalInfo> {
+    let mut terminals = Vec::new();
+
+    for shell in custom_shells {
+        if TERMINAL_TYPES.contains(&shell.id.as_str()) {
+            log::warn!("Ignoring custom shell '{}': the id is taken by a built-in terminal type", shell.id);
+            continue;
+        }
+        let version_args: Vec<&str> = match shell.version_args {
+            Some(ref args) => args.iter().map(String::as_str).collect(),
+            None => vec!["--version"],
+        };
+        terminals.push(probe_shell(&shell.id, &shell.name, &shell.executable, &version_args).await);
+    }
+
+    terminals
+}
+
+/**
+ * Look up a shell on PATH and ask it for its version
+ *
+ * No version arguments means the shell isn't asked.
+ */
+async fn probe_shell(id: &str, name: &str, executable: &str, version_args: &[&str]) -> TerminalInfo {
+    let path = which::which(executable).ok().map(|p| p.to_string_lossy().to_string());
+    let version = match path {
+        Some(ref path) if !version_args.is_empty() => shell_version(path, version_args).await,
+        _ => None,
     };
 
-    // Bash
-    let bash_available = which::which("bash").is_ok();
-    terminals.push(TerminalInfo {
-        id: "bash".to_string(),
-        name: "Bash".to_string(),
-        executable: "bash".to_string(),
-        available: bash_available,
-        platform: platform.to_string(),
-    });
+    TerminalInfo {
+        id: id.to_string(),
+        name: name.to_string(),
+        executable: executable.to_string(),
+        available: path.is_some(),
+        platform: std::env::consts::OS.to_string(),
+        path,
+        version,
+        is_default: false,
+    }
+}
 
-    // Zsh
-    let zsh_available = which::which("zsh").is_ok();
-    terminals.push(TerminalInfo {
-        id: "zsh".to_string(),
-        name: "Zsh".to_string(),
-        executable: "zsh".to_string(),
-        available: zsh_available,
-        platform: platform.to_string(),
-    });
+/**
+ * First line a shell prints when asked for its version
+ */
+async fn shell_version(executable: &str, version_args: &[&str]) -> Option<String> {
+    let mut cmd = tokio::process::Command::new(executable);
+    cmd.args(version_args).stdin(std::process::Stdio::null()).kill_on_drop(true);
+
+    // Hide console window on Windows
+    #[cfg(windows)]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
 
-    // Fish
-    let fish_available = which::which("fish").is_ok();
-    terminals.push(TerminalInfo {
-        id: "fish".to_string(),
-        name: "Fish".to_string(),
-        executable: "fish".to_string(),
-        available: fish_available,
-        platform: platform.to_string(),
-    });
+    let output = tokio::time::timeout(VERSION_TIMEOUT, cmd.output()).await.ok()?.ok()?;
+    // Some shells print their version on stderr
+    [output.stdout, output.stderr]
+        .iter()
+        .flat_map(|stream| decode_output(stream).lines().map(str::to_string).collect::<Vec<_>>())
+        .map(|line| line.trim().to_string())
+        .find(|line| !line.is_empty())
+}
+
+/**
+ * Mark the terminal the login shell runs as the default
+ *
+ * Matched by resolved path first, so /bin/sh linking to dash isn't taken for another shell, then by name.
+ */
+fn mark_default_shell(terminals: &mut [TerminalInfo], login_shell: &str) {
+    let resolved = std::fs::canonicalize(login_shell).ok();
+    let name = Path::new(login_shell).file_name().map(|n| n.to_string_lossy().to_string());
 
-    // Sh (standard shell)
-    let sh_available = which::which("sh").is_ok();
-    terminals.push(TerminalInfo {
-        id: "sh".to_string(),
-        name: "Sh".to_string(),
-        executable: "sh".to_string(),
-        available: sh_available,
-        platform: platform.to_string(),
+    let by_path = terminals.iter().position(|t| {
+        let path = t.path.as_ref().and_then(|p| std::fs::canonicalize(p).ok());
+        t.available && path.is_some() && path == resolved
     });
+    let by_name = || {
+        terminals
+            .iter()
+            .position(|t| t.available && Some(&t.executable) == name.as_ref())
+    };
 
-    terminals
+    if let Some(index) = by_path.or_else(by_name) {
+        terminals[index].is_default = true;
+    }
+}
+
+/**
+ * Names of the installed WSL distributions, from `wsl -l -q`
+ */
+pub(crate) async fn list_wsl_distros() -> Vec<String> {
+    let mut cmd = tokio::process::Command::new("wsl.exe");
+    cmd.args(["-l", "-q"])
+        .env("WSL_UTF8", "1")
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true);
+
+    // Hide console window on Windows
+    #[cfg(windows)]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = match tokio::time::timeout(VERSION_TIMEOUT, cmd.output()).await {
+        Ok(Ok(output)) if output.status.success() => output,
+        _ => return Vec::new(),
+    };
+    decode_output(&output.stdout)
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/**
+ * WSL distribution and user launch commands run as; unset parts fall back to WSL's defaults
+ */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WslTarget {
+    pub distro: Option<String>,
+    pub user: Option<String>,
+}
+
+impl WslTarget {
+    /// An app's WSL options, taking the distribution from a `\\wsl.localhost\<distro>\` working directory if unset
+    pub fn for_app(wsl_distro: Option<&str>, wsl_user: Option<&str>, working_dir: Option<&str>) -> Self {
+        let non_empty = |value: Option<&str>| value.map(str::trim).filter(|v| !v.is_empty()).map(str::to_string);
+        Self {
+            distro: non_empty(wsl_distro).or_else(|| working_dir.and_then(platform_utils::wsl_network_distro)),
+            user: non_empty(wsl_user),
+        }
+    }
+
+    /// `wsl.exe` options selecting the distribution and user, placed before the command
+    pub fn args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(ref distro) = self.distro {
+            args.extend(["-d".to_string(), distro.clone()]);
+        }
+        if let Some(ref user) = self.user {
+            args.extend(["-u".to_string(), user.clone()]);
+        }
+        args
+    }
+}
+
+/**
+ * Decode process output, including the UTF-16 wsl.exe prints when WSL_UTF8 isn't supported
+ */
+fn decode_output(bytes: &[u8]) -> String {
+    // UTF-8 text never contains NUL bytes, while ASCII in UTF-16 is every other byte
+    if bytes.contains(&0) {
+        let units: Vec<u16> = bytes.chunks_exact(2).map(|pair| u16::from_le_bytes([pair[0], pair[1]])).collect();
+        return String::from_utf16_lossy(&units).trim_start_matches('\u{feff}').to_string();
+    }
+    String::from_utf8_lossy(bytes).to_string()
 }
 
 /**
@@ -168,13 +395,15 @@ async fn detect_git_bash() -> bool {
  * Get the appropriate terminal command for executing user commands
  */
 pub fn get_terminal_command(terminal_type: &str, user_commands: &str, working_dir: Option<&str>) -> Vec<String> {
+    let version_manager_init = get_version_manager_init(terminal_type, working_dir, user_commands);
+
     match terminal_type {
         "cmd" => {
             let mut args = vec!["cmd.exe".to_string(), "/c".to_string()];
             let mut script = String::new();
             
             if let Some(dir) = working_dir {
-                script.push_str(&format!("cd /d \"{}\" && ", dir));
+                script.push_str(&format!("cd /d {} && ", ShellDialect::Cmd.quote(dir)));
             }
             
             // Split user commands and echo each one before executing
@@ -185,7 +414,12 @@ pub fn get_terminal_command(terminal_type: &str, user_commands: &str, working_di
                 .collect();
 
             for (i, command) in user_command_lines.iter().enumerate() {
-                script.push_str(&format!("echo ^> {} && {}", command, command));
+                script.push_str(&format!(
+                    "{} && {} && {}",
+                    step_start(ShellDialect::Cmd, i),
+                    ShellDialect::Cmd.echo(&format!("> {}", command)),
+                    command
+                ));
                 if i < user_command_lines.len() - 1 {
                     script.push_str(" && ");
                 }
@@ -197,7 +431,10 @@ pub fn get_terminal_command(terminal_type: &str, user_commands: &str, working_di
         "powershell" => {
             let mut script = String::new();
             if let Some(dir) = working_dir {
-                script.push_str(&format!("Set-Location -Path '{}'; ", dir));
+                script.push_str(&format!("Set-Location -LiteralPath {}; ", ShellDialect::PowerShell.quote(dir)));
+            }
+            for line in &version_manager_init {
+                script.push_str(&format!("{}; ", line));
             }
             
             // Split user commands and echo each one before executing
@@ -208,7 +445,13 @@ pub fn get_terminal_command(terminal_type: &str, user_commands: &str, working_di
                 .collect();
 
             for (i, command) in user_command_lines.iter().enumerate() {
-                script.push_str(&format!("Write-Host 'PS> {}'; {}", command, command));
+                script.push_str(&format!(
+                    "{}; {}; {}; {}",
+                    step_start(ShellDialect::PowerShell, i),
+                    ShellDialect::PowerShell.echo(&format!("PS> {}", command)),
+                    command,
+                    powershell_step_end(i)
+                ));
                 if i < user_command_lines.len() - 1 {
                     script.push_str("; ");
                 }
@@ -219,7 +462,10 @@ pub fn get_terminal_command(terminal_type: &str, user_commands: &str, working_di
         "pwsh" => {
             let mut script = String::new();
             if let Some(dir) = working_dir {
-                script.push_str(&format!("Set-Location -Path '{}'; ", dir));
+                script.push_str(&format!("Set-Location -LiteralPath {}; ", ShellDialect::PowerShell.quote(dir)));
+            }
+            for line in &version_manager_init {
+                script.push_str(&format!("{}; ", line));
             }
             
             // Split user commands and echo each one before executing
@@ -230,7 +476,13 @@ pub fn get_terminal_command(terminal_type: &str, user_commands: &str, working_di
                 .collect();
 
             for (i, command) in user_command_lines.iter().enumerate() {
-                script.push_str(&format!("Write-Host 'PS> {}'; {}", command, command));
+                script.push_str(&format!(
+                    "{}; {}; {}; {}",
+                    step_start(ShellDialect::PowerShell, i),
+                    ShellDialect::PowerShell.echo(&format!("PS> {}", command)),
+                    command,
+                    powershell_step_end(i)
+                ));
                 if i < user_command_lines.len() - 1 {
                     script.push_str("; ");
                 }
@@ -243,8 +495,9 @@ pub fn get_terminal_command(terminal_type: &str, user_commands: &str, working_di
             if let Some(dir) = working_dir {
                 // Convert Windows path to Unix-style for Git Bash if needed
                 let unix_dir = convert_to_unix_path(dir);
-                script.push_str(&format!("cd '{}' && ", unix_dir));
+                script.push_str(&format!("cd {} && ", ShellDialect::Posix.quote(&unix_dir)));
             }
+            script.push_str(&init_group(ShellDialect::Posix, &version_manager_init));
             
             // Split user commands and echo each one before executing
             let user_command_lines: Vec<&str> = user_commands
@@ -254,7 +507,12 @@ pub fn get_terminal_command(terminal_type: &str, user_commands: &str, working_di
                 .collect();
 
             for (i, command) in user_command_lines.iter().enumerate() {
-                script.push_str(&format!("echo '$ {}' && {}", command, command));
+                script.push_str(&format!(
+                    "{} && {} && {}",
+                    step_start(ShellDialect::Posix, i),
+                    ShellDialect::Posix.echo(&format!("$ {}", command)),
+                    command
+                ));
                 if i < user_command_lines.len() - 1 {
                     script.push_str(" && ");
                 }
@@ -264,37 +522,15 @@ pub fn get_terminal_command(terminal_type: &str, user_commands: &str, working_di
         },
         "wsl" => {
             // Create a complete bash script with clean environment to avoid Windows PATH conflicts
-            let mut script_lines = vec![
-                "#!/bin/bash".to_string(),
-                "set -e".to_string(),
-                "".to_string(),
-                "# Clean PATH to avoid Windows executable conflicts".to_string(),
-                "export PATH=\"$(echo $PATH | tr ':' '\\n' | grep -v '^/mnt/c' | tr '\\n' ':' | sed 's/:$//')\"".to_string(),
-                "".to_string(),
-                "# Add standard Linux paths".to_string(),
-                "export PATH=\"/usr/local/bin:/usr/bin:/bin:/usr/local/sbin:/usr/sbin:/sbin:$PATH\"".to_string(),
-                "".to_string(),
-                "# Add user bin directories".to_string(),
-                "[ -d \"$HOME/.local/bin\" ] && export PATH=\"$HOME/.local/bin:$PATH\"".to_string(),
-                "[ -d \"$HOME/bin\" ] && export PATH=\"$HOME/bin:$PATH\"".to_string(),
-                "".to_string(),
-                "# Initialize shell environment".to_string(),
-                "source /etc/profile 2>/dev/null || true".to_string(),
-                "source ~/.profile 2>/dev/null || true".to_string(),
-                "source ~/.bashrc 2>/dev/null || true".to_string(),
-                "".to_string(),
-                "# Initialize version managers".to_string(),
-                "if [ -f ~/.nvm/nvm.sh ]; then source ~/.nvm/nvm.sh; fi".to_string(),
-                "if command -v rbenv >/dev/null 2>&1; then eval \"$(rbenv init -)\"; fi".to_string(),
-                "".to_string(),
-            ];
+            let mut script_lines = wsl_preamble();
 
             if let Some(dir) = working_dir {
                 // Convert Windows path to WSL path if needed
                 let wsl_dir = convert_to_wsl_path(dir);
-                script_lines.push(format!("cd '{}'", wsl_dir));
+                script_lines.push(format!("cd {}", ShellDialect::Posix.quote(&wsl_dir)));
                 script_lines.push("".to_string());
             }
+            script_lines.extend(version_manager_init);
 
             // Split user commands by lines and echo each one before executing (like typing in terminal)
             let user_command_lines: Vec<&str> = user_commands
@@ -305,7 +541,8 @@ pub fn get_terminal_command(terminal_type: &str, user_commands: &str, working_di
 
             for (i, command) in user_command_lines.iter().enumerate() {
                 // Show the command being executed (like terminal prompt)
-                script_lines.push(format!("echo '$ {}'", command));
+                script_lines.push(step_start(ShellDialect::Posix, i));
+                script_lines.push(ShellDialect::Posix.echo(&format!("$ {}", command)));
                 script_lines.push(command.to_string());
                 if i < user_command_lines.len() - 1 {
                     script_lines.push("".to_string());
@@ -315,6 +552,26 @@ pub fn get_terminal_command(terminal_type: &str, user_commands: &str, working_di
             let complete_script = script_lines.join("\n");
             vec!["wsl.exe".to_string(), "bash".to_string(), "-c".to_string(), complete_script]
         },
+        "nu" | "xonsh" | "elvish" => {
+            // One statement per line; xonsh needs telling to stop at the first failed command
+            let dialect = ShellDialect::for_terminal(terminal_type);
+            let mut script_lines = Vec::new();
+            if terminal_type == "xonsh" {
+                script_lines.push("$RAISE_SUBPROC_ERROR = True".to_string());
+            }
+            if let Some(dir) = working_dir {
+                script_lines.push(format!("cd {}", dialect.quote(dir)));
+            }
+
+            let user_command_lines = user_commands.lines().map(|line| line.trim()).filter(|line| !line.is_empty());
+            for (i, command) in user_command_lines.enumerate() {
+                script_lines.push(step_start(dialect, i));
+                script_lines.push(dialect.echo(&format!("$ {}", command)));
+                script_lines.push(command.to_string());
+            }
+
+            vec![terminal_type.to_string(), "-c".to_string(), script_lines.join("\n")]
+        },
         "bash" | "zsh" | "fish" | "sh" | _ => {
             // Default Unix shell behavior
             let shell = match terminal_type {
@@ -324,10 +581,12 @@ pub fn get_terminal_command(terminal_type: &str, user_commands: &str, working_di
                 _ => "bash", // fallback to bash
             };
 
+            let dialect = ShellDialect::for_terminal(shell);
             let mut script = String::new();
             if let Some(dir) = working_dir {
-                script.push_str(&format!("cd '{}' && ", dir));
+                script.push_str(&format!("cd {} && ", dialect.quote(dir)));
             }
+            script.push_str(&init_group(dialect, &version_manager_init));
             
             // Split user commands and echo each one before executing
             let user_command_lines: Vec<&str> = user_commands
@@ -337,7 +596,12 @@ pub fn get_terminal_command(terminal_type: &str, user_commands: &str, working_di
                 .collect();
 
             for (i, command) in user_command_lines.iter().enumerate() {
-                script.push_str(&format!("echo '$ {}' && {}", command, command));
+                script.push_str(&format!(
+                    "{} && {} && {}",
+                    step_start(dialect, i),
+                    dialect.echo(&format!("$ {}", command)),
+                    command
+                ));
                 if i < user_command_lines.len() - 1 {
                     script.push_str(" && ");
                 }
@@ -348,6 +612,260 @@ pub fn get_terminal_command(terminal_type: &str, user_commands: &str, working_di
     }
 }
 
+/**
+ * Launch commands written out as a script file for a terminal type
+ */
+#[derive(Debug, Clone)]
+pub struct TerminalScript {
+    /// File extension the shell expects, without the dot
+    pub extension: &'static str,
+    pub content: String,
+}
+
+/**
+ * Get a script that runs the user commands as written, for shell syntax that doesn't survive
+ * being joined into one line (heredocs, `if` blocks, line continuations, functions)
+ *
+ * Echo statements between the lines would end up inside heredocs and blocks, so instead of the
+ * inline mode's per-line echo the shell's own tracing prints each command before it runs, with the
+ * prompt made to look like the inline echo where the shell allows it (`$ ` for POSIX shells, `> `
+ * for cmd). PowerShell and fish trace in their own format. Nushell and Elvish can't trace at all,
+ * so the commands are printed once, a line at a time, before the script starts running them.
+ * For the same reason no step markers are printed: the whole script is one step.
+ */
+pub fn get_terminal_script(terminal_type: &str, user_commands: &str, working_dir: Option<&str>) -> TerminalScript {
+    let user_commands = user_commands.trim_end();
+    let version_manager_init = get_version_manager_init(terminal_type, working_dir, user_commands);
+
+    match terminal_type {
+        "cmd" => {
+            let mut lines = vec!["@echo off".to_string()];
+            if let Some(dir) = working_dir {
+                lines.push(format!("cd /d {} || exit /b 1", ShellDialect::Cmd.quote_in_script(dir)));
+            }
+            // With echo on, each command is shown after the prompt, so make the prompt look like the inline mode's
+            lines.push("prompt $G$S".to_string());
+            lines.push("echo on".to_string());
+            lines.push(user_commands.to_string());
+
+            // Batch files are read with Windows line endings in mind
+            TerminalScript {
+                extension: "cmd",
+                content: lines.join("\n").replace("\r\n", "\n").replace('\n', "\r\n") + "\r\n",
+            }
+        },
+        "powershell" | "pwsh" => {
+            let mut lines = Vec::new();
+            if let Some(dir) = working_dir {
+                lines.push(format!("Set-Location -LiteralPath {}", ShellDialect::PowerShell.quote(dir)));
+            }
+            lines.extend(version_manager_init);
+            lines.push("Set-PSDebug -Trace 1".to_string());
+            lines.push(user_commands.to_string());
+
+            TerminalScript {
+                extension: "ps1",
+                content: lines.join("\n") + "\n",
+            }
+        },
+        "nu" | "xonsh" | "elvish" => {
+            let dialect = ShellDialect::for_terminal(terminal_type);
+            let mut lines = Vec::new();
+            if terminal_type == "xonsh" {
+                lines.push("$RAISE_SUBPROC_ERROR = True".to_string());
+            }
+            if let Some(dir) = working_dir {
+                lines.push(format!("cd {}", dialect.quote(dir)));
+            }
+            // Only xonsh can trace commands as they run; the others list them up front
+            if terminal_type == "xonsh" {
+                lines.push("$XONSH_TRACE_SUBPROC = True".to_string());
+            } else {
+                let command_lines = user_commands.lines().map(|line| line.trim()).filter(|line| !line.is_empty());
+                lines.extend(command_lines.map(|command| dialect.echo(&format!("$ {}", command))));
+            }
+            lines.push(user_commands.to_string());
+
+            TerminalScript {
+                extension: match terminal_type {
+                    "nu" => "nu",
+                    "xonsh" => "xsh",
+                    _ => "elv",
+                },
+                content: lines.join("\n") + "\n",
+            }
+        },
+        "fish" => {
+            let mut lines = vec!["#!/usr/bin/env fish".to_string()];
+            if let Some(dir) = working_dir {
+                lines.push(format!("cd {}; or exit 1", ShellDialect::Fish.quote(dir)));
+            }
+            lines.extend(version_manager_init);
+            lines.push("set fish_trace 1".to_string());
+            lines.push(user_commands.to_string());
+
+            TerminalScript {
+                extension: "fish",
+                content: lines.join("\n") + "\n",
+            }
+        },
+        _ => {
+            let mut lines = match terminal_type {
+                "wsl" => wsl_preamble(),
+                "sh" => vec!["#!/bin/sh".to_string(), "set -e".to_string()],
+                "zsh" => vec!["#!/usr/bin/env zsh".to_string(), "set -e".to_string()],
+                _ => vec!["#!/usr/bin/env bash".to_string(), "set -e".to_string()],
+            };
+            if let Some(dir) = working_dir {
+                let dir = match terminal_type {
+                    "wsl" => convert_to_wsl_path(dir),
+                    "gitbash" => convert_to_unix_path(dir),
+                    _ => dir.to_string(),
+                };
+                lines.push(format!("cd {}", ShellDialect::Posix.quote(&dir)));
+            }
+            lines.extend(version_manager_init);
+            // Trace to stdout where bash allows it, so commands appear alongside their output
+            if terminal_type != "sh" && terminal_type != "zsh" {
+                lines.push("BASH_XTRACEFD=1".to_string());
+            }
+            lines.push("PS4='$ '".to_string());
+            lines.push("set -x".to_string());
+            lines.push(user_commands.to_string());
+
+            TerminalScript {
+                extension: "sh",
+                content: lines.join("\n") + "\n",
+            }
+        }
+    }
+}
+
+/**
+ * Statement printing the marker that step `index` (counting launch command lines from 0) is starting
+ */
+pub(crate) fn step_start(dialect: ShellDialect, index: usize) -> String {
+    dialect.echo(&format!("{} start {}", STEP_MARKER, index))
+}
+
+/**
+ * PowerShell statement printing how step `index` ended, since PowerShell carries on after a failed command
+ *
+ * `$?` covers cmdlets and `$LASTEXITCODE` has the exit code of native commands.
+ */
+fn powershell_step_end(index: usize) -> String {
+    format!(
+        "$oddlauncherOk = $?; Write-Output \"{} end {} $(if ($oddlauncherOk) {{ 0 }} elseif ($LASTEXITCODE) {{ $LASTEXITCODE }} else {{ 1 }})\"; $global:LASTEXITCODE = 0",
+        STEP_MARKER, index
+    )
+}
+
+/**
+ * Version manager initialization as one step of a `&&` chain, empty if there's none
+ */
+fn init_group(dialect: ShellDialect, init_lines: &[String]) -> String {
+    if init_lines.is_empty() {
+        return String::new();
+    }
+    match dialect {
+        ShellDialect::Fish => format!("begin; {}; true; end && ", init_lines.join("; ")),
+        _ => format!("{{ {}; true; }} && ", init_lines.join("; ")),
+    }
+}
+
+/**
+ * Get the command that runs a script from `get_terminal_script`
+ */
+pub fn get_terminal_script_command(terminal_type: &str, script_path: &str) -> Vec<String> {
+    match terminal_type {
+        // Quoted as one word for cmd.exe, which receives its arguments unescaped
+        "cmd" => vec!["cmd.exe".to_string(), "/d".to_string(), "/c".to_string(), format!("\"{}\"", script_path)],
+        "powershell" | "pwsh" => vec![
+            if terminal_type == "pwsh" { "pwsh.exe" } else { "powershell.exe" }.to_string(),
+            "-ExecutionPolicy".to_string(),
+            "Bypass".to_string(),
+            "-File".to_string(),
+            script_path.to_string(),
+        ],
+        "gitbash" => vec!["bash.exe".to_string(), convert_to_unix_path(script_path)],
+        "wsl" => vec!["wsl.exe".to_string(), "bash".to_string(), convert_to_wsl_path(script_path)],
+        "zsh" | "fish" | "sh" | "nu" | "xonsh" | "elvish" => vec![terminal_type.to_string(), script_path.to_string()],
+        _ => vec!["bash".to_string(), script_path.to_string()],
+    }
+}
+
+/**
+ * Get the command that runs user commands in a user-defined shell
+ *
+ * The working directory is left to the process, since there's no telling how the shell changes directory.
+ */
+pub fn get_custom_shell_command(shell: &CustomShell, user_commands: &str) -> Vec<String> {
+    let dialect = shell.quoting.as_deref().map_or(ShellDialect::Posix, ShellDialect::for_terminal);
+    let mut statements = Vec::new();
+
+    for command in user_commands.lines().map(|line| line.trim()).filter(|line| !line.is_empty()) {
+        if let Some(ref echo) = shell.echo {
+            statements.push(echo.replace("{command}", &dialect.quote(&format!("$ {}", command))));
+        }
+        statements.push(command.to_string());
+    }
+
+    let script = statements.join(shell.command_separator.as_deref().unwrap_or("\n"));
+    std::iter::once(shell.executable.clone())
+        .chain(shell.args.iter().map(|arg| arg.replace("{script}", &script)))
+        .collect()
+}
+
+/**
+ * Find the user-defined shell a terminal type refers to; built-in terminal types always win
+ */
+pub fn find_custom_shell<'a>(custom_shells: &'a [CustomShell], terminal_type: &str) -> Option<&'a CustomShell> {
+    if TERMINAL_TYPES.contains(&terminal_type) {
+        return None;
+    }
+    custom_shells.iter().find(|shell| shell.id == terminal_type)
+}
+
+/**
+ * Get a script file for a user-defined shell: the user commands as written, run as `<executable> <script>`
+ */
+pub fn get_custom_shell_script(user_commands: &str) -> TerminalScript {
+    TerminalScript {
+        extension: "txt",
+        content: user_commands.trim_end().to_string() + "\n",
+    }
+}
+
+/**
+ * Opening lines of WSL scripts: a Linux-only PATH and the user's shell environment
+ */
+fn wsl_preamble() -> Vec<String> {
+    vec![
+        "#!/bin/bash".to_string(),
+        "set -e".to_string(),
+        "".to_string(),
+        "# Clean PATH to avoid Windows executable conflicts".to_string(),
+        "export PATH=\"$(echo $PATH | tr ':' '\\n' | grep -v '^/mnt/c' | tr '\\n' ':' | sed 's/:$//')\"".to_string(),
+        "".to_string(),
+        "# Add standard Linux paths".to_string(),
+        "export PATH=\"/usr/local/bin:/usr/bin:/bin:/usr/local/sbin:/usr/sbin:/sbin:$PATH\"".to_string(),
+        "".to_string(),
+        "# Add user bin directories".to_string(),
+        "[ -d \"$HOME/.local/bin\" ] && export PATH=\"$HOME/.local/bin:$PATH\"".to_string(),
+        "[ -d \"$HOME/bin\" ] && export PATH=\"$HOME/bin:$PATH\"".to_string(),
+        "".to_string(),
+        "# Initialize shell environment".to_string(),
+        "source /etc/profile 2>/dev/null || true".to_string(),
+        "source ~/.profile 2>/dev/null || true".to_string(),
+        "source ~/.bashrc 2>/dev/null || true".to_string(),
+        "".to_string(),
+        "# Initialize version managers".to_string(),
+        "if [ -f ~/.nvm/nvm.sh ]; then source ~/.nvm/nvm.sh; fi".to_string(),
+        "if command -v rbenv >/dev/null 2>&1; then eval \"$(rbenv init -)\"; fi".to_string(),
+        "".to_string(),
+    ]
+}
+
 /**
  * Convert Windows path to Unix-style path for Git Bash
  */
@@ -375,3 +893,86 @@ fn convert_to_wsl_path(windows_path: &str) -> String {
         windows_path.replace('\\', "/")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMMANDS: &str = "cat <<EOF\nhello\nEOF\n\necho done\n";
+
+    #[test]
+    fn bash_script_traces_commands() {
+        let script = get_terminal_script("bash", COMMANDS, Some("/srv/it's here"));
+        assert_eq!(script.extension, "sh");
+        assert_eq!(
+            script.content,
+            "#!/usr/bin/env bash\nset -e\ncd '/srv/it'\\''s here'\nBASH_XTRACEFD=1\nPS4='$ '\nset -x\ncat <<EOF\nhello\nEOF\n\necho done\n"
+        );
+    }
+
+    #[test]
+    fn sh_script_traces_to_stderr() {
+        let script = get_terminal_script("sh", "echo hi", None);
+        assert_eq!(script.content, "#!/bin/sh\nset -e\nPS4='$ '\nset -x\necho hi\n");
+    }
+
+    #[test]
+    fn cmd_script_uses_crlf() {
+        let script = get_terminal_script("cmd", "echo one\necho two", Some(r"C:\100% done"));
+        assert_eq!(script.extension, "cmd");
+        assert_eq!(
+            script.content,
+            "@echo off\r\ncd /d \"C:\\100%% done\" || exit /b 1\r\nprompt $G$S\r\necho on\r\necho one\r\necho two\r\n"
+        );
+    }
+
+    #[test]
+    fn powershell_script() {
+        let script = get_terminal_script("pwsh", "Get-Date", Some("C:\\it's"));
+        assert_eq!(script.extension, "ps1");
+        assert_eq!(script.content, "Set-Location -LiteralPath 'C:\\it''s'\nSet-PSDebug -Trace 1\nGet-Date\n");
+    }
+
+    #[test]
+    fn fish_script() {
+        let script = get_terminal_script("fish", "echo hi", None);
+        assert_eq!(script.extension, "fish");
+        assert_eq!(script.content, "#!/usr/bin/env fish\nset fish_trace 1\necho hi\n");
+    }
+
+    #[test]
+    fn nu_and_elvish_list_commands() {
+        let script = get_terminal_script("nu", "ls\n\n  echo 'x'\n", Some("/srv"));
+        assert_eq!(script.extension, "nu");
+        assert_eq!(script.content, "cd r#'/srv'#\nprint r#'$ ls'#\nprint r#'$ echo 'x''#\nls\n\n  echo 'x'\n");
+
+        let script = get_terminal_script("elvish", "echo hi", None);
+        assert_eq!(script.extension, "elv");
+        assert_eq!(script.content, "echo '$ echo hi'\necho hi\n");
+    }
+
+    #[test]
+    fn xonsh_script_traces() {
+        let script = get_terminal_script("xonsh", "echo hi", None);
+        assert_eq!(script.extension, "xsh");
+        assert_eq!(script.content, "$RAISE_SUBPROC_ERROR = True\n$XONSH_TRACE_SUBPROC = True\necho hi\n");
+    }
+
+    #[test]
+    fn wsl_script_converts_dir() {
+        let script = get_terminal_script("wsl", "make", Some(r"C:\src\app"));
+        assert!(script.content.starts_with("#!/bin/bash\nset -e\n"));
+        assert!(script.content.ends_with("cd '/mnt/c/src/app'\nBASH_XTRACEFD=1\nPS4='$ '\nset -x\nmake\n"));
+    }
+
+    #[test]
+    fn script_commands() {
+        assert_eq!(get_terminal_script_command("bash", "/tmp/a.sh"), vec!["bash", "/tmp/a.sh"]);
+        assert_eq!(get_terminal_script_command("cmd", r"C:\a b.cmd"), vec!["cmd.exe", "/d", "/c", "\"C:\\a b.cmd\""]);
+        assert_eq!(get_terminal_script_command("wsl", r"C:\t\a.sh"), vec!["wsl.exe", "bash", "/mnt/c/t/a.sh"]);
+        assert_eq!(
+            get_terminal_script_command("powershell", "a.ps1"),
+            vec!["powershell.exe", "-ExecutionPolicy", "Bypass", "-File", "a.ps1"]
+        );
+    }
+}
@@ -0,0 +1,689 @@
+use crate::commands::config::read_global_config;
+use crate::commands::dev_env::detect_dev_environments;
+use crate::commands::environment::is_valid_name;
+use crate::commands::process::platform_utils;
+use crate::commands::terminal::{find_custom_shell, TERMINAL_TYPES, WINDOWS_TERMINAL_TYPES};
+use crate::models::app::{AppConfig, AppError, AppResult, AppType, CustomShell, DevEnvironment};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Longest app name accepted without a warning
+const MAX_NAME_LENGTH: usize = 100;
+
+/// Browser delays above this many seconds are almost certainly a mistake
+const MAX_REASONABLE_BROWSER_DELAY: u32 = 60;
+
+/**
+ * A single validation problem tied to a config field
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationIssue {
+    /// Field path in camelCase, e.g. `name` or `profiles[0].portToCheck`
+    pub field: String,
+    pub message: String,
+}
+
+/**
+ * Result of validating an app configuration
+ */
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationResult {
+    pub valid: bool,
+    /// Problems that prevent the app from being saved
+    pub errors: Vec<ValidationIssue>,
+    /// Likely mistakes that don't block saving
+    pub warnings: Vec<ValidationIssue>,
+}
+
+impl ValidationResult {
+    fn error(&mut self, field: &str, message: &str) {
+        self.errors.push(ValidationIssue {
+            field: field.to_string(),
+            message: message.to_string(),
+        });
+    }
+
+    fn warning(&mut self, field: &str, message: &str) {
+        self.warnings.push(ValidationIssue {
+            field: field.to_string(),
+            message: message.to_string(),
+        });
+    }
+}
+
+/// Validate an app configuration against the saved apps without saving it
+#[tauri::command]
+pub async fn validate_app_config(app_config: AppConfig) -> AppResult<ValidationResult> {
+    let config = read_global_config()?;
+    Ok(validate_app(&app_config, &config.apps, &config.settings.custom_shells))
+}
+
+/// Validate an app, returning a VALIDATION_ERROR with field-level details if it has errors
+///
+/// Warnings are logged and returned on success.
+pub(crate) fn ensure_valid_app(
+    app: &AppConfig,
+    others: &[AppConfig],
+    custom_shells: &[CustomShell],
+) -> AppResult<ValidationResult> {
+    let result = validate_app(app, others, custom_shells);
+
+    if !result.valid {
+        let mut details = HashMap::new();
+        details.insert("appId".to_string(), serde_json::json!(app.id));
+        details.insert("errors".to_string(), serde_json::json!(result.errors));
+        details.insert("warnings".to_string(), serde_json::json!(result.warnings));
+
+        let summary = result
+            .errors
+            .iter()
+            .map(|issue| format!("{}: {}", issue.field, issue.message))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        return Err(AppError::new(
+            "VALIDATION_ERROR",
+            &format!("Invalid configuration for '{}': {}", app.name, summary),
+        )
+        .with_details(details));
+    }
+
+    for warning in &result.warnings {
+        log::warn!("App '{}' {}: {}", app.name, warning.field, warning.message);
+    }
+
+    Ok(result)
+}
+
+/// Check the user-defined shells in the settings, returning a VALIDATION_ERROR listing every problem
+pub(crate) fn ensure_valid_custom_shells(custom_shells: &[CustomShell]) -> AppResult<()> {
+    let result = validate_custom_shells(custom_shells);
+    if result.valid {
+        return Ok(());
+    }
+
+    let mut details = HashMap::new();
+    details.insert("errors".to_string(), serde_json::json!(result.errors));
+    let summary = result
+        .errors
+        .iter()
+        .map(|issue| format!("{}: {}", issue.field, issue.message))
+        .collect::<Vec<_>>()
+        .join("; ");
+
+    Err(AppError::new("VALIDATION_ERROR", &format!("Invalid custom shells: {}", summary)).with_details(details))
+}
+
+/// Check that each user-defined shell can be run and selected as a terminal type
+fn validate_custom_shells(custom_shells: &[CustomShell]) -> ValidationResult {
+    let mut result = ValidationResult::default();
+    let mut seen_ids = HashSet::new();
+
+    for (i, shell) in custom_shells.iter().enumerate() {
+        let id = shell.id.trim();
+        if id.is_empty() {
+            result.error(&format!("customShells[{}].id", i), "Is required");
+        } else if TERMINAL_TYPES.contains(&id) {
+            result.error(
+                &format!("customShells[{}].id", i),
+                &format!("'{}' is a built-in terminal type", id),
+            );
+        } else if !seen_ids.insert(id) {
+            result.error(
+                &format!("customShells[{}].id", i),
+                &format!("Another custom shell already uses '{}'", id),
+            );
+        }
+
+        if shell.executable.trim().is_empty() {
+            result.error(&format!("customShells[{}].executable", i), "Is required");
+        }
+        if !shell.args.iter().any(|arg| arg.contains("{script}")) {
+            result.error(
+                &format!("customShells[{}].args", i),
+                "Must include {script} where the launch commands go",
+            );
+        }
+    }
+
+    result.valid = result.errors.is_empty();
+    result
+}
+
+/// Check an app configuration for errors and warnings
+///
+/// `others` are the apps it must not clash with; an entry with the same ID is ignored so
+/// the saved version of an app being updated doesn't count as a duplicate. `custom_shells` are
+/// the user-defined shells its terminal type may refer to.
+pub(crate) fn validate_app(app: &AppConfig, others: &[AppConfig], custom_shells: &[CustomShell]) -> ValidationResult {
+    let mut result = ValidationResult::default();
+
+    validate_identity(app, others, &mut result);
+    validate_launch_fields(app, &mut result);
+    validate_browser_fields(app, &mut result);
+    validate_environment(app, &mut result);
+    validate_profiles(app, &mut result);
+
+    if let Some(ref terminal_type) = app.terminal_type {
+        validate_terminal_type(terminal_type, custom_shells, &mut result);
+    }
+
+    if let Some(ref tags) = app.tags {
+        if tags.iter().any(|tag| tag.trim().is_empty()) {
+            result.warning("tags", "Contains empty tags");
+        }
+    }
+
+    result.valid = result.errors.is_empty();
+    result
+}
+
+fn validate_identity(app: &AppConfig, others: &[AppConfig], result: &mut ValidationResult) {
+    if app.id.trim().is_empty() {
+        result.error("id", "Must not be empty");
+    }
+
+    let name = app.name.trim();
+    if name.is_empty() {
+        result.error("name", "Must not be empty");
+    } else if name.chars().count() > MAX_NAME_LENGTH {
+        result.warning("name", &format!("Is longer than {} characters", MAX_NAME_LENGTH));
+    }
+
+    if !name.is_empty()
+        && others
+            .iter()
+            .any(|other| other.id != app.id && other.name.trim().eq_ignore_ascii_case(name))
+    {
+        result.error("name", &format!("Another app is already named '{}'", name));
+    }
+}
+
+fn validate_launch_fields(app: &AppConfig, result: &mut ValidationResult) {
+    let has_commands = app.launch_commands.as_ref().is_some_and(|c| !c.trim().is_empty());
+    let has_url = app.url.as_ref().is_some_and(|u| !u.trim().is_empty());
+
+    match app.get_app_type() {
+        AppType::Process if !has_commands => {
+            result.error("launchCommands", "Required for process apps")
+        }
+        AppType::Bookmark if !has_url => result.error("url", "Required for bookmark apps"),
+        AppType::Bookmark if has_commands => {
+            result.warning("launchCommands", "Ignored for bookmark apps")
+        }
+        AppType::Both => {
+            if !has_commands {
+                result.error("launchCommands", "Required for apps that launch a process");
+            }
+            if !has_url {
+                result.error("url", "Required for apps that open a URL");
+            }
+        }
+        _ => {}
+    }
+
+    if let Some(ref dir) = app.working_directory {
+        validate_working_directory(dir, result);
+    }
+
+    if let Some(kind) = app.dev_environment {
+        validate_dev_environment(kind, app.working_directory.as_deref(), result);
+    }
+
+    validate_wsl_target(app, result);
+    validate_hooks(app, result);
+
+    validate_port("portToCheck", app.port_to_check, result);
+    if app.port_check_timeout == Some(0) {
+        result.warning("portCheckTimeout", "A timeout of 0 seconds disables waiting for the port");
+    }
+}
+
+fn validate_working_directory(dir: &str, result: &mut ValidationResult) {
+    let dir = dir.trim();
+
+    // Interpolated paths are only known at launch time
+    if dir.is_empty() || dir.contains("${") {
+        return;
+    }
+
+    // Linux paths on Windows are run through WSL, so they can't be checked from here
+    if cfg!(windows) && (dir.starts_with('/') || platform_utils::wsl_network_distro(dir).is_some()) {
+        return;
+    }
+
+    // A missing directory may exist on another machine (imported apps) or be created later
+    let path = Path::new(dir);
+    if !path.exists() {
+        result.warning("workingDirectory", &format!("Directory does not exist: {}", dir));
+    } else if !path.is_dir() {
+        result.error("workingDirectory", &format!("Not a directory: {}", dir));
+    }
+}
+
+fn validate_dev_environment(kind: DevEnvironment, working_dir: Option<&str>, result: &mut ValidationResult) {
+    let dir = working_dir.map(str::trim).unwrap_or("");
+    if dir.is_empty() {
+        result.error("devEnvironment", "Requires a working directory");
+    } else if !dir.contains("${")
+        && Path::new(dir).is_dir()
+        && !detect_dev_environments(dir, None).iter().any(|d| d.kind == kind)
+    {
+        result.warning(
+            "devEnvironment",
+            &format!("No {} in the working directory", kind.files().join(" or ")),
+        );
+    }
+}
+
+fn validate_wsl_target(app: &AppConfig, result: &mut ValidationResult) {
+    let distro = app.wsl_distro.as_deref().map(str::trim).unwrap_or("");
+    let user = app.wsl_user.as_deref().map(str::trim).unwrap_or("");
+    if distro.is_empty() && user.is_empty() {
+        return;
+    }
+
+    // Launches without a terminal type still go through WSL for Linux working directories on Windows
+    let terminal_type = app.terminal_type.as_deref().unwrap_or("");
+    if !terminal_type.is_empty() && terminal_type != "wsl" {
+        let field = if distro.is_empty() { "wslUser" } else { "wslDistro" };
+        result.warning(field, "Only used by the wsl terminal type");
+    }
+
+    // Passed to wsl.exe as arguments, so they mustn't look like options
+    if !distro.is_empty() {
+        if !distro.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_')) || distro.starts_with('-') {
+            result.error("wslDistro", "Distribution names contain only letters, digits, '.', '-' and '_'");
+        }
+        let path_distro = app.working_directory.as_deref().and_then(platform_utils::wsl_network_distro);
+        if let Some(path_distro) = path_distro.filter(|d| !d.eq_ignore_ascii_case(distro)) {
+            result.error(
+                "wslDistro",
+                &format!("The working directory is in the '{}' distribution", path_distro),
+            );
+        }
+    }
+    if !user.is_empty() && (user.starts_with('-') || user.chars().any(char::is_whitespace)) {
+        result.error("wslUser", &format!("Not a valid user name: {}", user));
+    }
+}
+
+fn validate_hooks(app: &AppConfig, result: &mut ValidationResult) {
+    let hooks = [("preLaunch", &app.pre_launch), ("postStop", &app.post_stop)];
+    for (field, commands) in hooks {
+        if commands.iter().flatten().any(|command| command.trim().is_empty()) {
+            result.warning(field, "Contains empty commands");
+        }
+    }
+
+    let has_hooks = hooks.iter().any(|(_, commands)| commands.iter().flatten().any(|c| !c.trim().is_empty()));
+    if has_hooks && app.get_app_type() == AppType::Bookmark {
+        result.warning("preLaunch", "Hooks only run for apps that launch a process");
+    }
+    if app.hook_timeout == Some(0) {
+        result.error("hookTimeout", "Must be at least 1 second");
+    }
+}
+
+fn validate_port(field: &str, port: Option<u16>, result: &mut ValidationResult) {
+    if port == Some(0) {
+        result.error(field, "Port must be between 1 and 65535");
+    }
+}
+
+fn validate_browser_fields(app: &AppConfig, result: &mut ValidationResult) {
+    if let Some(ref url) = app.url {
+        validate_url("url", url, result);
+    }
+
+    let has_url = app.url.as_ref().is_some_and(|u| !u.trim().is_empty());
+    if app.auto_launch_browser == Some(true) && !has_url {
+        result.warning("autoLaunchBrowser", "Enabled but the app has no URL");
+    }
+
+    if let Some(delay) = app.browser_delay {
+        if delay > MAX_REASONABLE_BROWSER_DELAY {
+            result.warning(
+                "browserDelay",
+                &format!("Browser delay of {} seconds is unusually long", delay),
+            );
+        }
+    }
+}
+
+fn validate_url(field: &str, url: &str, result: &mut ValidationResult) {
+    let url = url.trim();
+    if url.is_empty() {
+        return;
+    }
+
+    if !url.starts_with("http://") && !url.starts_with("https://") && !url.starts_with("file://") {
+        result.error(field, "Must start with http://, https:// or file://");
+        return;
+    }
+
+    // Interpolated URLs (e.g. http://localhost:${PORT}) can only be fully checked at launch time
+    if url.contains("${") {
+        return;
+    }
+
+    if let Err(e) = reqwest::Url::parse(url) {
+        result.error(field, &format!("Invalid URL: {}", e));
+    }
+}
+
+fn validate_environment(app: &AppConfig, result: &mut ValidationResult) {
+    if let Some(ref env) = app.environment_variables {
+        validate_variable_names("environmentVariables", env.keys(), result);
+    }
+
+    if let Some(ref secrets) = app.secret_variables {
+        validate_variable_names("secretVariables", secrets.keys(), result);
+        for (name, secret) in secrets {
+            if secret.trim().is_empty() {
+                result.error(
+                    &format!("secretVariables.{}", name),
+                    "Must reference a secret name",
+                );
+            }
+        }
+    }
+
+    if let Some(ref env_files) = app.env_files {
+        if env_files.iter().any(|path| path.trim().is_empty()) {
+            result.error("envFiles", "Contains an empty path");
+        }
+        let has_working_directory = app.working_directory.as_ref().is_some_and(|dir| !dir.trim().is_empty());
+        if !has_working_directory && env_files.iter().any(|path| !path.trim().is_empty() && Path::new(path).is_relative()) {
+            result.error("envFiles", "Relative paths need a working directory to resolve against");
+        }
+    }
+}
+
+fn validate_variable_names<'a>(
+    field: &str,
+    names: impl Iterator<Item = &'a String>,
+    result: &mut ValidationResult,
+) {
+    for name in names {
+        if !is_valid_name(name) {
+            result.error(
+                &format!("{}.{}", field, name),
+                "Variable names must start with a letter or underscore and contain only letters, digits and underscores",
+            );
+        }
+    }
+}
+
+fn validate_profiles(app: &AppConfig, result: &mut ValidationResult) {
+    let profiles = app.profiles.as_deref().unwrap_or_default();
+    let mut seen = HashSet::new();
+
+    for (i, profile) in profiles.iter().enumerate() {
+        let field = format!("profiles[{}]", i);
+        let name = profile.name.trim();
+
+        if name.is_empty() {
+            result.error(&format!("{}.name", field), "Must not be empty");
+        } else if !seen.insert(name.to_lowercase()) {
+            result.error(&format!("{}.name", field), &format!("Duplicate profile name '{}'", name));
+        }
+
+        validate_port(&format!("{}.portToCheck", field), profile.port_to_check, result);
+        if let Some(ref url) = profile.url {
+            validate_url(&format!("{}.url", field), url, result);
+        }
+        if let Some(ref env) = profile.environment_variables {
+            validate_variable_names(&format!("{}.environmentVariables", field), env.keys(), result);
+        }
+    }
+
+    if let Some(ref default_profile) = app.default_profile {
+        if !default_profile.is_empty()
+            && !profiles.iter().any(|p| p.name == *default_profile)
+        {
+            result.error(
+                "defaultProfile",
+                &format!("No profile named '{}'", default_profile),
+            );
+        }
+    }
+}
+
+fn validate_terminal_type(terminal_type: &str, custom_shells: &[CustomShell], result: &mut ValidationResult) {
+    // An empty terminal type means the system default
+    if terminal_type.is_empty() || find_custom_shell(custom_shells, terminal_type).is_some() {
+        return;
+    }
+
+    if !TERMINAL_TYPES.contains(&terminal_type) {
+        let known: Vec<&str> = TERMINAL_TYPES
+            .iter()
+            .copied()
+            .chain(custom_shells.iter().map(|shell| shell.id.as_str()))
+            .collect();
+        result.error(
+            "terminalType",
+            &format!(
+                "Unknown terminal type '{}' (expected one of: {})",
+                terminal_type,
+                known.join(", ")
+            ),
+        );
+    } else if !cfg!(windows) && WINDOWS_TERMINAL_TYPES.contains(&terminal_type) {
+        result.warning(
+            "terminalType",
+            &format!("'{}' is only available on Windows", terminal_type),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(name: &str) -> AppConfig {
+        AppConfig::for_test(&format!("{}-id", name), name, "npm start")
+    }
+
+    fn error_fields(result: &ValidationResult) -> Vec<&str> {
+        result.errors.iter().map(|issue| issue.field.as_str()).collect()
+    }
+
+    fn warning_fields(result: &ValidationResult) -> Vec<&str> {
+        result.warnings.iter().map(|issue| issue.field.as_str()).collect()
+    }
+
+    #[test]
+    fn minimal_app_is_valid() {
+        let result = validate_app(&app("api"), &[], &[]);
+        assert!(result.valid, "{:?}", result.errors);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn names_are_required_and_unique() {
+        let mut nameless = app("api");
+        nameless.name = "  ".to_string();
+        assert_eq!(error_fields(&validate_app(&nameless, &[], &[])), ["name"]);
+
+        let others = [app("API")];
+        assert_eq!(error_fields(&validate_app(&app("api"), &others, &[])), ["name"]);
+
+        // The saved copy of the app being updated isn't a duplicate
+        let mut saved = app("api");
+        saved.launch_commands = None;
+        assert!(validate_app(&app("api"), &[saved], &[]).valid);
+    }
+
+    #[test]
+    fn app_type_requires_its_fields() {
+        let mut process = app("api");
+        process.app_type = Some(AppType::Process);
+        process.launch_commands = Some(" ".to_string());
+        assert_eq!(error_fields(&validate_app(&process, &[], &[])), ["launchCommands"]);
+
+        let mut bookmark = app("docs");
+        bookmark.app_type = Some(AppType::Bookmark);
+        bookmark.url = Some("https://example.com".to_string());
+        let result = validate_app(&bookmark, &[], &[]);
+        assert!(result.valid);
+        assert_eq!(warning_fields(&result), ["launchCommands"]);
+    }
+
+    #[test]
+    fn missing_working_directory_is_only_a_warning() {
+        let mut missing = app("api");
+        missing.working_directory = Some("/definitely/not/here/oddlauncher".to_string());
+        let result = validate_app(&missing, &[], &[]);
+        assert!(result.valid);
+        if !cfg!(windows) {
+            assert_eq!(warning_fields(&result), ["workingDirectory"]);
+        }
+
+        let mut interpolated = app("api");
+        interpolated.working_directory = Some("${HOME}/missing".to_string());
+        assert!(validate_app(&interpolated, &[], &[]).warnings.is_empty());
+    }
+
+    fn shell(id: &str, executable: &str, args: &[&str]) -> CustomShell {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": id,
+            "executable": executable,
+            "args": args
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn custom_shells_are_checked() {
+        let valid = [shell("dash", "/bin/dash", &["-c", "{script}"]), shell("ion", "ion", &["-c", "{script}"])];
+        assert!(ensure_valid_custom_shells(&valid).is_ok());
+
+        let invalid = [
+            shell(" ", "", &["-c", "{script}"]),
+            shell("bash", "bash", &["-c", "{script}"]),
+            shell("dash", "dash", &["-c"]),
+            shell("dash", "dash", &["-c", "{script}"]),
+        ];
+        let result = validate_custom_shells(&invalid);
+        assert!(!result.valid);
+        assert_eq!(
+            error_fields(&result),
+            [
+                "customShells[0].id",
+                "customShells[0].executable",
+                "customShells[1].id",
+                "customShells[2].args",
+                "customShells[3].id"
+            ]
+        );
+        assert_eq!(ensure_valid_custom_shells(&invalid).unwrap_err().code, "VALIDATION_ERROR");
+    }
+
+    #[test]
+    fn relative_env_files_need_a_working_directory() {
+        let mut relative = app("api");
+        relative.env_files = Some(vec![".env".to_string()]);
+        assert_eq!(error_fields(&validate_app(&relative, &[], &[])), ["envFiles"]);
+
+        relative.working_directory = Some("${HOME}".to_string());
+        assert!(validate_app(&relative, &[], &[]).valid);
+    }
+
+    #[test]
+    fn working_directory_must_be_a_directory() {
+        let file = std::env::temp_dir().join(format!("oddlauncher-validation-{}", std::process::id()));
+        std::fs::write(&file, "").unwrap();
+        let mut not_dir = app("api");
+        not_dir.working_directory = Some(file.to_string_lossy().to_string());
+        let result = validate_app(&not_dir, &[], &[]);
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(error_fields(&result), ["workingDirectory"]);
+    }
+
+    #[test]
+    fn ports_and_urls_are_checked() {
+        let mut bad = app("api");
+        bad.port_to_check = Some(0);
+        bad.url = Some("localhost:3000".to_string());
+        assert_eq!(error_fields(&validate_app(&bad, &[], &[])), ["portToCheck", "url"]);
+
+        let mut interpolated = app("api");
+        interpolated.url = Some("http://localhost:${PORT}".to_string());
+        assert!(validate_app(&interpolated, &[], &[]).valid);
+
+        let mut no_url = app("api");
+        no_url.auto_launch_browser = Some(true);
+        assert_eq!(warning_fields(&validate_app(&no_url, &[], &[])), ["autoLaunchBrowser"]);
+    }
+
+    #[test]
+    fn variable_names_are_checked() {
+        let mut bad = app("api");
+        bad.environment_variables = Some(HashMap::from([
+            ("GOOD_NAME".to_string(), "1".to_string()),
+            ("1BAD".to_string(), "2".to_string()),
+        ]));
+        bad.secret_variables = Some(HashMap::from([("TOKEN".to_string(), " ".to_string())]));
+        let result = validate_app(&bad, &[], &[]);
+        let mut fields = error_fields(&result);
+        fields.sort();
+        assert_eq!(fields, ["environmentVariables.1BAD", "secretVariables.TOKEN"]);
+    }
+
+    #[test]
+    fn profiles_are_checked() {
+        let mut profiled = app("api");
+        profiled.profiles = Some(
+            serde_json::from_value(serde_json::json!([
+                { "name": "dev" },
+                { "name": "Dev" },
+                { "name": "", "portToCheck": 0 }
+            ]))
+            .unwrap(),
+        );
+        profiled.default_profile = Some("prod".to_string());
+        assert_eq!(
+            error_fields(&validate_app(&profiled, &[], &[])),
+            ["profiles[1].name", "profiles[2].name", "profiles[2].portToCheck", "defaultProfile"]
+        );
+
+        profiled.profiles.as_mut().unwrap().truncate(1);
+        profiled.default_profile = Some("dev".to_string());
+        assert!(validate_app(&profiled, &[], &[]).valid);
+    }
+
+    #[test]
+    fn terminal_types_include_custom_shells() {
+        let mut custom = app("api");
+        custom.terminal_type = Some("oil".to_string());
+        assert_eq!(error_fields(&validate_app(&custom, &[], &[])), ["terminalType"]);
+
+        let shells: Vec<CustomShell> = serde_json::from_value(serde_json::json!([
+            { "id": "oil", "name": "Oils", "executable": "osh", "args": ["-c", "{script}"] }
+        ]))
+        .unwrap();
+        assert!(validate_app(&custom, &[], &shells).valid);
+    }
+
+    #[test]
+    fn wsl_target_is_checked() {
+        let mut wsl = app("api");
+        wsl.terminal_type = Some("wsl".to_string());
+        wsl.wsl_distro = Some("-d evil".to_string());
+        wsl.wsl_user = Some("root user".to_string());
+        assert_eq!(error_fields(&validate_app(&wsl, &[], &[])), ["wslDistro", "wslUser"]);
+
+        wsl.terminal_type = Some("bash".to_string());
+        wsl.wsl_distro = Some("Ubuntu-22.04".to_string());
+        wsl.wsl_user = None;
+        let result = validate_app(&wsl, &[], &[]);
+        assert!(result.valid);
+        assert_eq!(warning_fields(&result), ["wslDistro"]);
+    }
+}
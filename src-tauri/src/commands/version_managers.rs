@@ -0,0 +1,282 @@
+use crate::commands::shell_quote::ShellDialect;
+use std::path::Path;
+
+// Version manager detection: works out which managers a project relies on from its version files
+// and launch commands, and produces the shell lines that initialize them before the commands run.
+
+/**
+ * A tool version manager that needs initializing in the launch shell
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum VersionManager {
+    Asdf,
+    Mise,
+    Nvm,
+    Fnm,
+    Volta,
+    Pyenv,
+    Rbenv,
+    Sdkman,
+}
+
+/// Version files and the managers that read them
+const VERSION_FILES: [(&str, &[VersionManager]); 9] = [
+    (".tool-versions", &[VersionManager::Asdf, VersionManager::Mise]),
+    ("mise.toml", &[VersionManager::Mise]),
+    (".mise.toml", &[VersionManager::Mise]),
+    (".nvmrc", &[VersionManager::Nvm, VersionManager::Fnm]),
+    (".node-version", &[VersionManager::Fnm]),
+    (".python-version", &[VersionManager::Pyenv]),
+    (".ruby-version", &[VersionManager::Rbenv]),
+    (".sdkmanrc", &[VersionManager::Sdkman]),
+    // Volta pins tools in package.json; checked for its "volta" key below
+    ("package.json", &[VersionManager::Volta]),
+];
+
+impl VersionManager {
+    const ALL: [VersionManager; 8] = [
+        VersionManager::Asdf,
+        VersionManager::Mise,
+        VersionManager::Nvm,
+        VersionManager::Fnm,
+        VersionManager::Volta,
+        VersionManager::Pyenv,
+        VersionManager::Rbenv,
+        VersionManager::Sdkman,
+    ];
+
+    /// Command a launch command starts with when it uses the manager directly
+    fn command(self) -> &'static str {
+        match self {
+            VersionManager::Asdf => "asdf",
+            VersionManager::Mise => "mise",
+            VersionManager::Nvm => "nvm",
+            VersionManager::Fnm => "fnm",
+            VersionManager::Volta => "volta",
+            VersionManager::Pyenv => "pyenv",
+            VersionManager::Rbenv => "rbenv",
+            VersionManager::Sdkman => "sdk",
+        }
+    }
+
+    /// Statements that initialize the manager, each skipped if the manager isn't installed
+    ///
+    /// `shell` is the POSIX shell in use (bash, zsh or sh), for managers that print shell-specific setup.
+    /// Failures are reported by the manager but never stop the launch.
+    pub fn init_lines(self, dialect: ShellDialect, shell: &str) -> Vec<String> {
+        match dialect {
+            ShellDialect::Posix => self.posix_init_lines(shell),
+            ShellDialect::Fish => self.fish_init_lines(),
+            ShellDialect::PowerShell => self.powershell_init_lines(),
+            // cmd has no eval; managers on Windows (volta, pyenv-win, nvm-windows) work through shims or global switches
+            ShellDialect::Cmd => Vec::new(),
+            // Few managers print setup for these shells; PATH from the login shell environment has to do
+            ShellDialect::Nu | ShellDialect::Xonsh | ShellDialect::Elvish => Vec::new(),
+        }
+    }
+
+    fn posix_init_lines(self, shell: &str) -> Vec<String> {
+        match self {
+            VersionManager::Asdf => vec![
+                r#"if [ -d "${ASDF_DATA_DIR:-$HOME/.asdf}/shims" ]; then export PATH="${ASDF_DATA_DIR:-$HOME/.asdf}/shims:$PATH"; fi"#.to_string(),
+            ],
+            VersionManager::Mise => vec![
+                r#"if command -v mise >/dev/null 2>&1; then eval "$(mise env -s bash)" || true; fi"#.to_string(),
+            ],
+            VersionManager::Nvm => vec![
+                r#"export NVM_DIR="${NVM_DIR:-$HOME/.nvm}""#.to_string(),
+                r#"for nvm_sh in "$NVM_DIR/nvm.sh" /opt/homebrew/opt/nvm/nvm.sh /usr/local/share/nvm/nvm.sh; do if [ -s "$nvm_sh" ]; then . "$nvm_sh"; break; fi; done"#.to_string(),
+                r#"if [ -f .nvmrc ] && command -v nvm >/dev/null 2>&1; then nvm use || true; fi"#.to_string(),
+            ],
+            VersionManager::Fnm => vec![
+                r#"if command -v fnm >/dev/null 2>&1; then eval "$(fnm env --shell bash)" || true; if [ -f .nvmrc ] || [ -f .node-version ]; then fnm use || true; fi; fi"#.to_string(),
+            ],
+            VersionManager::Volta => vec![
+                r#"if [ -d "${VOLTA_HOME:-$HOME/.volta}/bin" ]; then export PATH="${VOLTA_HOME:-$HOME/.volta}/bin:$PATH"; fi"#.to_string(),
+            ],
+            // pyenv and rbenv print setup for bash and zsh only; plain sh gets by with their shims
+            VersionManager::Pyenv if shell == "sh" => vec![
+                r#"if [ -d "${PYENV_ROOT:-$HOME/.pyenv}/shims" ]; then export PATH="${PYENV_ROOT:-$HOME/.pyenv}/shims:$PATH"; fi"#.to_string(),
+            ],
+            VersionManager::Pyenv => vec![
+                r#"export PYENV_ROOT="${PYENV_ROOT:-$HOME/.pyenv}""#.to_string(),
+                r#"if [ -d "$PYENV_ROOT/bin" ]; then export PATH="$PYENV_ROOT/bin:$PATH"; fi"#.to_string(),
+                format!(r#"if command -v pyenv >/dev/null 2>&1; then eval "$(pyenv init - {})" || true; fi"#, shell),
+            ],
+            VersionManager::Rbenv if shell == "sh" => vec![
+                r#"if [ -d "${RBENV_ROOT:-$HOME/.rbenv}/shims" ]; then export PATH="${RBENV_ROOT:-$HOME/.rbenv}/shims:$PATH"; fi"#.to_string(),
+            ],
+            VersionManager::Rbenv => vec![
+                format!(r#"if command -v rbenv >/dev/null 2>&1; then eval "$(rbenv init - {})" || true; fi"#, shell),
+            ],
+            // sdkman-init.sh needs bash or zsh
+            VersionManager::Sdkman if shell == "sh" => Vec::new(),
+            VersionManager::Sdkman => vec![
+                r#"export SDKMAN_DIR="${SDKMAN_DIR:-$HOME/.sdkman}""#.to_string(),
+                r#"if [ -s "$SDKMAN_DIR/bin/sdkman-init.sh" ]; then . "$SDKMAN_DIR/bin/sdkman-init.sh"; if [ -f .sdkmanrc ]; then sdk env || true; fi; fi"#.to_string(),
+            ],
+        }
+    }
+
+    fn fish_init_lines(self) -> Vec<String> {
+        let lines: &[&str] = match self {
+            VersionManager::Asdf => &[
+                "set -q ASDF_DATA_DIR; or set -l ASDF_DATA_DIR ~/.asdf",
+                "if test -d $ASDF_DATA_DIR/shims; set -gx PATH $ASDF_DATA_DIR/shims $PATH; end",
+            ],
+            VersionManager::Mise => &["if type -q mise; mise env -s fish | source; end"],
+            // nvm.sh doesn't run in fish; the nvm.fish plugin provides its own `nvm`
+            VersionManager::Nvm => &["if type -q nvm; and test -f .nvmrc; nvm use; or true; end"],
+            VersionManager::Fnm => &[
+                "if type -q fnm; fnm env --shell fish | source; if test -f .nvmrc -o -f .node-version; fnm use; or true; end; end",
+            ],
+            VersionManager::Volta => &[
+                "set -q VOLTA_HOME; or set -l VOLTA_HOME ~/.volta",
+                "if test -d $VOLTA_HOME/bin; set -gx PATH $VOLTA_HOME/bin $PATH; end",
+            ],
+            VersionManager::Pyenv => &["if type -q pyenv; pyenv init - fish | source; end"],
+            VersionManager::Rbenv => &["if type -q rbenv; rbenv init - fish | source; end"],
+            // Provided by the sdkman-for-fish plugin
+            VersionManager::Sdkman => &["if type -q sdk; and test -f .sdkmanrc; sdk env; or true; end"],
+        };
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    fn powershell_init_lines(self) -> Vec<String> {
+        let lines: &[&str] = match self {
+            VersionManager::Mise => &[
+                "if (Get-Command mise -ErrorAction SilentlyContinue) { mise env -s pwsh | Out-String | Invoke-Expression }",
+            ],
+            VersionManager::Fnm => &[
+                "if (Get-Command fnm -ErrorAction SilentlyContinue) { fnm env --shell powershell | Out-String | Invoke-Expression; if ((Test-Path .nvmrc) -or (Test-Path .node-version)) { fnm use } }",
+            ],
+            // Volta and pyenv-win use shims; nvm-windows switches versions globally; the rest are Unix-only
+            _ => &[],
+        };
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+}
+
+/// Find the version managers a project uses, from version files in its working directory
+/// and from launch commands that call a manager directly
+pub fn detect_version_managers(working_dir: Option<&str>, user_commands: &str) -> Vec<VersionManager> {
+    let mut managers = Vec::new();
+
+    if let Some(dir) = working_dir {
+        let dir = Path::new(dir);
+        for (file, file_managers) in VERSION_FILES {
+            let path = dir.join(file);
+            if !path.is_file() {
+                continue;
+            }
+            if file == "package.json" && !package_json_pins_volta(&path) {
+                continue;
+            }
+            managers.extend_from_slice(file_managers);
+        }
+    }
+
+    // Each command in a line like `cd web && nvm use && npm start`, but not arguments that happen to match
+    let segments = user_commands.split(['\n', ';', '&', '|']);
+    for segment in segments {
+        let first_word = segment.split_whitespace().next().unwrap_or("");
+        if let Some(manager) = VersionManager::ALL.iter().find(|m| m.command() == first_word) {
+            managers.push(*manager);
+        }
+    }
+
+    managers.sort();
+    managers.dedup();
+    if !managers.is_empty() {
+        log::info!("Detected version managers: {:?}", managers);
+    }
+    managers
+}
+
+fn package_json_pins_volta(path: &Path) -> bool {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .is_some_and(|package| package.get("volta").is_some())
+}
+
+/// Statements initializing every version manager the project uses, for a terminal type
+///
+/// They must run after changing to the working directory, since managers read version files from there.
+pub fn get_version_manager_init(terminal_type: &str, working_dir: Option<&str>, user_commands: &str) -> Vec<String> {
+    let dialect = ShellDialect::for_terminal(terminal_type);
+    let shell = match terminal_type {
+        "zsh" | "sh" => terminal_type,
+        _ => "bash",
+    };
+
+    let mut init = Vec::new();
+    for manager in detect_version_managers(working_dir, user_commands) {
+        let lines = manager.init_lines(dialect, shell);
+        if lines.is_empty() {
+            log::warn!(
+                "{:?} can't be initialized in {}; the launch relies on its shims or PATH being set up already",
+                manager,
+                terminal_type
+            );
+        }
+        init.extend(lines);
+    }
+    init
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(name: &str, files: &[(&str, &str)]) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("oddlauncher-vm-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, content) in files {
+            std::fs::write(dir.join(name), content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn version_files() {
+        let dir = project("files", &[(".tool-versions", "nodejs 20.0.0"), (".nvmrc", "20"), ("package.json", "{}")]);
+        let detected = detect_version_managers(dir.to_str(), "npm start");
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            detected,
+            [VersionManager::Asdf, VersionManager::Mise, VersionManager::Nvm, VersionManager::Fnm]
+        );
+    }
+
+    #[test]
+    fn volta_needs_its_package_json_key() {
+        let dir = project("volta", &[("package.json", r#"{"volta": {"node": "20.0.0"}}"#)]);
+        let detected = detect_version_managers(dir.to_str(), "");
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(detected, [VersionManager::Volta]);
+    }
+
+    #[test]
+    fn command_segments() {
+        assert_eq!(
+            detect_version_managers(None, "cd web && nvm use && npm start\n  pyenv shell 3.12; sdk env|cat"),
+            [VersionManager::Nvm, VersionManager::Pyenv, VersionManager::Sdkman]
+        );
+        // Only the command itself counts, not arguments or longer names
+        assert!(detect_version_managers(None, "echo nvm\nnvmx install\nmy-mise run").is_empty());
+        assert!(detect_version_managers(Some("/definitely/not/here/oddlauncher"), "").is_empty());
+    }
+
+    #[test]
+    fn init_lines_per_shell() {
+        assert_eq!(get_version_manager_init("cmd", None, "nvm use"), Vec::<String>::new());
+        assert_eq!(get_version_manager_init("nu", None, "mise run dev"), Vec::<String>::new());
+        assert_eq!(
+            get_version_manager_init("zsh", None, "rbenv exec ruby app.rb"),
+            [r#"if command -v rbenv >/dev/null 2>&1; then eval "$(rbenv init - zsh)" || true; fi"#]
+        );
+        assert_eq!(get_version_manager_init("sh", None, "sdk env"), Vec::<String>::new());
+        assert_eq!(get_version_manager_init("pwsh", None, "fnm use").len(), 1);
+    }
+}
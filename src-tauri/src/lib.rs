@@ -1,13 +1,43 @@
 pub mod models;
 pub mod commands;
+pub mod cli;
 
+use commands::deep_link::PendingImports;
 use commands::process::ProcessManager;
+use commands::secrets::SecretStore;
+use commands::shell_env::ShellEnvironment;
+use commands::terminal::TerminalCache;
+use tauri::Manager;
+use tauri_plugin_deep_link::DeepLinkExt;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+  let args: Vec<String> = std::env::args().skip(1).collect();
+
+  // Only one instance may own a config directory; later launches hand their arguments to it
+  let _instance_lock = match commands::acquire_instance_lock() {
+    Ok(Some(lock)) => Some(lock),
+    Ok(None) => {
+      if let Err(e) = commands::forward_to_running_instance(&args) {
+        eprintln!("{}", e.message);
+        std::process::exit(1);
+      }
+      return;
+    }
+    Err(e) => {
+      eprintln!("{} (continuing without single-instance protection)", e.message);
+      None
+    }
+  };
+
   tauri::Builder::default()
     .plugin(tauri_plugin_dialog::init())
+    .plugin(tauri_plugin_deep_link::init())
     .manage(ProcessManager::default())
+    .manage(SecretStore::default())
+    .manage(PendingImports::default())
+    .manage(ShellEnvironment::default())
+    .manage(TerminalCache::default())
     .setup(|app| {
       // Always enable logging, with different levels for debug vs release
       let log_level = if cfg!(debug_assertions) {
@@ -21,6 +51,41 @@ pub fn run() {
           .level(log_level)
           .build(),
       )?;
+
+      commands::start_control_server(app.handle().clone());
+
+      // Capture the login shell environment up front so the first launch doesn't wait for it
+      let handle = app.handle().clone();
+      tauri::async_runtime::spawn(async move {
+        handle.state::<ShellEnvironment>().variables().await;
+      });
+
+      // Installers register the oddlauncher:// scheme; this covers development builds and AppImages
+      #[cfg(any(windows, target_os = "linux"))]
+      {
+        if let Err(e) = app.deep_link().register_all() {
+          log::warn!("Failed to register deep link scheme: {}", e);
+        }
+      }
+
+      // On macOS links arrive as events; elsewhere they're launch arguments (see handle_instance_args)
+      let handle = app.handle().clone();
+      app.deep_link().on_open_url(move |event| {
+        let handle = handle.clone();
+        let links: Vec<String> = event.urls().iter().map(|url| url.to_string()).collect();
+        tauri::async_runtime::spawn(async move {
+          for link in links {
+            commands::handle_deep_link(&handle, &link).await;
+          }
+        });
+      });
+
+      if commands::has_instance_actions(&args) {
+        let handle = app.handle().clone();
+        tauri::async_runtime::spawn(async move {
+          commands::handle_instance_args(&handle, &args).await;
+        });
+      }
       Ok(())
     })
     .invoke_handler(tauri::generate_handler![
@@ -32,6 +97,20 @@ pub fn run() {
       commands::get_config_info,
       commands::backup_config,
       commands::restore_config,
+      commands::convert_config_format,
+      commands::list_backups,
+      commands::diff_backup,
+      commands::get_config_history,
+      commands::undo_config_change,
+      commands::redo_config_change,
+      commands::export_apps,
+      commands::import_apps,
+      commands::list_pending_imports,
+      commands::confirm_deep_link_import,
+      commands::cancel_deep_link_import,
+      commands::validate_app_config,
+      commands::list_app_templates,
+      commands::create_app_from_template,
       commands::pick_directory,
       commands::pick_file,
       commands::validate_directory,
@@ -46,6 +125,17 @@ pub fn run() {
       commands::wait_for_port_ready,
       commands::get_debug_info,
       commands::detect_available_terminals,
+      commands::get_terminal_detection,
+      commands::refresh_terminal_detection,
+      commands::get_shell_environment_status,
+      commands::refresh_shell_environment,
+      commands::detect_dev_environment,
+      commands::preview_app_environment,
+      commands::get_secret_store_status,
+      commands::unlock_secret_store,
+      commands::lock_secret_store,
+      commands::set_secret,
+      commands::remove_secret,
     ])
     .run(tauri::generate_context!())
     .expect("error while running tauri application");
@@ -23,7 +23,7 @@ impl Default for AppStatus {
 /**
  * Configuration for an individual app
  */
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct AppConfig {
     /// Unique identifier for the app
@@ -38,6 +38,10 @@ pub struct AppConfig {
     pub url: Option<String>,
     /// Environment variables to set (optional)
     pub environment_variables: Option<HashMap<String, String>>,
+    /// Dotenv files to load, relative to the working directory, in increasing precedence (optional)
+    pub env_files: Option<Vec<String>>,
+    /// Environment variables whose values come from the encrypted secret store, mapped to secret names (optional)
+    pub secret_variables: Option<HashMap<String, String>>,
     /// Auto-launch browser when app starts (default: true if url provided)
     pub auto_launch_browser: Option<bool>,
     /// Delay in seconds before opening browser (default: 0)
@@ -50,6 +54,28 @@ pub struct AppConfig {
     pub tags: Option<Vec<String>>,
     /// Terminal/shell type to use for executing commands (optional)
     pub terminal_type: Option<String>,
+    /// WSL distribution the wsl terminal type runs in (default: WSL's default distribution)
+    pub wsl_distro: Option<String>,
+    /// Linux user the wsl terminal type runs as (default: the distribution's default user)
+    pub wsl_user: Option<String>,
+    /// How launch commands are handed to the shell (default: inline)
+    pub launch_mode: Option<LaunchMode>,
+    /// Start from the login shell's environment rather than the launcher's own (default: true)
+    pub use_login_shell_env: Option<bool>,
+    /// Project development environment tool to run the launch commands in (optional)
+    pub dev_environment: Option<DevEnvironment>,
+    /// Commands run one at a time before the launch commands, each to completion (optional)
+    pub pre_launch: Option<Vec<String>>,
+    /// Commands run one at a time after the app's process has ended (optional)
+    pub post_stop: Option<Vec<String>>,
+    /// Maximum time each pre-launch or post-stop command may run in seconds (default: 300)
+    pub hook_timeout: Option<u32>,
+    /// Don't launch the app when a pre-launch command fails (default: true)
+    pub abort_on_pre_launch_failure: Option<bool>,
+    /// Named environment profiles overriding env vars, URL, port and launch commands (optional)
+    pub profiles: Option<Vec<AppProfile>>,
+    /// Profile used when no profile is requested at launch (optional)
+    pub default_profile: Option<String>,
     /// Explicit app type (process, bookmark, both) - optional for back-compat
     pub app_type: Option<AppType>,
     /// Last time the app was used (process started or bookmark opened)
@@ -82,6 +108,44 @@ pub struct AppProcess {
     pub output: Vec<String>,
     /// Whether the process is detached/background
     pub is_background: Option<bool>,
+    /// Environment profile the process was started with (if any)
+    pub active_profile: Option<String>,
+    /// Launch command the process is running, when its steps can be followed
+    pub current_step: Option<LaunchStep>,
+}
+
+/**
+ * One launch command of a running app
+ */
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchStep {
+    /// Position among the launch commands, from 0
+    pub index: usize,
+    /// Number of launch commands
+    pub total: usize,
+    /// The command line
+    pub command: String,
+    /// When the step started, in RFC3339 format
+    pub started_at: String,
+}
+
+/**
+ * Named set of overrides applied on top of an app's configuration
+ */
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AppProfile {
+    /// Profile name (e.g. dev, staging, local-prod)
+    pub name: String,
+    /// Environment variables added to or overriding the app's (optional)
+    pub environment_variables: Option<HashMap<String, String>>,
+    /// URL replacing the app's URL (optional)
+    pub url: Option<String>,
+    /// Port replacing the app's port to check (optional)
+    pub port_to_check: Option<u16>,
+    /// Launch commands replacing the app's launch commands (optional)
+    pub launch_commands: Option<String>,
 }
 
 /**
@@ -98,7 +162,7 @@ pub struct AppState {
 /**
  * Global application settings
  */
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct GlobalSettings {
     /// Theme preference (currently only 'dark' supported)
@@ -111,6 +175,18 @@ pub struct GlobalSettings {
     pub default_browser: Option<String>,
     /// Auto-save configuration changes
     pub auto_save: bool,
+    /// User-defined variables available to `${VAR}` interpolation in every app
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+    /// Automatic snapshot and backup retention policy
+    #[serde(default)]
+    pub backup_retention: BackupRetention,
+    /// https URL prefixes whose `oddlauncher://import` links are imported without asking for confirmation
+    #[serde(default)]
+    pub trusted_import_sources: Vec<String>,
+    /// Shells defined by the user, available as terminal types alongside the built-in ones
+    #[serde(default)]
+    pub custom_shells: Vec<CustomShell>,
 }
 
 impl Default for GlobalSettings {
@@ -121,6 +197,62 @@ impl Default for GlobalSettings {
             max_terminal_lines: 1000,
             default_browser: None,
             auto_save: true,
+            variables: HashMap::new(),
+            backup_retention: BackupRetention::default(),
+            trusted_import_sources: Vec::new(),
+            custom_shells: Vec::new(),
+        }
+    }
+}
+
+/**
+ * A user-defined shell that launch commands can run in
+ */
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomShell {
+    /// Terminal type apps select it by; must not clash with a built-in one
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// Program to run, a name looked up on PATH or an absolute path
+    pub executable: String,
+    /// Arguments, with `{script}` replaced by the launch commands (e.g. `["-c", "{script}"]`)
+    pub args: Vec<String>,
+    /// Placed between launch commands (default: a newline)
+    pub command_separator: Option<String>,
+    /// Statement printing a command before it runs, with `{command}` replaced by the quoted command (optional)
+    pub echo: Option<String>,
+    /// Quoting rules for `{command}`: posix, fish, powershell, cmd, nu, xonsh or elvish (default: posix)
+    pub quoting: Option<String>,
+    /// Arguments that make the shell print its version (default: `["--version"]`)
+    pub version_args: Option<Vec<String>>,
+}
+
+/**
+ * Retention policy for automatic configuration snapshots
+ *
+ * A snapshot is kept if it is among the newest `keep_last` snapshots, or if it is the
+ * newest snapshot of its day within the last `keep_daily_days` days. The newest
+ * snapshot is never pruned, and backups created on request are never pruned.
+ */
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BackupRetention {
+    /// Snapshot the configuration automatically before saves that remove or overwrite apps
+    pub auto_backup: bool,
+    /// Number of most recent snapshots to always keep
+    pub keep_last: u32,
+    /// Number of days for which one snapshot per day is kept
+    pub keep_daily_days: u32,
+}
+
+impl Default for BackupRetention {
+    fn default() -> Self {
+        Self {
+            auto_backup: true,
+            keep_last: 10,
+            keep_daily_days: 7,
         }
     }
 }
@@ -213,6 +345,33 @@ pub enum AppType {
     Both,
 }
 
+/**
+ * How launch commands are handed to the shell
+ */
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LaunchMode {
+    /// Each line is echoed and run in turn, joined into a single command string
+    #[default]
+    Inline,
+    /// The commands are written to a temporary script file, so multi-line shell syntax works
+    Script,
+}
+
+/**
+ * Tool that sets up a project's development environment around its launch commands
+ */
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DevEnvironment {
+    /// `direnv exec`, configured by .envrc
+    Direnv,
+    /// `nix develop` for flake.nix, or `nix-shell` for shell.nix
+    Nix,
+    /// `devbox run`, configured by devbox.json
+    Devbox,
+}
+
 /**
  * Information about an available terminal/shell
  */
@@ -229,6 +388,46 @@ pub struct TerminalInfo {
     pub available: bool,
     /// Platform this terminal is associated with
     pub platform: String,
+    /// Where the executable was found, if it was looked up
+    pub path: Option<String>,
+    /// First line of the shell's version output, if it was asked
+    pub version: Option<String>,
+    /// Whether this is the user's login shell ($SHELL)
+    #[serde(default)]
+    pub is_default: bool,
+}
+
+/**
+ * Everything terminal detection found, cached until refreshed
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalDetection {
+    /// Built-in, other and user-defined shells
+    pub terminals: Vec<TerminalInfo>,
+    /// The user's login shell, if there is one (not on Windows)
+    pub default_shell: Option<String>,
+    /// Where Windows Terminal (wt.exe) was found, on Windows
+    pub windows_terminal: Option<String>,
+    /// Installed WSL distributions, on Windows
+    pub wsl_distros: Vec<String>,
+    /// When detection ran, in RFC3339 format
+    pub detected_at: String,
+}
+
+#[cfg(test)]
+impl AppConfig {
+    /// A minimal process app for tests, with every optional field unset
+    pub(crate) fn for_test(id: &str, name: &str, command: &str) -> Self {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": name,
+            "launchCommands": command,
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap()
+    }
 }
 
 impl AppConfig {
@@ -268,4 +467,23 @@ impl AppConfig {
             _ => false,
         }
     }
+
+    /// Find the profile to launch with: the requested one, else the default profile (if any)
+    pub fn resolve_profile(&self, requested: Option<&str>) -> AppResult<Option<&AppProfile>> {
+        let name = match requested.or(self.default_profile.as_deref()) {
+            Some(name) if !name.trim().is_empty() => name,
+            _ => return Ok(None),
+        };
+
+        self.profiles
+            .as_ref()
+            .and_then(|profiles| profiles.iter().find(|p| p.name == name))
+            .map(Some)
+            .ok_or_else(|| {
+                AppError::new(
+                    "PROFILE_NOT_FOUND",
+                    &format!("Profile '{}' not found for app '{}'", name, self.name),
+                )
+            })
+    }
 }
@@ -2,13 +2,13 @@ import { useState, useEffect } from 'react'
 import { useConfigManager } from './hooks/useConfig'
 import { useKeyboardShortcuts } from './hooks/useKeyboardShortcuts'
 import { useProcessManager } from './hooks/useProcessManager'
+import { useDeepLinks } from './hooks/useDeepLinks'
 import { AppLayout, LibrarySidebar, MainContent, AppConfigModal, ConfirmationModal } from './components'
 import type { AppConfig } from './types'
 import type { AppConfigModalMode } from './components/AppConfigModal'
 import {
-  parseImportData,
-  validateAppConfig,
-  generateUniqueName,
+  importApps,
+  formatImportReport,
   createFileInput,
   readFileAsText
 } from './utils/import-export'
@@ -49,6 +49,7 @@ function App() {
   })
   const configManager = useConfigManager()
   const { processes } = useProcessManager()
+  useDeepLinks()
 
   // Update selectedApp when config changes (for title updates)
   useEffect(() => {
@@ -179,47 +180,14 @@ function App() {
       const fileInput = createFileInput(async (file) => {
         try {
           const fileContent = await readFileAsText(file)
-          const parseResult = parseImportData(fileContent)
+          const report = await importApps(fileContent, { strategy: 'duplicate' })
 
-          if (!parseResult.success) {
-            alert(`Import failed: ${parseResult.error}`)
-            return
-          }
-
-          const exportData = parseResult.data!
-
-          if (exportData.type === 'single-app') {
-            const importedApp = exportData.data as AppConfig
-
-            if (!validateAppConfig(importedApp)) {
-              alert('Import failed: Invalid app configuration format.')
-              return
-            }
-
-            // Check for name conflicts and generate unique name if needed
-            const existingNames = configManager.config?.apps.map((app: AppConfig) => app.name) || []
-            if (existingNames.includes(importedApp.name)) {
-              importedApp.name = generateUniqueName(importedApp.name, existingNames)
-            }
-
-            // Update timestamps and regenerate ID
-            importedApp.id = crypto.randomUUID()
-            importedApp.createdAt = new Date().toISOString()
-            importedApp.updatedAt = new Date().toISOString()
-
-            // Add the imported app
-            const success = await configManager.addApp(importedApp)
-            if (success) {
-              alert(`App imported successfully as "${importedApp.name}"`)
-            } else {
-              alert('Failed to import app. Please try again.')
-            }
-          } else {
-            alert('Full configuration import is not yet supported. Please import individual apps.')
-          }
+          await configManager.loadConfig()
+          alert(formatImportReport(report))
         } catch (error) {
           console.error('Import error:', error)
-          alert('Failed to import app. Please check the file format and try again.')
+          const message = (error as { message?: string })?.message
+          alert(`Import failed: ${message ?? 'Please check the file format and try again.'}`)
         } finally {
           // Clean up the file input
           document.body.removeChild(fileInput)
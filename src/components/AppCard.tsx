@@ -78,6 +78,11 @@ export const AppCard: React.FC<AppCardProps> = ({
             <div className={`app-card__status ${statusInfo.className}`}>
               <span className="app-card__status-icon">{statusInfo.icon}</span>
               <span className="app-card__status-text">{statusInfo.text}</span>
+              {isRunning && process?.currentStep && process.currentStep.total > 1 && (
+                <span className="app-card__step" title={process.currentStep.command}>
+                  · step {process.currentStep.index + 1}/{process.currentStep.total}
+                </span>
+              )}
             </div>
           </div>
           <div className="app-card__actions">
@@ -1,5 +1,5 @@
 import React, { useState, useEffect, useCallback } from 'react'
-import type { TerminalInfo } from '../../types/app'
+import type { DetectedDevEnvironment, TerminalDetection, TerminalInfo } from '../../types/app'
 import { X, Folder, Plus, AlertCircle, Settings, Link as LinkIcon, File as FileIcon } from 'lucide-react'
 import { invoke } from '@tauri-apps/api/core'
 import { useConfigManager } from '../../hooks/useConfig'
@@ -32,8 +32,12 @@ export function AppConfigModal({
 
   // Terminal detection state
   const [availableTerminals, setAvailableTerminals] = useState<TerminalInfo[]>([])
+  const [wslDistros, setWslDistros] = useState<string[]>([])
   const [isLoadingTerminals, setIsLoadingTerminals] = useState(false)
 
+  // Development environment tools configured in the working directory
+  const [detectedDevEnvironments, setDetectedDevEnvironments] = useState<DetectedDevEnvironment[]>([])
+
   // Collapsible sections state
   const [isTerminalSectionExpanded, setIsTerminalSectionExpanded] = useState(true)
   const [isBrowserSectionExpanded, setIsBrowserSectionExpanded] = useState(true)
@@ -53,17 +57,45 @@ export function AppConfigModal({
     }
   }, [isOpen, mode, appToEdit])
 
+  function applyTerminalDetection(detection: TerminalDetection | undefined) {
+    setAvailableTerminals(detection?.terminals ?? [])
+    setWslDistros(detection?.wslDistros ?? [])
+  }
+
   // Separate effect for loading terminals when needed
   useEffect(() => {
     if (isOpen && (mode === 'add' || (formData.appType === 'process' || formData.appType === 'both'))) {
       setIsLoadingTerminals(true)
-      invoke<TerminalInfo[]>('detect_available_terminals')
-        .then((result) => setAvailableTerminals(result))
-        .catch(() => setAvailableTerminals([]))
+      invoke<TerminalDetection>('get_terminal_detection')
+        .then(applyTerminalDetection)
+        .catch(() => applyTerminalDetection(undefined))
         .finally(() => setIsLoadingTerminals(false))
     }
   }, [isOpen, mode, formData.appType])
 
+  // Detection is cached by the backend; rescanning picks up newly installed shells
+  const rescanTerminals = () => {
+    setIsLoadingTerminals(true)
+    invoke<TerminalDetection>('refresh_terminal_detection')
+      .then(applyTerminalDetection)
+      .catch(() => applyTerminalDetection(undefined))
+      .finally(() => setIsLoadingTerminals(false))
+  }
+
+  const selectedTerminal = availableTerminals.find((t) => t.id === formData.terminalType)
+
+  // Look for .envrc, flake.nix, shell.nix and devbox.json when the working directory changes
+  useEffect(() => {
+    const workingDirectory = formData.workingDirectory.trim()
+    if (!isOpen || !workingDirectory) {
+      setDetectedDevEnvironments([])
+      return
+    }
+    invoke<DetectedDevEnvironment[]>('detect_dev_environment', { workingDirectory })
+      .then((result) => setDetectedDevEnvironments(result))
+      .catch(() => setDetectedDevEnvironments([]))
+  }, [isOpen, formData.workingDirectory])
+
   // Handle form field changes
   const handleInputChange = useCallback((field: keyof AppConfigFormData, value: string | number | boolean) => {
     setFormData(prev => ({ ...prev, [field]: value }))
@@ -318,12 +350,66 @@ export function AppConfigModal({
                           >
                             <option value="">System Default</option>
                             {availableTerminals.filter(t => t.available).map(t => (
-                              <option key={t.id} value={t.id}>
-                                {t.name}
+                              <option key={t.id} value={t.id} title={[t.path, t.version].filter(Boolean).join(' — ')}>
+                                {t.name}{t.isDefault ? ' (login shell)' : ''}
                               </option>
                             ))}
                           </select>
+                          <div className="form-help">
+                            {selectedTerminal?.path && (
+                              <>
+                                {selectedTerminal.path}
+                                {selectedTerminal.version && ` — ${selectedTerminal.version}`}{' '}
+                              </>
+                            )}
+                            <button
+                              type="button"
+                              className="link-button"
+                              onClick={rescanTerminals}
+                              disabled={isLoadingTerminals}
+                            >
+                              Rescan
+                            </button>
+                          </div>
                         </div>
+                        {formData.terminalType === 'wsl' && (
+                          <div className="form-row">
+                            <div className="form-group">
+                              <label htmlFor="wslDistro" className="form-label">
+                                WSL Distribution
+                              </label>
+                              <select
+                                id="wslDistro"
+                                className="form-input compact-select"
+                                value={formData.wslDistro}
+                                onChange={e => handleInputChange('wslDistro', e.target.value)}
+                              >
+                                <option value="">Default</option>
+                                {formData.wslDistro && !wslDistros.includes(formData.wslDistro) && (
+                                  <option value={formData.wslDistro}>{formData.wslDistro} (not installed)</option>
+                                )}
+                                {wslDistros.map(distro => (
+                                  <option key={distro} value={distro}>
+                                    {distro}
+                                  </option>
+                                ))}
+                              </select>
+                            </div>
+                            <div className="form-group">
+                              <label htmlFor="wslUser" className="form-label">
+                                WSL User
+                              </label>
+                              <input
+                                id="wslUser"
+                                type="text"
+                                className="form-input"
+                                value={formData.wslUser}
+                                onChange={e => handleInputChange('wslUser', e.target.value)}
+                                placeholder="Default user"
+                              />
+                            </div>
+                          </div>
+                        )}
                   {/* Launch Commands */}
                   <div className="form-group">
                     <label htmlFor="launchCommands" className="form-label">
@@ -348,6 +434,34 @@ yarn watch`}
                     )}
                   </div>
 
+                  {/* Launch Mode */}
+                  <div className="form-group">
+                    <div className="checkbox-group">
+                      <input
+                        id="runAsScript"
+                        type="checkbox"
+                        className="checkbox-input"
+                        checked={formData.runAsScript}
+                        onChange={(e) => handleInputChange('runAsScript', e.target.checked)}
+                      />
+                      <label htmlFor="runAsScript" className="checkbox-label">
+                        Run as a script file (for heredocs, if blocks and line continuations)
+                      </label>
+                    </div>
+                    <div className="checkbox-group">
+                      <input
+                        id="useLoginShellEnv"
+                        type="checkbox"
+                        className="checkbox-input"
+                        checked={formData.useLoginShellEnv}
+                        onChange={(e) => handleInputChange('useLoginShellEnv', e.target.checked)}
+                      />
+                      <label htmlFor="useLoginShellEnv" className="checkbox-label">
+                        Use my login shell's environment (PATH from ~/.zshrc, ~/.bashrc)
+                      </label>
+                    </div>
+                  </div>
+
                   {/* Working Directory */}
                   <div className="form-group">
                     <label htmlFor="workingDirectory" className="form-label">
@@ -379,6 +493,102 @@ yarn watch`}
                       </div>
                     )}
                   </div>
+
+                  {/* Development Environment */}
+                  <div className="form-group">
+                    <label htmlFor="devEnvironment" className="form-label">
+                      Development Environment
+                    </label>
+                    <select
+                      id="devEnvironment"
+                      className="form-input compact-select"
+                      value={formData.devEnvironment || ''}
+                      onChange={(e) => handleInputChange('devEnvironment', e.target.value)}
+                    >
+                      <option value="">None</option>
+                      <option value="direnv">direnv (direnv exec)</option>
+                      <option value="nix">Nix (nix develop / nix-shell)</option>
+                      <option value="devbox">devbox (devbox run)</option>
+                    </select>
+                    {detectedDevEnvironments.length > 0 && (
+                      <div className="form-help">
+                        Found{' '}
+                        {detectedDevEnvironments
+                          .map((d) => `${d.file}${d.installed ? '' : ` (${d.kind} not installed)`}`)
+                          .join(', ')}
+                        {!formData.devEnvironment && (
+                          <>
+                            {' '}
+                            <button
+                              type="button"
+                              className="link-button"
+                              onClick={() => handleInputChange('devEnvironment', detectedDevEnvironments[0].kind)}
+                            >
+                              Use {detectedDevEnvironments[0].kind}
+                            </button>
+                          </>
+                        )}
+                      </div>
+                    )}
+                  </div>
+
+                  {/* Hooks */}
+                  <div className="form-group">
+                    <label htmlFor="preLaunch" className="form-label">
+                      Before Launch
+                    </label>
+                    <textarea
+                      id="preLaunch"
+                      className="form-input"
+                      value={formData.preLaunch}
+                      onChange={(e) => handleInputChange('preLaunch', e.target.value)}
+                      placeholder={`Commands run to completion before launching, one per line
+git pull
+docker compose up -d db`}
+                      rows={2}
+                    />
+                    <div className="checkbox-group">
+                      <input
+                        id="abortOnPreLaunchFailure"
+                        type="checkbox"
+                        className="checkbox-input"
+                        checked={formData.abortOnPreLaunchFailure}
+                        onChange={(e) => handleInputChange('abortOnPreLaunchFailure', e.target.checked)}
+                      />
+                      <label htmlFor="abortOnPreLaunchFailure" className="checkbox-label">
+                        Don't launch if one of these fails
+                      </label>
+                    </div>
+                  </div>
+                  <div className="form-group">
+                    <label htmlFor="postStop" className="form-label">
+                      After Stop
+                    </label>
+                    <textarea
+                      id="postStop"
+                      className="form-input"
+                      value={formData.postStop}
+                      onChange={(e) => handleInputChange('postStop', e.target.value)}
+                      placeholder={`Commands run after the app has stopped, one per line
+docker compose stop db`}
+                      rows={2}
+                    />
+                  </div>
+                  <div className="form-group">
+                    <label htmlFor="hookTimeout" className="form-label">
+                      Hook Timeout (seconds)
+                    </label>
+                    <input
+                      id="hookTimeout"
+                      type="number"
+                      min="0"
+                      className="form-input"
+                      value={formData.hookTimeout || ''}
+                      onChange={(e) => handleInputChange('hookTimeout', parseInt(e.target.value) || 0)}
+                      placeholder="300"
+                    />
+                    <div className="form-help">Each before-launch or after-stop command is stopped after this long</div>
+                  </div>
                       </div>
                     </>
                   )}
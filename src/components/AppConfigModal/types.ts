@@ -2,7 +2,7 @@
  * Types for App Configuration Modal Component
  */
 
-import type { AppConfig } from '../../types'
+import type { AppConfig, DevEnvironment } from '../../types'
 import type { useConfigManager } from '../../hooks/useConfig'
 
 /**
@@ -25,6 +25,24 @@ export interface AppConfigFormData {
   tags: string[]
   /** Terminal/shell type to use for executing commands (optional) */
   terminalType?: string
+  /** WSL distribution for the wsl terminal type (empty for WSL's default) */
+  wslDistro: string
+  /** Linux user for the wsl terminal type (empty for the distribution's default) */
+  wslUser: string
+  /** Write launch commands to a script file instead of running them line by line */
+  runAsScript: boolean
+  /** Start from the login shell's environment */
+  useLoginShellEnv: boolean
+  /** Development environment tool to run the launch commands in */
+  devEnvironment?: DevEnvironment
+  /** Pre-launch commands, one per line */
+  preLaunch: string
+  /** Post-stop commands, one per line */
+  postStop: string
+  /** Per-command hook timeout in seconds (0 for the default) */
+  hookTimeout: number
+  /** Don't launch when a pre-launch command fails */
+  abortOnPreLaunchFailure: boolean
 }
 
 /**
@@ -31,9 +31,29 @@ export function appConfigToFormData(config: AppConfig): AppConfigFormData {
     browserDelay: config.browserDelay || 0,
     tags: config.tags || [],
     terminalType: config.terminalType,
+    wslDistro: config.wslDistro || '',
+    wslUser: config.wslUser || '',
+    runAsScript: config.launchMode === 'script',
+    useLoginShellEnv: config.useLoginShellEnv ?? true,
+    devEnvironment: config.devEnvironment,
+    preLaunch: (config.preLaunch || []).join('\n'),
+    postStop: (config.postStop || []).join('\n'),
+    hookTimeout: config.hookTimeout || 0,
+    abortOnPreLaunchFailure: config.abortOnPreLaunchFailure ?? true,
   }
 }
 
+/**
+ * Split a hook textarea into commands, one per non-empty line
+ */
+function hookCommands(text: string): string[] | undefined {
+  const commands = text
+    .split('\n')
+    .map((line) => line.trim())
+    .filter((line) => line)
+  return commands.length > 0 ? commands : undefined
+}
+
 /**
  * Convert form data to AppConfig
  */
@@ -70,6 +90,15 @@ export function formDataToAppConfig(
         ? formData.tags.filter((tag) => tag.trim()).map((tag) => tag.trim())
         : undefined,
     terminalType: formData.terminalType,
+    wslDistro: formData.wslDistro.trim() || undefined,
+    wslUser: formData.wslUser.trim() || undefined,
+    launchMode: formData.runAsScript ? 'script' : undefined,
+    useLoginShellEnv: formData.useLoginShellEnv ? undefined : false,
+    devEnvironment: formData.devEnvironment || undefined,
+    preLaunch: hookCommands(formData.preLaunch),
+    postStop: hookCommands(formData.postStop),
+    hookTimeout: formData.hookTimeout > 0 ? formData.hookTimeout : undefined,
+    abortOnPreLaunchFailure: formData.abortOnPreLaunchFailure ? undefined : false,
     createdAt: existingConfig?.createdAt || now,
     updatedAt: now,
     // Set lastUsedAt for new apps so they appear in the "RECENT" section initially
@@ -94,6 +123,15 @@ export const getEmptyFormData = (): AppConfigFormData => ({
   browserDelay: 0,
   tags: [],
   terminalType: undefined,
+  wslDistro: '',
+  wslUser: '',
+  runAsScript: false,
+  useLoginShellEnv: true,
+  devEnvironment: undefined,
+  preLaunch: '',
+  postStop: '',
+  hookTimeout: 0,
+  abortOnPreLaunchFailure: true,
 })
 
 /**
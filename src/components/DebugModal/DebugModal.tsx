@@ -66,6 +66,17 @@ export default function DebugModal({
     }
   }
 
+  // Picks up PATH changes made to shell profiles since the launcher started
+  const refreshShellEnvironment = async () => {
+    setIsLoading(true)
+    try {
+      await invoke('refresh_shell_environment')
+    } catch (error) {
+      console.error('Failed to refresh shell environment:', error)
+    }
+    await loadDebugInfo()
+  }
+
   const handleOpen = () => {
     if (isOpen) {
       loadDebugInfo()
@@ -212,6 +223,9 @@ export default function DebugModal({
           <button onClick={loadDebugInfo} disabled={isLoading}>
             Refresh Debug Info
           </button>
+          <button onClick={refreshShellEnvironment} disabled={isLoading}>
+            Reload Shell Environment
+          </button>
           <button onClick={onClose}>Close</button>
         </div>
       </div>
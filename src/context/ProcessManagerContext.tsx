@@ -23,6 +23,15 @@ export interface ProcessEvent {
   timestamp: string
   url?: string
   reason?: string
+  /** Launch step events: position, number of steps, command and how long it took */
+  index?: number
+  total?: number
+  command?: string
+  durationMs?: number
+  /** Hook events: which hooks ran and whether the command was killed for running too long or by a stop */
+  stage?: 'pre-launch' | 'post-stop'
+  timedOut?: boolean
+  cancelled?: boolean
 }
 
 export interface ProcessManagerAPI {
@@ -39,7 +48,8 @@ export interface ProcessManagerAPI {
     browserDelay?: number,
     portToCheck?: number,
     portCheckTimeout?: number,
-    terminalType?: string
+    terminalType?: string,
+    profile?: string
   ) => Promise<ProcessResult>
   stopProcess: (appId: string) => Promise<ProcessResult>
   getProcessStatus: (appId: string) => Promise<AppProcess | null>
@@ -120,7 +130,8 @@ export function ProcessManagerProvider({ children }: { children: React.ReactNode
     browserDelay?: number,
     portToCheck?: number,
     portCheckTimeout?: number,
-    terminalType?: string
+    terminalType?: string,
+    profile?: string
   ): Promise<ProcessResult> => {
     try {
       debugLogger.info('ProcessManager', `Starting process for app: ${appId}`, {
@@ -133,6 +144,7 @@ export function ProcessManagerProvider({ children }: { children: React.ReactNode
         portToCheck,
         portCheckTimeout,
         terminalType,
+        profile,
       })
 
       setIsLoading(true)
@@ -163,6 +175,7 @@ export function ProcessManagerProvider({ children }: { children: React.ReactNode
         portToCheck,
         portCheckTimeout,
         terminalType,
+        profile,
       })
 
       debugLogger.info('ProcessManager', `Process start result for ${appId}:`, result)
@@ -399,6 +412,79 @@ export function ProcessManagerProvider({ children }: { children: React.ReactNode
           })
         })
 
+        const unlistenStepStarted = await listen<ProcessEvent>('launch-step-started', (event) => {
+          const { appId, index, total, command, timestamp } = event.payload
+          if (!appId || index === undefined || !isMounted) return
+          setProcesses((prev) => {
+            const currentProcess = prev[appId]
+            if (!currentProcess) return prev
+            const currentStep = { index, total: total ?? 0, command: command ?? '', startedAt: timestamp }
+            return { ...prev, [appId]: { ...currentProcess, currentStep } }
+          })
+        })
+
+        const unlistenStepFinished = await listen<ProcessEvent>('launch-step-finished', (event) => {
+          const { appId, index, total, command, exitCode, durationMs, timestamp } = event.payload
+          if (!appId || index === undefined || !isMounted) return
+          setProcesses((prev) => {
+            const currentProcess = prev[appId]
+            if (!currentProcess) return prev
+            // Only failures get a line of their own; the commands themselves are already echoed
+            let output = currentProcess.output
+            if (exitCode !== 0) {
+              const seconds = ((durationMs ?? 0) / 1000).toFixed(1)
+              const result = exitCode == null ? 'ended' : `failed with exit code ${exitCode}`
+              const stepMessage = `[${toSafeTime(timestamp)}] ❌ Step ${index + 1}/${total} ${result} after ${seconds}s: ${command}`
+              output = [...output, stepMessage].slice(-1000)
+            }
+            return { ...prev, [appId]: { ...currentProcess, currentStep: undefined, output } }
+          })
+        })
+
+        // Pre-launch hooks run before the process exists, so their lines may start the entry
+        const appendHookLine = (appId: string, line: string) => {
+          setProcesses((prev) => {
+            const currentProcess = prev[appId]
+            if (!currentProcess) {
+              return {
+                ...prev,
+                [appId]: {
+                  appId,
+                  pid: undefined,
+                  status: 'starting' as AppStatus,
+                  startedAt: undefined,
+                  errorMessage: undefined,
+                  output: [line],
+                  isBackground: false,
+                },
+              }
+            }
+            const output = [...currentProcess.output, line].slice(-1000)
+            return { ...prev, [appId]: { ...currentProcess, output } }
+          })
+        }
+
+        const unlistenHookStarted = await listen<ProcessEvent>('hook-started', (event) => {
+          const { appId, stage, index, total, command, timestamp } = event.payload
+          if (!appId || index === undefined || !isMounted) return
+          appendHookLine(appId, `[${toSafeTime(timestamp)}] ▶ ${stage} ${index + 1}/${total}: ${command}`)
+        })
+
+        const unlistenHookFinished = await listen<ProcessEvent>('hook-finished', (event) => {
+          const { appId, stage, index, total, command, exitCode, timedOut, cancelled, error, durationMs, timestamp } = event.payload
+          if (!appId || index === undefined || !isMounted) return
+          if (exitCode === 0) return
+          const seconds = ((durationMs ?? 0) / 1000).toFixed(1)
+          const result = timedOut
+            ? 'timed out'
+            : cancelled
+              ? 'was cancelled'
+              : error
+                ? `could not be started (${error})`
+                : `failed with exit code ${exitCode ?? 'unknown'}`
+          appendHookLine(appId, `[${toSafeTime(timestamp)}] ❌ ${stage} ${index + 1}/${total} ${result} after ${seconds}s: ${command}`)
+        })
+
         const unlistenBrowserLaunched = await listen<ProcessEvent>('browser-launched', (event) => {
           const { appId, url, timestamp } = event.payload
           if (!appId || !url || !isMounted) return
@@ -433,6 +519,10 @@ export function ProcessManagerProvider({ children }: { children: React.ReactNode
           unlistenExit,
           unlistenError,
           unlistenOutput,
+          unlistenStepStarted,
+          unlistenStepFinished,
+          unlistenHookStarted,
+          unlistenHookFinished,
           unlistenBrowserLaunched,
           unlistenBrowserLaunchFailed,
         ]
@@ -1,6 +1,16 @@
 import { useState, useEffect, useCallback } from 'react'
 import { invoke } from '@tauri-apps/api/core'
-import type { GlobalConfig, AppConfig, AppError } from '../types'
+import { listen } from '@tauri-apps/api/event'
+import type {
+  GlobalConfig,
+  AppConfig,
+  AppError,
+  BackupInfo,
+  BackupDiff,
+  ConfigFormat,
+  ConfigHistory,
+  ValidationResult,
+} from '../types'
 
 /**
  * Hook for managing global configuration
@@ -88,6 +98,16 @@ export function useConfig() {
     loadConfig()
   }, []) // Remove loadConfig from dependencies since it's stable with empty deps
 
+  // Reload when the configuration is changed through the control API
+  useEffect(() => {
+    const unlisten = listen('config-changed', () => {
+      loadConfig()
+    })
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [loadConfig])
+
   return {
     config,
     isLoading,
@@ -214,11 +234,18 @@ export function useAppConfig() {
         return null
       }
 
+      // Pick a copy name that isn't taken yet, since the backend rejects duplicate names
+      const takenNames = new Set(config.apps.map((app) => app.name.toLowerCase()))
+      let copyName = `${originalApp.name} (Copy)`
+      for (let n = 2; takenNames.has(copyName.toLowerCase()); n++) {
+        copyName = `${originalApp.name} (Copy ${n})`
+      }
+
       // Create new app config with modified name and new ID
       const newApp: AppConfig = {
         ...originalApp,
         id: crypto.randomUUID(),
-        name: `${originalApp.name} (Copy)`,
+        name: copyName,
         createdAt: new Date().toISOString(),
         updatedAt: new Date().toISOString(),
       }
@@ -228,6 +255,21 @@ export function useAppConfig() {
     [getAppById, addApp]
   )
 
+  // Validate an app configuration without saving it
+  const validateApp = useCallback(
+    async (appConfig: AppConfig): Promise<ValidationResult | null> => {
+      try {
+        return await invoke<ValidationResult>('validate_app_config', {
+          appConfig,
+        })
+      } catch (err) {
+        console.error('Failed to validate app config:', err)
+        return null
+      }
+    },
+    []
+  )
+
   return {
     isOperating,
     error,
@@ -237,6 +279,7 @@ export function useAppConfig() {
     getAppById,
     isAppNameTaken,
     duplicateApp,
+    validateApp,
   }
 }
 
@@ -299,12 +342,105 @@ export function useConfigOperations() {
     []
   )
 
+  // Convert the configuration file to another storage format
+  const convertConfigFormat = useCallback(
+    async (format: ConfigFormat): Promise<string | null> => {
+      try {
+        setIsOperating(true)
+        setError(null)
+        return await invoke<string>('convert_config_format', { format })
+      } catch (err) {
+        console.error('Failed to convert config format:', err)
+        setError(err as AppError)
+        return null
+      } finally {
+        setIsOperating(false)
+      }
+    },
+    []
+  )
+
+  // List available backups, newest first
+  const listBackups = useCallback(async (): Promise<BackupInfo[]> => {
+    try {
+      setError(null)
+      return await invoke<BackupInfo[]>('list_backups')
+    } catch (err) {
+      console.error('Failed to list backups:', err)
+      setError(err as AppError)
+      return []
+    }
+  }, [])
+
+  // Preview what restoring a backup would change
+  const diffBackup = useCallback(
+    async (backupPath: string): Promise<BackupDiff | null> => {
+      try {
+        setError(null)
+        return await invoke<BackupDiff>('diff_backup', { backupPath })
+      } catch (err) {
+        console.error('Failed to diff backup:', err)
+        setError(err as AppError)
+        return null
+      }
+    },
+    []
+  )
+
+  // Get the configuration change history
+  const getHistory = useCallback(async (): Promise<ConfigHistory | null> => {
+    try {
+      setError(null)
+      return await invoke<ConfigHistory>('get_config_history')
+    } catch (err) {
+      console.error('Failed to get config history:', err)
+      setError(err as AppError)
+      return null
+    }
+  }, [])
+
+  // Undo the most recent configuration change
+  const undoChange = useCallback(async (): Promise<GlobalConfig | null> => {
+    try {
+      setIsOperating(true)
+      setError(null)
+      return await invoke<GlobalConfig>('undo_config_change')
+    } catch (err) {
+      console.error('Failed to undo config change:', err)
+      setError(err as AppError)
+      return null
+    } finally {
+      setIsOperating(false)
+    }
+  }, [])
+
+  // Redo the most recently undone configuration change
+  const redoChange = useCallback(async (): Promise<GlobalConfig | null> => {
+    try {
+      setIsOperating(true)
+      setError(null)
+      return await invoke<GlobalConfig>('redo_config_change')
+    } catch (err) {
+      console.error('Failed to redo config change:', err)
+      setError(err as AppError)
+      return null
+    } finally {
+      setIsOperating(false)
+    }
+  }, [])
+
   return {
     isOperating,
     error,
     getConfigInfo,
     createBackup,
     restoreFromBackup,
+    convertConfigFormat,
+    listBackups,
+    diffBackup,
+    getHistory,
+    undoChange,
+    redoChange,
   }
 }
 
@@ -366,6 +502,7 @@ export function useConfigManager() {
     ),
 
     // App utilities
+    validateApp: appConfigHook.validateApp,
     getAppById: useCallback(
       (appId: string) => appConfigHook.getAppById(configHook.config, appId),
       [appConfigHook, configHook.config]
@@ -380,6 +517,20 @@ export function useConfigManager() {
     getConfigInfo: operationsHook.getConfigInfo,
     createBackup: operationsHook.createBackup,
     restoreFromBackup: operationsHook.restoreFromBackup,
+    convertConfigFormat: operationsHook.convertConfigFormat,
+    listBackups: operationsHook.listBackups,
+    diffBackup: operationsHook.diffBackup,
+
+    // History operations with auto-refresh
+    getHistory: operationsHook.getHistory,
+    undoChange: useCallback(
+      async () => saveAppAndRefresh(() => operationsHook.undoChange()),
+      [saveAppAndRefresh, operationsHook]
+    ),
+    redoChange: useCallback(
+      async () => saveAppAndRefresh(() => operationsHook.redoChange()),
+      [saveAppAndRefresh, operationsHook]
+    ),
 
     // Loading states
     isOperating: appConfigHook.isOperating || operationsHook.isOperating,
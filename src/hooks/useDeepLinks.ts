@@ -0,0 +1,54 @@
+import { useEffect } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
+import { formatImportReport, type ImportReport, type PendingImport } from '../utils/import-export'
+
+/**
+ * Hook that asks the user to confirm imports from untrusted oddlauncher:// links
+ * and reports links that failed
+ */
+export function useDeepLinks() {
+  useEffect(() => {
+    const reviewImport = async (pending: PendingImport) => {
+      const confirmed = window.confirm(
+        `Import apps from ${pending.url}?\n\n${formatImportReport(pending.preview)}\n\n` +
+          'Only import from sources you trust.'
+      )
+
+      try {
+        if (confirmed) {
+          const report = await invoke<ImportReport>('confirm_deep_link_import', {
+            requestId: pending.requestId,
+          })
+          alert(formatImportReport(report))
+        } else {
+          await invoke('cancel_deep_link_import', { requestId: pending.requestId })
+        }
+      } catch (error) {
+        const message = (error as { message?: string })?.message
+        alert(`Import failed: ${message ?? 'Unknown error'}`)
+      }
+    }
+
+    const unlistenRequest = listen<PendingImport>('deep-link-import-request', (event) => {
+      reviewImport(event.payload)
+    })
+    const unlistenFailed = listen<{ url: string; error: string }>('deep-link-failed', (event) => {
+      alert(`Could not open ${event.payload.url}:\n${event.payload.error}`)
+    })
+
+    // Links that opened the launcher arrive before this listener exists
+    invoke<PendingImport[]>('list_pending_imports')
+      .then(async (pending) => {
+        for (const item of pending) {
+          await reviewImport(item)
+        }
+      })
+      .catch((error) => console.error('Failed to load pending imports:', error))
+
+    return () => {
+      unlistenRequest.then((fn) => fn())
+      unlistenFailed.then((fn) => fn())
+    }
+  }, [])
+}
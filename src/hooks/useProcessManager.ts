@@ -41,7 +41,8 @@ export function useAppProcess(appId: string) {
       browserDelay?: number,
       portToCheck?: number,
       portCheckTimeout?: number,
-      terminalType?: string
+      terminalType?: string,
+      profile?: string
     ) =>
       startProcess(
         appId,
@@ -53,7 +54,8 @@ export function useAppProcess(appId: string) {
         browserDelay,
         portToCheck,
         portCheckTimeout,
-        terminalType
+        terminalType,
+        profile
       ),
     [appId, startProcess]
   )
@@ -35,7 +35,8 @@ export function useAppProcess(appId: string) {
     browserDelay?: number,
     portToCheck?: number,
     portCheckTimeout?: number,
-    terminalType?: string
+    terminalType?: string,
+    profile?: string
   ) =>
     startProcess(
       appId,
@@ -47,7 +48,8 @@ export function useAppProcess(appId: string) {
       browserDelay,
       portToCheck,
       portCheckTimeout,
-      terminalType
+      terminalType,
+      profile
     )
 
   const stop = () => stopProcess(appId)
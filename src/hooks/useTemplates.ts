@@ -0,0 +1,58 @@
+import { useState, useCallback } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import type { AppConfig, AppError, AppTemplate } from '../types'
+
+/**
+ * Hook for listing app templates and creating apps from them
+ */
+export function useTemplates() {
+  const [templates, setTemplates] = useState<AppTemplate[]>([])
+  const [isLoading, setIsLoading] = useState(false)
+  const [error, setError] = useState<AppError | null>(null)
+
+  // Load built-in and user templates
+  const loadTemplates = useCallback(async (): Promise<AppTemplate[]> => {
+    try {
+      setIsLoading(true)
+      setError(null)
+      const result = await invoke<AppTemplate[]>('list_app_templates')
+      setTemplates(result)
+      return result
+    } catch (err) {
+      console.error('Failed to load templates:', err)
+      setError(err as AppError)
+      return []
+    } finally {
+      setIsLoading(false)
+    }
+  }, [])
+
+  // Build a validated (unsaved) app configuration from a template
+  const createAppFromTemplate = useCallback(
+    async (
+      templateId: string,
+      params: Record<string, string>
+    ): Promise<AppConfig | null> => {
+      try {
+        setError(null)
+        return await invoke<AppConfig>('create_app_from_template', {
+          templateId,
+          params,
+        })
+      } catch (err) {
+        console.error('Failed to create app from template:', err)
+        setError(err as AppError)
+        return null
+      }
+    },
+    []
+  )
+
+  return {
+    templates,
+    isLoading,
+    error,
+    loadTemplates,
+    createAppFromTemplate,
+  }
+}
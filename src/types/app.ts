@@ -17,6 +17,23 @@ export type AppStatus =
  */
 export type AppType = 'process' | 'bookmark' | 'both'
 
+/** inline: each line echoed and joined into one command; script: written to a temporary script file */
+export type LaunchMode = 'inline' | 'script'
+
+/** Tool that sets up a project's development environment: direnv (.envrc), nix (flake.nix or shell.nix), devbox (devbox.json) */
+export type DevEnvironment = 'direnv' | 'nix' | 'devbox'
+
+/**
+ * A development environment tool configured in a working directory
+ */
+export interface DetectedDevEnvironment {
+  kind: DevEnvironment
+  /** File that configures the tool, relative to the working directory */
+  file: string
+  /** Whether the tool's program was found on PATH */
+  installed: boolean
+}
+
 /**
  * Information about an available terminal/shell
  */
@@ -31,6 +48,50 @@ export interface TerminalInfo {
   available: boolean
   /** Platform this terminal is associated with */
   platform: string
+  /** Resolved path of the executable, when found */
+  path?: string
+  /** Version the shell reports, when it could be determined */
+  version?: string
+  /** Whether this is the user's login shell ($SHELL) */
+  isDefault: boolean
+}
+
+/**
+ * Everything terminal detection found, cached by the backend until refreshed
+ */
+export interface TerminalDetection {
+  /** Built-in, other and user-defined shells */
+  terminals: TerminalInfo[]
+  /** The user's login shell, if there is one (not on Windows) */
+  defaultShell?: string
+  /** Where Windows Terminal (wt.exe) was found, on Windows */
+  windowsTerminal?: string
+  /** Installed WSL distributions, on Windows */
+  wslDistros: string[]
+  /** When detection ran */
+  detectedAt: string
+}
+
+/**
+ * A user-defined shell that launch commands can run in
+ */
+export interface CustomShell {
+  /** Terminal type apps select it by; must not clash with a built-in one */
+  id: string
+  /** Display name */
+  name: string
+  /** Program to run, a name looked up on PATH or an absolute path */
+  executable: string
+  /** Arguments, with {script} replaced by the launch commands (e.g. ["-c", "{script}"]) */
+  args: string[]
+  /** Placed between launch commands (default: a newline) */
+  commandSeparator?: string
+  /** Statement printing a command before it runs, with {command} replaced by the quoted command (optional) */
+  echo?: string
+  /** Quoting rules for {command} (default: posix) */
+  quoting?: 'posix' | 'fish' | 'powershell' | 'cmd' | 'nu' | 'xonsh' | 'elvish'
+  /** Arguments that make the shell print its version (default: ["--version"]) */
+  versionArgs?: string[]
 }
 
 /**
@@ -49,6 +110,10 @@ export interface AppConfig {
   url?: string
   /** Environment variables to set (optional) */
   environmentVariables?: Record<string, string>
+  /** Dotenv files to load, relative to the working directory, in increasing precedence (optional) */
+  envFiles?: string[]
+  /** Environment variables whose values come from the encrypted secret store, mapped to secret names (optional) */
+  secretVariables?: Record<string, string>
   /** Auto-launch browser when app starts (default: true if url provided) */
   autoLaunchBrowser?: boolean
   /** Delay in seconds before opening browser (default: 0) */
@@ -61,6 +126,28 @@ export interface AppConfig {
   tags?: string[]
   /** Terminal/shell type to use for executing commands (optional) */
   terminalType?: string
+  /** WSL distribution the wsl terminal type runs in (optional) */
+  wslDistro?: string
+  /** Linux user the wsl terminal type runs as (optional) */
+  wslUser?: string
+  /** How launch commands are handed to the shell (default: inline) */
+  launchMode?: LaunchMode
+  /** Start from the login shell's environment rather than the launcher's own (default: true) */
+  useLoginShellEnv?: boolean
+  /** Project development environment tool to run the launch commands in (optional) */
+  devEnvironment?: DevEnvironment
+  /** Commands run one at a time before the launch commands, each to completion (optional) */
+  preLaunch?: string[]
+  /** Commands run one at a time after the app's process has ended (optional) */
+  postStop?: string[]
+  /** Maximum time each pre-launch or post-stop command may run in seconds (default: 300) */
+  hookTimeout?: number
+  /** Don't launch the app when a pre-launch command fails (default: true) */
+  abortOnPreLaunchFailure?: boolean
+  /** Named environment profiles overriding env vars, URL, port and launch commands (optional) */
+  profiles?: AppProfile[]
+  /** Profile used when no profile is requested at launch (optional) */
+  defaultProfile?: string
   /** Explicit app type (process, bookmark, or both). If missing, inferred at runtime for back-compat */
   appType?: AppType
   /** Last time the app was used (process started or bookmark opened) */
@@ -91,6 +178,72 @@ export interface AppProcess {
   output: string[]
   /** Whether the process is detached/background */
   isBackground?: boolean
+  /** Environment profile the process was started with (if any) */
+  activeProfile?: string
+  /** Launch command the process is running, when its steps can be followed */
+  currentStep?: LaunchStep
+}
+
+/**
+ * One launch command of a running app
+ */
+export interface LaunchStep {
+  /** Position among the launch commands, from 0 */
+  index: number
+  /** Number of launch commands */
+  total: number
+  /** The command line */
+  command: string
+  /** When the step started */
+  startedAt: string
+}
+
+/**
+ * Named set of overrides applied on top of an app's configuration
+ */
+export interface AppProfile {
+  /** Profile name (e.g. dev, staging, local-prod) */
+  name: string
+  /** Environment variables added to or overriding the app's (optional) */
+  environmentVariables?: Record<string, string>
+  /** URL replacing the app's URL (optional) */
+  url?: string
+  /** Port replacing the app's port to check (optional) */
+  portToCheck?: number
+  /** Launch commands replacing the app's launch commands (optional) */
+  launchCommands?: string
+}
+
+/**
+ * A parameter that fills {{name}} placeholders in a template
+ */
+export interface TemplateParameter {
+  name: string
+  /** Human-readable label for forms */
+  label?: string
+  description?: string
+  /** Value used when none is given */
+  default?: string
+  /** Whether a value must be given */
+  required: boolean
+}
+
+/**
+ * Template for creating apps for a common stack
+ */
+export interface AppTemplate {
+  id: string
+  name: string
+  description: string
+  /** Grouping for display, e.g. "JavaScript" */
+  category?: string
+  parameters: TemplateParameter[]
+  /** AppConfig fields whose strings may contain {{param}} placeholders */
+  app: Partial<Record<keyof AppConfig, unknown>>
+  /** Whether the template ships with OddLauncher */
+  builtin: boolean
+  /** File the template was loaded from (user templates only) */
+  source?: string
 }
 
 /**
@@ -123,11 +276,101 @@ export interface GlobalConfig {
     defaultBrowser?: string
     /** Auto-save configuration changes */
     autoSave: boolean
+    /** User-defined variables available to ${VAR} interpolation in every app */
+    variables?: Record<string, string>
+    /** Automatic snapshot and backup retention policy */
+    backupRetention?: BackupRetention
+    /** URL prefixes whose oddlauncher://import links are imported without confirmation */
+    trustedImportSources?: string[]
+    /** User-defined shells apps can select as their terminal type */
+    customShells?: CustomShell[]
   }
   /** Last modified timestamp */
   lastModified: string
 }
 
+/**
+ * Storage format of the main configuration file (apps.json, apps.toml or apps.yaml)
+ */
+export type ConfigFormat = 'json' | 'toml' | 'yaml'
+
+/**
+ * Retention policy for configuration backups
+ */
+export interface BackupRetention {
+  /** Snapshot the configuration automatically before destructive saves */
+  autoBackup: boolean
+  /** Number of most recent backups to always keep */
+  keepLast: number
+  /** Number of days for which one backup per day is kept */
+  keepDailyDays: number
+}
+
+/**
+ * Metadata about a configuration backup
+ */
+export interface BackupInfo {
+  /** Full path to the backup file */
+  path: string
+  fileName: string
+  kind: 'manual' | 'automatic'
+  /** When the backup was taken (ISO string) */
+  timestamp: string
+  sizeBytes: number
+  /** Number of apps in the backup (null if the file can't be parsed) */
+  appCount: number | null
+}
+
+/**
+ * Summary of one app in a backup diff
+ */
+export interface AppDiffEntry {
+  id: string
+  name: string
+  /** Top-level fields that differ (only for changed apps) */
+  changedFields: string[]
+}
+
+/**
+ * Changes that restoring a backup would make to the current configuration
+ */
+export interface BackupDiff {
+  backup: BackupInfo
+  /** Apps in the backup that don't exist in the current configuration */
+  appsAdded: AppDiffEntry[]
+  /** Apps in the current configuration that aren't in the backup */
+  appsRemoved: AppDiffEntry[]
+  /** Apps present in both with different settings */
+  appsChanged: AppDiffEntry[]
+  appsUnchanged: number
+  /** Global settings fields that differ */
+  settingsChanged: string[]
+}
+
+/**
+ * Configuration change recorded in the undo history
+ */
+export interface HistoryEntrySummary {
+  id: string
+  timestamp: string
+  operation: 'add' | 'update' | 'remove' | 'reorder' | 'settings' | 'replace'
+  description: string
+  /** Apps affected by the change */
+  appIds: string[]
+  /** Whether the change has been undone and can be redone */
+  undone: boolean
+}
+
+/**
+ * Configuration history overview
+ */
+export interface ConfigHistory {
+  /** Entries, oldest first */
+  entries: HistoryEntrySummary[]
+  canUndo: boolean
+  canRedo: boolean
+}
+
 /**
  * Error types for app operations
  */
@@ -142,6 +385,26 @@ export interface AppError {
   timestamp: string
 }
 
+/**
+ * A single validation problem tied to a config field
+ */
+export interface ValidationIssue {
+  /** Field path, e.g. "name" or "profiles[0].portToCheck" */
+  field: string
+  message: string
+}
+
+/**
+ * Result of validating an app configuration (also sent as AppError.details on VALIDATION_ERROR)
+ */
+export interface ValidationResult {
+  valid: boolean
+  /** Problems that prevent the app from being saved */
+  errors: ValidationIssue[]
+  /** Likely mistakes that don't block saving */
+  warnings: ValidationIssue[]
+}
+
 /**
  * Result type for app operations
  */
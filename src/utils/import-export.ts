@@ -1,10 +1,54 @@
+import { invoke } from '@tauri-apps/api/core'
 import { AppConfig, GlobalConfig } from '../types'
 
 export interface ExportData {
   version: string
   exportDate: string
-  type: 'single-app' | 'full-config'
-  data: AppConfig | GlobalConfig
+  type: 'single-app' | 'apps' | 'full-config'
+  data: AppConfig | AppConfig[] | GlobalConfig
+}
+
+export type ConflictStrategy = 'skip' | 'overwrite' | 'duplicate'
+
+export interface ExportOptions {
+  appIds?: string[]
+  includeSettings?: boolean
+  stripSecrets?: boolean
+  stripAbsolutePaths?: boolean
+}
+
+export interface ImportOptions {
+  strategy?: ConflictStrategy
+  dryRun?: boolean
+}
+
+export interface ImportItemResult {
+  index: number
+  id?: string
+  name?: string
+  action: 'added' | 'overwritten' | 'duplicated' | 'skipped' | 'invalid'
+  reason?: string
+  errors: string[]
+}
+
+export interface ImportReport {
+  total: number
+  added: number
+  overwritten: number
+  duplicated: number
+  skipped: number
+  invalid: number
+  dryRun: boolean
+  items: ImportItemResult[]
+}
+
+/** An import from an untrusted oddlauncher://import link awaiting confirmation */
+export interface PendingImport {
+  requestId: string
+  /** Where the import document was downloaded from */
+  url: string
+  /** What importing would do, from a dry run */
+  preview: ImportReport
 }
 
 export interface ImportResult {
@@ -42,6 +86,44 @@ export function exportFullConfig(config: GlobalConfig): string {
   return JSON.stringify(exportData, null, 2)
 }
 
+/**
+ * Export apps through the backend, optionally stripping secrets and machine-specific paths
+ */
+export function exportApps(options: ExportOptions = {}): Promise<string> {
+  return invoke<string>('export_apps', { options })
+}
+
+/**
+ * Import apps through the backend, merging them into the saved configuration
+ */
+export function importApps(
+  content: string,
+  options: ImportOptions = {}
+): Promise<ImportReport> {
+  return invoke<ImportReport>('import_apps', { content, options })
+}
+
+/**
+ * Summarize an import report for display
+ */
+export function formatImportReport(report: ImportReport): string {
+  const lines = [
+    `${report.dryRun ? 'Would import' : 'Imported'} ${report.added + report.overwritten + report.duplicated} of ${report.total} apps` +
+      ` (${report.added} added, ${report.overwritten} overwritten, ${report.duplicated} duplicated,` +
+      ` ${report.skipped} skipped, ${report.invalid} invalid)`,
+  ]
+
+  for (const item of report.items) {
+    if (item.action === 'invalid') {
+      lines.push(`- #${item.index + 1} ${item.name ?? ''}: ${item.errors.join('; ')}`)
+    } else if (item.reason) {
+      lines.push(`- ${item.name}: ${item.action} (${item.reason})`)
+    }
+  }
+
+  return lines.join('\n')
+}
+
 /**
  * Parse and validate imported JSON data
  */
@@ -61,10 +143,10 @@ export function parseImportData(jsonString: string): {
       }
     }
 
-    if (data.type !== 'single-app' && data.type !== 'full-config') {
+    if (data.type !== 'single-app' && data.type !== 'apps' && data.type !== 'full-config') {
       return {
         success: false,
-        error: 'Invalid export type. Must be "single-app" or "full-config".',
+        error: 'Invalid export type. Must be "single-app", "apps" or "full-config".',
       }
     }
 